@@ -0,0 +1,223 @@
+//! Import of boolean circuits in Bristol Fashion
+//! (<https://nigelsmart.github.io/MPC-Circuits/>).
+//!
+//! `XOR`, `AND`/`MAND` and `INV` map to `Xor`, `Mul` and `Not` gates; `EQW`
+//! aliases a wire and `EQ` binds it to a constant input. Gates are levelized
+//! as soon as possible and long wires are carried up by `Relay` gates.
+//!
+//! The layered circuit does not depend on the input bits: like
+//! [`crate::R1cs::assignment`], [`Bristol::assignment`] turns them into the
+//! layer‑0 values to prove with.
+
+use std::io::{BufRead, BufReader, Read};
+
+use field::FieldElement;
+
+use crate::gate::{Gate, GateType};
+use crate::layer::Circuit;
+use crate::levelize;
+use crate::Error;
+
+fn err(msg: impl Into<String>) -> Error {
+    Error::Parse(msg.into())
+}
+
+fn ints(line: &str) -> Result<Vec<usize>, Error> {
+    line.split_whitespace()
+        .map(|t| t.parse().map_err(|_| err(format!("bad integer {t:?}"))))
+        .collect()
+}
+
+/// Node computing wire `w`.
+fn node_of(wire: &[Option<usize>], w: usize) -> Result<usize, Error> {
+    wire.get(w)
+        .copied()
+        .flatten()
+        .ok_or_else(|| err(format!("wire {w} read before it is assigned")))
+}
+
+/// Record that wire `w` is computed by `node`; Bristol wires are assigned once.
+fn assign(wire: &mut [Option<usize>], w: usize, node: usize) -> Result<(), Error> {
+    match wire.get_mut(w) {
+        Some(slot @ None) => {
+            *slot = Some(node);
+            Ok(())
+        }
+        Some(Some(_)) => Err(err(format!("wire {w} assigned twice"))),
+        None => Err(err(format!("wire {w} out of range"))),
+    }
+}
+
+/// A parsed Bristol Fashion circuit.
+pub struct Bristol {
+    /// Bits of every input value, in order.
+    pub input_sizes: Vec<usize>,
+    /// Bits of every output value, in order.
+    pub output_sizes: Vec<usize>,
+    /// DAG nodes: the input wires, the constants 0 and 1, then the gates.
+    nodes: Vec<Gate>,
+    outputs: Vec<usize>,
+}
+
+impl Bristol {
+    pub fn from_reader<R: Read>(r: R) -> Result<Self, Error> {
+        let mut lines = BufReader::new(r)
+            .lines()
+            .filter(|l| !matches!(l, Ok(l) if l.trim().is_empty()));
+        let mut header = || -> Result<Vec<usize>, Error> {
+            ints(&lines.next().ok_or_else(|| err("truncated header"))??)
+        };
+
+        let sizes = header()?;
+        let [num_gates, num_wires] = sizes[..] else {
+            return Err(err("expected `<gates> <wires>`"));
+        };
+        let in_sizes = header()?;
+        let out_sizes = header()?;
+        let num_in: usize = in_sizes.iter().skip(1).sum();
+        let num_out: usize = out_sizes.iter().skip(1).sum();
+        if in_sizes.first() != Some(&(in_sizes.len() - 1))
+            || out_sizes.first() != Some(&(out_sizes.len() - 1))
+        {
+            return Err(err("input/output value counts do not match"));
+        }
+        if num_in + num_out > num_wires {
+            return Err(err("more input/output wires than wires"));
+        }
+
+        // DAG nodes: inputs first (node i = input wire i), then two constants;
+        // input values are filled in by `assignment`
+        let mut nodes = vec![
+            Gate {
+                ty: GateType::Input,
+                u: 0,
+                v: 0,
+            };
+            num_in
+        ];
+        let consts = [num_in, num_in + 1];
+        for c in 0..2 {
+            nodes.push(Gate {
+                ty: GateType::Input,
+                u: c,
+                v: 0,
+            });
+        }
+
+        // wire → node computing it
+        let mut wire = vec![None::<usize>; num_wires];
+        for (w, slot) in wire.iter_mut().enumerate().take(num_in) {
+            *slot = Some(w);
+        }
+
+        for _ in 0..num_gates {
+            let line = lines.next().ok_or_else(|| err("truncated gate list"))??;
+            let mut tok = line.split_whitespace();
+            let op = tok.next_back().ok_or_else(|| err("empty gate line"))?;
+            let args = ints(&tok.collect::<Vec<_>>().join(" "))?;
+            let (&n_in, &n_out) = match &args[..] {
+                [i, o, ..] if args.len() == 2 + i + o => (i, o),
+                _ => return Err(err(format!("malformed gate line {line:?}"))),
+            };
+            let (ins, outs) = args[2..].split_at(n_in);
+
+            match (op, n_in, n_out) {
+                ("XOR" | "AND", 2, 1) => {
+                    let ty = if op == "XOR" {
+                        GateType::Xor
+                    } else {
+                        GateType::Mul
+                    };
+                    let u = node_of(&wire, ins[0])?;
+                    let v = node_of(&wire, ins[1])?;
+                    nodes.push(Gate { ty, u, v });
+                    assign(&mut wire, outs[0], nodes.len() - 1)?;
+                }
+                ("INV", 1, 1) => {
+                    let u = node_of(&wire, ins[0])?;
+                    nodes.push(Gate {
+                        ty: GateType::Not,
+                        u,
+                        v: u,
+                    });
+                    assign(&mut wire, outs[0], nodes.len() - 1)?;
+                }
+                ("EQW", 1, 1) => {
+                    let src = node_of(&wire, ins[0])?;
+                    assign(&mut wire, outs[0], src)?;
+                }
+                ("EQ", 1, 1) => match ins[0] {
+                    b @ (0 | 1) => assign(&mut wire, outs[0], consts[b])?,
+                    _ => return Err(err("EQ constant must be 0 or 1")),
+                },
+                ("MAND", _, k) if n_in == 2 * k => {
+                    for i in 0..k {
+                        let u = node_of(&wire, ins[i])?;
+                        let v = node_of(&wire, ins[k + i])?;
+                        nodes.push(Gate {
+                            ty: GateType::Mul,
+                            u,
+                            v,
+                        });
+                        assign(&mut wire, outs[i], nodes.len() - 1)?;
+                    }
+                }
+                _ => {
+                    return Err(err(format!(
+                        "unsupported gate {op} ({n_in} in, {n_out} out)"
+                    )))
+                }
+            }
+        }
+
+        let outputs = (num_wires - num_out..num_wires)
+            .map(|w| node_of(&wire, w))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            input_sizes: in_sizes[1..].to_vec(),
+            output_sizes: out_sizes[1..].to_vec(),
+            nodes,
+            outputs,
+        })
+    }
+
+    pub fn load<P: AsRef<std::path::Path>>(p: P) -> Result<Self, Error> {
+        Self::from_reader(std::fs::File::open(p)?)
+    }
+
+    /// Number of input wires.
+    pub fn num_inputs(&self) -> usize {
+        self.input_sizes.iter().sum()
+    }
+
+    /// The layered circuit. Layer 0 holds the input wires in order, then the
+    /// constants 0 and 1; the output layer holds the output wires in order.
+    pub fn to_circuit(&self) -> Circuit {
+        let level = levelize::asap(&self.nodes);
+        levelize::layered(&self.nodes, &level, &self.outputs, &[])
+    }
+
+    /// Layer‑0 values for `inputs`, one bit per input wire (all input values
+    /// concatenated in wire order).
+    pub fn assignment(&self, inputs: &[bool]) -> Result<Vec<FieldElement>, Error> {
+        if inputs.len() != self.num_inputs() {
+            return Err(err(format!(
+                "circuit has {} input wires, got {} bits",
+                self.num_inputs(),
+                inputs.len()
+            )));
+        }
+        let bits = inputs.iter().chain(&[false, true]);
+        Ok(bits.map(|&b| FieldElement::from(b as u64)).collect())
+    }
+}
+
+/// Load a Bristol circuit and the layer‑0 values for `inputs`.
+pub fn load_bristol<P: AsRef<std::path::Path>>(
+    p: P,
+    inputs: &[bool],
+) -> Result<(Circuit, Vec<FieldElement>), Error> {
+    let bristol = Bristol::load(p)?;
+    let values = bristol.assignment(inputs)?;
+    Ok((bristol.to_circuit(), values))
+}
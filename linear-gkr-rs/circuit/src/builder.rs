@@ -0,0 +1,130 @@
+//! Layered circuits from straight‑line arithmetic on wires.
+//!
+//! Every operation adds one node to a gate DAG, placed one level above its
+//! deepest operand; [`Builder::build`] moves gates up towards their readers
+//! where that saves relays and lays the DAG out like the DSL does,
+//! with `Relay` gates carrying values across levels. Inputs are layer‑0 slots
+//! in creation order, their values supplied at evaluation time.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use field::FieldElement;
+
+use crate::gate::{Gate, GateType};
+use crate::layer::Circuit;
+use crate::levelize;
+
+/// A value computed by a [`Builder`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Wire(usize);
+
+#[derive(Default)]
+pub struct Builder {
+    nodes: Vec<Gate>,
+    level: Vec<usize>,
+    constants: Vec<FieldElement>,
+    outputs: Vec<usize>,
+    /// Output slots added by [`Builder::assert_zero`].
+    assertions: Vec<usize>,
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn push(&mut self, ty: GateType, u: usize, v: usize) -> Wire {
+        let gate = Gate { ty, u, v };
+        // constants have no operands but cannot live in the input layer
+        let l = levelize::operands(&gate)
+            .map(|op| self.level[op] + 1)
+            .max()
+            .unwrap_or(if ty == GateType::Input { 0 } else { 1 });
+        self.nodes.push(gate);
+        self.level.push(l);
+        Wire(self.nodes.len() - 1)
+    }
+
+    fn push_constant(&mut self, c: FieldElement) -> usize {
+        self.constants.push(c);
+        self.constants.len() - 1
+    }
+
+    /// A new input; the `i`‑th input created is layer‑0 slot `i`.
+    pub fn input(&mut self) -> Wire {
+        self.push(GateType::Input, 0, 0)
+    }
+
+    pub fn inputs(&mut self, n: usize) -> Vec<Wire> {
+        (0..n).map(|_| self.input()).collect()
+    }
+
+    pub fn constant(&mut self, c: FieldElement) -> Wire {
+        let idx = self.push_constant(c);
+        self.push(GateType::Const, idx, 0)
+    }
+
+    pub fn add(&mut self, a: Wire, b: Wire) -> Wire {
+        self.push(GateType::Add, a.0, b.0)
+    }
+
+    pub fn sub(&mut self, a: Wire, b: Wire) -> Wire {
+        self.push(GateType::Minus, a.0, b.0)
+    }
+
+    pub fn mul(&mut self, a: Wire, b: Wire) -> Wire {
+        self.push(GateType::Mul, a.0, b.0)
+    }
+
+    /// `c · a`.
+    pub fn scale(&mut self, a: Wire, c: FieldElement) -> Wire {
+        let idx = self.push_constant(c);
+        self.push(GateType::ScalarMul, a.0, idx)
+    }
+
+    /// `a + c`.
+    pub fn add_const(&mut self, a: Wire, c: FieldElement) -> Wire {
+        let idx = self.push_constant(c);
+        self.push(GateType::AddConst, a.0, idx)
+    }
+
+    /// Sum of `wires` as a tree that always joins the two shallowest
+    /// operands; zero if `wires` is empty.
+    pub fn sum(&mut self, wires: &[Wire]) -> Wire {
+        let mut heap: BinaryHeap<_> = wires
+            .iter()
+            .map(|w| Reverse((self.level[w.0], w.0)))
+            .collect();
+        loop {
+            let Some(Reverse((_, a))) = heap.pop() else {
+                return self.constant(FieldElement::from(0u64));
+            };
+            let Some(Reverse((_, b))) = heap.pop() else {
+                return Wire(a);
+            };
+            let w = self.add(Wire(a), Wire(b));
+            heap.push(Reverse((self.level[w.0], w.0)));
+        }
+    }
+
+    /// Append `w` to the output layer.
+    pub fn output(&mut self, w: Wire) {
+        self.outputs.push(w.0);
+    }
+
+    /// Append `w` to the output layer as an assertion: proofs claim it is
+    /// zero instead of sending its value.
+    pub fn assert_zero(&mut self, w: Wire) {
+        self.assertions.push(self.outputs.len());
+        self.outputs.push(w.0);
+    }
+
+    pub fn build(&self) -> Circuit {
+        let mut level = self.level.clone();
+        levelize::sink(&self.nodes, &mut level, &self.outputs);
+        let mut c = levelize::layered(&self.nodes, &level, &self.outputs, &self.constants);
+        c.assertions.clone_from(&self.assertions);
+        c
+    }
+}
@@ -0,0 +1,157 @@
+//! Data‑parallel circuits: `2^log_copies` copies of one template side by side.
+//!
+//! Gate `g` of copy `c` in layer `i` has id `g | c << b_i`, where `b_i` is the
+//! template's bit length, so the instance index occupies the top `log_copies`
+//! bits of every layer. Copy `c` only reads copy `c` of the layer below, hence
+//! the wiring predicate of a layer factors as
+//!
+//! ```text
+//! W(g, u, v) = W_template(g_lo, u_lo, v_lo) · eq(g_hi, u_hi, v_hi)
+//! ```
+//!
+//! with `eq(a, b, c) = Π (aₖbₖcₖ + (1 − aₖ)(1 − bₖ)(1 − cₖ))` over the instance
+//! bits, and can be evaluated at the cost of the template alone.
+
+use ark_ff::{BigInteger, PrimeField, Zero};
+use field::FieldElement;
+use sha3::{Digest, Keccak256};
+
+use crate::gate::{Gate, GateType};
+use crate::layer::{Circuit, Layer};
+use crate::wiring::{Layered, Term};
+
+pub struct DataParallel {
+    pub template: Circuit,
+    pub log_copies: usize,
+}
+
+impl DataParallel {
+    pub fn new(template: Circuit, log_copies: usize) -> Self {
+        Self {
+            template,
+            log_copies,
+        }
+    }
+
+    /// Enough copies of `template` for `instances` witnesses of it, so that
+    /// all of them are proven at once.
+    pub fn batch(template: Circuit, instances: usize) -> Self {
+        let log_copies = instances.max(1).next_power_of_two().trailing_zeros() as usize;
+        Self::new(template, log_copies)
+    }
+
+    pub fn copies(&self) -> usize {
+        1 << self.log_copies
+    }
+
+    /// Layer‑0 values for `instances` (one input vector per copy, each padded
+    /// like [`Circuit::pad_inputs`]); missing trailing copies are zero.
+    pub fn pad_inputs(&self, instances: &[Vec<FieldElement>]) -> Vec<FieldElement> {
+        assert!(
+            instances.len() <= self.copies(),
+            "{} instances for {} copies",
+            instances.len(),
+            self.copies()
+        );
+        let mut layer0 = Vec::with_capacity(self.template.layers[0].len() << self.log_copies);
+        for inputs in instances {
+            layer0.extend(self.template.pad_inputs(inputs));
+        }
+        layer0.resize(
+            self.template.layers[0].len() << self.log_copies,
+            FieldElement::zero(),
+        );
+        layer0
+    }
+
+    /// The output layer `outputs` (see [`DataParallel::pad_inputs`], and
+    /// `Proof::output_layer` for proofs with assertions) split into one slice
+    /// per copy.
+    pub fn copy_outputs<'a>(
+        &self,
+        outputs: &'a [FieldElement],
+    ) -> impl Iterator<Item = &'a [FieldElement]> {
+        let top = self.template.layers.last().expect("at least one layer");
+        outputs.chunks(1 << top.bit_length)
+    }
+
+    /// The equivalent explicit circuit, with every copy written out.
+    pub fn flatten(&self) -> Circuit {
+        let mut layers = Vec::with_capacity(self.template.layers.len());
+        for (i, t) in self.template.layers.iter().enumerate() {
+            let shift = if i == 0 {
+                0
+            } else {
+                self.template.layers[i - 1].bit_length
+            };
+            let mut layer = Layer::dummy(t.bit_length + self.log_copies);
+            layer.constants = t.constants.clone();
+            for c in 0..self.copies() {
+                let base = c << shift;
+                for (g, gate) in t.gates() {
+                    let (u, v) = match gate.ty {
+                        GateType::Input | GateType::Const | GateType::Dummy => (gate.u, gate.v),
+                        GateType::ScalarMul | GateType::AddConst => (gate.u + base, gate.v),
+                        _ => (gate.u + base, gate.v + base),
+                    };
+                    layer.set(g | c << t.bit_length, &Gate { ty: gate.ty, u, v });
+                }
+            }
+            layers.push(layer);
+        }
+        Circuit {
+            layers,
+            assertions: self.assertions(),
+        }
+    }
+}
+
+impl Layered for DataParallel {
+    fn depth(&self) -> usize {
+        self.template.layers.len()
+    }
+
+    fn bit_length(&self, layer: usize) -> usize {
+        self.template.layers[layer].bit_length + self.log_copies
+    }
+
+    /// The template's terms are computed once and replayed for every copy.
+    fn for_each_term(&self, layer: usize, f: &mut dyn FnMut(usize, Term)) {
+        let mut terms = Vec::new();
+        self.template
+            .for_each_term(layer, &mut |g, t| terms.push((g, t)));
+        let b = self.template.layers[layer].bit_length;
+        let b_prev = self.template.layers[layer - 1].bit_length;
+        for c in 0..self.copies() {
+            for &(g, t) in &terms {
+                f(
+                    g | c << b,
+                    Term {
+                        u: t.u | c << b_prev,
+                        v: t.v | c << b_prev,
+                        ..t
+                    },
+                );
+            }
+        }
+    }
+
+    /// The template's assertions in every copy.
+    fn assertions(&self) -> Vec<usize> {
+        let top = self.template.layers.last().expect("at least one layer");
+        let b = top.bit_length;
+        (0..self.copies())
+            .flat_map(|c| self.template.assertions.iter().map(move |&g| g | c << b))
+            .collect()
+    }
+
+    /// The template's digest and the number of copies, so that it does not
+    /// take a pass over every copy.
+    fn digest(&self) -> FieldElement {
+        let mut h = Keccak256::new();
+        h.update(b"linear-gkr-data-parallel");
+        h.update(self.template.digest().into_bigint().to_bytes_be());
+        h.update((self.log_copies as u64).to_be_bytes());
+        FieldElement::from_be_bytes_mod_order(&h.finalize())
+    }
+}
@@ -0,0 +1,413 @@
+//! A small arithmetic language compiled to layered circuits.
+//!
+//! ```text
+//! input x, y, z;          # inputs, in layer‑0 order; must come first
+//! let a = x * y + 3;      # let‑bindings are shared, never recomputed
+//! let b = -(a - z) * a;
+//! output a, b * b;        # outputs, in output‑layer order
+//! ```
+//!
+//! Expressions are lowered to a gate DAG with common subexpressions merged.
+//! Chains of `+`/`-` and of `*` are flattened and rebuilt as balanced trees
+//! that combine the shallowest operands first, which minimizes their depth;
+//! the DAG is then levelized with `Relay` gates carrying long wires.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use field::FieldElement;
+
+use crate::gate::{Gate, GateType};
+use crate::layer::Circuit;
+use crate::levelize;
+use crate::Error;
+
+/// Deepest nesting of parentheses and unary minus the parser accepts; the
+/// parser and the lowering pass both recurse once per level.
+const MAX_DEPTH: usize = 256;
+
+/// A compiled program.
+pub struct Program {
+    pub circuit: Circuit,
+    /// Input names; input `i` is layer‑0 slot `i`.
+    pub inputs: Vec<String>,
+}
+
+impl Program {
+    /// Layer‑0 assignment for `values` (one per input, in declaration order),
+    /// with the program's constants filled in behind them.
+    pub fn assignment(&self, values: &[FieldElement]) -> Result<Vec<FieldElement>, Error> {
+        if values.len() != self.inputs.len() {
+            return Err(Error::Parse(format!(
+                "program has {} inputs, got {} values",
+                self.inputs.len(),
+                values.len()
+            )));
+        }
+        let mut layer0 = self.circuit.input_values();
+        layer0[..values.len()].copy_from_slice(values);
+        Ok(layer0)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Tok {
+    Ident(String),
+    Num(u64),
+    Sym(char),
+}
+
+fn lex(src: &str) -> Result<Vec<(Tok, usize)>, Error> {
+    let mut out = Vec::new();
+    for (n, line) in src.lines().enumerate() {
+        let line = line.split('#').next().unwrap();
+        let mut chars = line.char_indices().peekable();
+        while let Some(&(start, c)) = chars.peek() {
+            if c.is_whitespace() {
+                chars.next();
+            } else if c.is_ascii_alphanumeric() || c == '_' {
+                let mut end = start;
+                while let Some(&(i, c)) = chars.peek() {
+                    if !(c.is_ascii_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                let word = &line[start..end];
+                let tok = if c.is_ascii_digit() {
+                    Tok::Num(word.parse().map_err(|_| {
+                        Error::Parse(format!("line {}: bad number {word:?}", n + 1))
+                    })?)
+                } else {
+                    Tok::Ident(word.to_owned())
+                };
+                out.push((tok, n + 1));
+            } else if "+-*=(),;".contains(c) {
+                out.push((Tok::Sym(c), n + 1));
+                chars.next();
+            } else {
+                return Err(Error::Parse(format!("line {}: unexpected {c:?}", n + 1)));
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Parsed expression; names are already resolved to DAG nodes.
+enum Expr {
+    Node(usize),
+    Const(u64),
+    /// `+`/`-` chain, each term with its sign (`true` for `+`)
+    Sum(Vec<(bool, Expr)>),
+    Neg(Box<Expr>),
+    /// `*` chain
+    Product(Vec<Expr>),
+}
+
+struct Compiler {
+    toks: Vec<(Tok, usize)>,
+    pos: usize,
+    /// current nesting of `unary`, bounded by [`MAX_DEPTH`]
+    depth: usize,
+    env: HashMap<String, usize>,
+    inputs: Vec<String>,
+    nodes: Vec<Gate>,
+    level: Vec<usize>,
+    /// hash‑consing of `(ty, u, v)` and of constants
+    dedup: HashMap<(GateType, usize, usize), usize>,
+    consts: HashMap<u64, usize>,
+    outputs: Vec<usize>,
+}
+
+impl Compiler {
+    fn err<T>(&self, msg: impl std::fmt::Display) -> Result<T, Error> {
+        let line = self
+            .toks
+            .get(self.pos.min(self.toks.len().saturating_sub(1)))
+            .map_or(0, |t| t.1);
+        Err(Error::Parse(format!("line {line}: {msg}")))
+    }
+
+    fn peek(&self) -> Option<&Tok> {
+        self.toks.get(self.pos).map(|t| &t.0)
+    }
+
+    fn eat(&mut self, sym: char) -> bool {
+        if self.peek() == Some(&Tok::Sym(sym)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, sym: char) -> Result<(), Error> {
+        if self.eat(sym) {
+            Ok(())
+        } else {
+            self.err(format!("expected `{sym}`"))
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, Error> {
+        match self.peek() {
+            Some(Tok::Ident(name)) => {
+                let name = name.clone();
+                self.pos += 1;
+                Ok(name)
+            }
+            _ => self.err("expected identifier"),
+        }
+    }
+
+    // ───────────── parsing ─────────────
+
+    fn statement(&mut self) -> Result<(), Error> {
+        match self.ident()?.as_str() {
+            "input" => {
+                if self.nodes.len() > self.inputs.len() {
+                    return self.err("inputs must be declared before other statements");
+                }
+                loop {
+                    let name = self.ident()?;
+                    if self.env.contains_key(&name) {
+                        return self.err(format!("`{name}` already defined"));
+                    }
+                    self.env.insert(name.clone(), self.nodes.len());
+                    self.push(Gate {
+                        ty: GateType::Input,
+                        u: 0,
+                        v: 0,
+                    });
+                    self.inputs.push(name);
+                    if !self.eat(',') {
+                        break;
+                    }
+                }
+            }
+            "let" => {
+                let name = self.ident()?;
+                if self.env.contains_key(&name) {
+                    return self.err(format!("`{name}` already defined"));
+                }
+                self.expect('=')?;
+                let e = self.expr()?;
+                let node = self.lower(&e);
+                self.env.insert(name, node);
+            }
+            "output" => loop {
+                let e = self.expr()?;
+                let node = self.lower(&e);
+                self.outputs.push(node);
+                if !self.eat(',') {
+                    break;
+                }
+            },
+            other => return self.err(format!("unknown statement `{other}`")),
+        }
+        self.expect(';')
+    }
+
+    // Chains are parsed flat rather than as left‑nested binary trees, so the
+    // recursion depth of parsing and lowering is the nesting depth only.
+
+    fn expr(&mut self) -> Result<Expr, Error> {
+        let mut terms = vec![(true, self.term()?)];
+        loop {
+            if self.eat('+') {
+                terms.push((true, self.term()?));
+            } else if self.eat('-') {
+                terms.push((false, self.term()?));
+            } else if terms.len() == 1 {
+                return Ok(terms.pop().unwrap().1);
+            } else {
+                return Ok(Expr::Sum(terms));
+            }
+        }
+    }
+
+    fn term(&mut self) -> Result<Expr, Error> {
+        let mut factors = vec![self.unary()?];
+        while self.eat('*') {
+            factors.push(self.unary()?);
+        }
+        Ok(if factors.len() == 1 {
+            factors.pop().unwrap()
+        } else {
+            Expr::Product(factors)
+        })
+    }
+
+    fn unary(&mut self) -> Result<Expr, Error> {
+        if self.eat('-') {
+            let e = self.nested(Self::unary)?;
+            return Ok(Expr::Neg(Box::new(e)));
+        }
+        if self.eat('(') {
+            let e = self.nested(Self::expr)?;
+            self.expect(')')?;
+            return Ok(e);
+        }
+        match self.peek().cloned() {
+            Some(Tok::Num(c)) => {
+                self.pos += 1;
+                Ok(Expr::Const(c))
+            }
+            Some(Tok::Ident(name)) => match self.env.get(&name) {
+                Some(&node) => {
+                    self.pos += 1;
+                    Ok(Expr::Node(node))
+                }
+                None => self.err(format!("undefined `{name}`")),
+            },
+            _ => self.err("expected expression"),
+        }
+    }
+
+    /// Run `parse` one nesting level deeper.
+    fn nested(&mut self, parse: fn(&mut Self) -> Result<Expr, Error>) -> Result<Expr, Error> {
+        if self.depth == MAX_DEPTH {
+            return self.err(format!("expression nested deeper than {MAX_DEPTH}"));
+        }
+        self.depth += 1;
+        let e = parse(self);
+        self.depth -= 1;
+        e
+    }
+
+    // ───────────── lowering ─────────────
+
+    fn push(&mut self, gate: Gate) -> usize {
+        let l = levelize::operands(&gate)
+            .map(|op| self.level[op] + 1)
+            .max()
+            .unwrap_or(0);
+        self.nodes.push(gate);
+        self.level.push(l);
+        self.nodes.len() - 1
+    }
+
+    fn constant(&mut self, c: u64) -> usize {
+        if let Some(&node) = self.consts.get(&c) {
+            return node;
+        }
+        let node = self.push(Gate {
+            ty: GateType::Input,
+            u: c as usize,
+            v: 0,
+        });
+        self.consts.insert(c, node);
+        node
+    }
+
+    fn gate(&mut self, ty: GateType, mut u: usize, mut v: usize) -> usize {
+        if matches!(ty, GateType::Add | GateType::Mul) && u > v {
+            std::mem::swap(&mut u, &mut v);
+        }
+        if let Some(&node) = self.dedup.get(&(ty, u, v)) {
+            return node;
+        }
+        let node = self.push(Gate { ty, u, v });
+        self.dedup.insert((ty, u, v), node);
+        node
+    }
+
+    /// Combine `operands` with `ty`, always joining the two shallowest.
+    fn balance(&mut self, ty: GateType, operands: Vec<usize>) -> usize {
+        let mut heap: BinaryHeap<_> = operands
+            .into_iter()
+            .map(|n| Reverse((self.level[n], n)))
+            .collect();
+        loop {
+            let Reverse((_, a)) = heap.pop().expect("non‑empty operand list");
+            let Some(Reverse((_, b))) = heap.pop() else {
+                return a;
+            };
+            let n = self.gate(ty, a, b);
+            heap.push(Reverse((self.level[n], n)));
+        }
+    }
+
+    fn sum_terms(&mut self, e: &Expr, positive: bool, pos: &mut Vec<usize>, neg: &mut Vec<usize>) {
+        match e {
+            Expr::Sum(terms) => {
+                for (sign, t) in terms {
+                    self.sum_terms(t, positive == *sign, pos, neg);
+                }
+            }
+            Expr::Neg(a) => self.sum_terms(a, !positive, pos, neg),
+            _ => {
+                let n = self.lower(e);
+                if positive {
+                    pos.push(n)
+                } else {
+                    neg.push(n)
+                }
+            }
+        }
+    }
+
+    fn factors(&mut self, e: &Expr, out: &mut Vec<usize>) {
+        if let Expr::Product(fs) = e {
+            for f in fs {
+                self.factors(f, out);
+            }
+        } else {
+            let n = self.lower(e);
+            out.push(n);
+        }
+    }
+
+    fn lower(&mut self, e: &Expr) -> usize {
+        match e {
+            Expr::Node(n) => *n,
+            Expr::Const(c) => self.constant(*c),
+            Expr::Product(_) => {
+                let mut fs = Vec::new();
+                self.factors(e, &mut fs);
+                self.balance(GateType::Mul, fs)
+            }
+            Expr::Sum(_) | Expr::Neg(_) => {
+                let (mut pos, mut neg) = (Vec::new(), Vec::new());
+                self.sum_terms(e, true, &mut pos, &mut neg);
+                let p = if pos.is_empty() {
+                    self.constant(0)
+                } else {
+                    self.balance(GateType::Add, pos)
+                };
+                if neg.is_empty() {
+                    return p;
+                }
+                let n = self.balance(GateType::Add, neg);
+                self.gate(GateType::Minus, p, n)
+            }
+        }
+    }
+}
+
+/// Compile DSL source to a layered circuit.
+pub fn compile(src: &str) -> Result<Program, Error> {
+    let mut c = Compiler {
+        toks: lex(src)?,
+        pos: 0,
+        depth: 0,
+        env: HashMap::new(),
+        inputs: Vec::new(),
+        nodes: Vec::new(),
+        level: Vec::new(),
+        dedup: HashMap::new(),
+        consts: HashMap::new(),
+        outputs: Vec::new(),
+    };
+    while c.pos < c.toks.len() {
+        c.statement()?;
+    }
+    if c.outputs.is_empty() {
+        return Err(Error::Parse("program has no outputs".into()));
+    }
+    Ok(Program {
+        circuit: levelize::layered(&c.nodes, &c.level, &c.outputs, &[]),
+        inputs: c.inputs,
+    })
+}
@@ -0,0 +1,131 @@
+//! Graphviz DOT and JSON views of a layered circuit for debugging.
+//!
+//! Both take a range of layers and leave out dummies (in DOT, those that the
+//! next layer does not read); edges are drawn only between gates that are
+//! both in the range.
+
+use std::fmt::Write;
+use std::ops::Range;
+
+use ark_ff::PrimeField;
+use field::FieldElement;
+use serde::Serialize;
+
+use crate::gate::GateType;
+use crate::layer::Circuit;
+
+/// Fill colour of a gate type in [`to_dot`].
+fn color(ty: GateType) -> &'static str {
+    match ty {
+        GateType::Input => "palegreen",
+        GateType::Const => "khaki",
+        GateType::Add | GateType::Sum => "lightblue",
+        GateType::Minus => "lightcyan",
+        GateType::Mul => "salmon",
+        GateType::ScalarMul | GateType::AddConst => "orange",
+        GateType::Xor | GateType::Naab | GateType::Not => "plum",
+        GateType::Relay | GateType::DirectRelay => "lightgrey",
+        GateType::Dummy => "white",
+    }
+}
+
+/// `range` clamped to the circuit's layers.
+fn clamp(c: &Circuit, range: Range<usize>) -> Range<usize> {
+    range.start.min(c.layers.len())..range.end.min(c.layers.len())
+}
+
+/// The layers in `range` as a Graphviz digraph: one rank per layer, inputs at
+/// the bottom, each gate labelled with its type and id and filled by type.
+pub fn to_dot(c: &Circuit, range: Range<usize>) -> String {
+    let range = clamp(c, range);
+    let mut out = String::from("digraph circuit {\n  rankdir=BT;\n  node [style=filled];\n");
+    for i in range.clone() {
+        let layer = &c.layers[i];
+        writeln!(out, "  subgraph layer{i} {{\n    rank=same;").unwrap();
+        // dummies stand for values only when the next layer reads them
+        let mut read = vec![false; layer.len()];
+        if range.contains(&(i + 1)) {
+            for (_, gate) in c.layers[i + 1].gates() {
+                for u in gate.inputs() {
+                    read[u] = true;
+                }
+            }
+        }
+        for (g, gate) in layer
+            .gates()
+            .filter(|&(g, ref gate)| gate.ty != GateType::Dummy || read[g])
+        {
+            writeln!(
+                out,
+                "    l{i}_{g} [label=\"{:?} {i}:{g}\", fillcolor={}];",
+                gate.ty,
+                color(gate.ty)
+            )
+            .unwrap();
+        }
+        writeln!(out, "  }}").unwrap();
+        if i == 0 || !range.contains(&(i - 1)) {
+            continue;
+        }
+        for (g, gate) in layer.gates() {
+            for u in gate.inputs() {
+                writeln!(out, "  l{}_{u} -> l{i}_{g};", i - 1).unwrap();
+            }
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+#[derive(Serialize)]
+struct JsonGate {
+    id: usize,
+    #[serde(rename = "type")]
+    ty: GateType,
+    /// Ids read from the previous layer.
+    inputs: Vec<usize>,
+    /// Decimal value of the gate's constant or written input, if it has one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    constant: Option<String>,
+}
+
+#[derive(Serialize)]
+struct JsonLayer {
+    layer: usize,
+    bit_length: usize,
+    gates: Vec<JsonGate>,
+}
+
+/// The layers in `range` as JSON: `[{layer, bit_length, gates: [{id, type,
+/// inputs, constant?}]}]`.
+pub fn to_json(c: &Circuit, range: Range<usize>) -> String {
+    let layers: Vec<_> = clamp(c, range)
+        .map(|i| {
+            let layer = &c.layers[i];
+            let gates = layer
+                .gates()
+                .filter(|(_, gate)| gate.ty != GateType::Dummy)
+                .map(|(id, gate)| {
+                    let constant = match gate.ty {
+                        GateType::Input => Some(FieldElement::from(gate.u as u64)),
+                        GateType::Const => Some(layer.constants[gate.u]),
+                        GateType::ScalarMul | GateType::AddConst => Some(layer.constants[gate.v]),
+                        _ => None,
+                    };
+                    JsonGate {
+                        id,
+                        ty: gate.ty,
+                        inputs: gate.inputs(),
+                        constant: constant.map(|x| x.into_bigint().to_string()),
+                    }
+                })
+                .collect();
+            JsonLayer {
+                layer: i,
+                bit_length: layer.bit_length,
+                gates,
+            }
+        })
+        .collect();
+    serde_json::to_string_pretty(&layers).expect("circuit serializes")
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[repr(u8)]
 pub enum GateType {
     Add = 0,
@@ -14,6 +14,12 @@ pub enum GateType {
     Xor = 8,
     Naab = 9,
     Relay = 10,
+    /// `constants[u]` of the gate's layer
+    Const = 11,
+    /// `constants[v] · V(u)`
+    ScalarMul = 12,
+    /// `V(u) + constants[v]`
+    AddConst = 13,
 }
 
 impl TryFrom<u8> for GateType {
@@ -32,6 +38,9 @@ impl TryFrom<u8> for GateType {
             8 => Xor,
             9 => Naab,
             10 => Relay,
+            11 => Const,
+            12 => ScalarMul,
+            13 => AddConst,
             _ => return Err(format!("unknown gate type {v}")),
         })
     }
@@ -45,3 +54,19 @@ pub struct Gate {
     /// second input (meaning depends on gate type)
     pub v: usize,
 }
+
+impl Gate {
+    /// Ids of the previous layer's gates this gate reads; constant pool
+    /// indices and `Input` values are not wires.
+    pub fn inputs(&self) -> Vec<usize> {
+        match self.ty {
+            GateType::Input | GateType::Dummy | GateType::Const => Vec::new(),
+            GateType::Not | GateType::Relay | GateType::DirectRelay => vec![self.u],
+            GateType::ScalarMul | GateType::AddConst => vec![self.u],
+            GateType::Sum => (self.u..self.v).collect(),
+            GateType::Add | GateType::Mul | GateType::Minus | GateType::Xor | GateType::Naab => {
+                vec![self.u, self.v]
+            }
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! Dense, structure‑of‑arrays circuit storage.
+
+use std::collections::BTreeMap;
+
+use ark_ff::Zero;
+use field::{FieldElement, FieldExt, RngCore};
+
+use crate::gate::{Gate, GateType};
+use crate::wiring::WiringPredicate;
+
+/// Address bits for a layer with `slots` gate ids: ⌈log₂(slots)⌉, but at least 1
+/// (matching the C++ reference, which never builds a zero‑variable layer).
+pub fn bit_length(slots: usize) -> usize {
+    (slots.next_power_of_two().trailing_zeros() as usize).max(1)
+}
+
+pub struct Circuit {
+    pub layers: Vec<Layer>,
+    /// Output slots asserted to be zero, in increasing order. Proofs leave
+    /// their values out and verifiers take them as zero.
+    pub assertions: Vec<usize>,
+}
+
+impl Circuit {
+    /// Layer‑0 values as written in the circuit: `Input` gates carry their value
+    /// in `u`, `Const` gates index the layer's constants, dummies are zero.
+    pub fn input_values(&self) -> Vec<FieldElement> {
+        let input_layer = &self.layers[0];
+        input_layer
+            .ty
+            .iter()
+            .zip(&input_layer.u)
+            .map(|(ty, &u)| match ty {
+                GateType::Input => FieldElement::from(u as u64),
+                GateType::Const => input_layer.constants[u],
+                GateType::Dummy => FieldElement::zero(),
+                _ => panic!("only INPUT / CONST / DUMMY allowed in layer‑0"),
+            })
+            .collect()
+    }
+
+    /// Layer‑0 values with every `Input` gate drawn from `rng` instead;
+    /// constants and dummies are as in [`Circuit::input_values`]. With a
+    /// [`field::seeded_rng`] the assignment is reproducible from its seed.
+    pub fn random_inputs<R: RngCore + ?Sized>(&self, rng: &mut R) -> Vec<FieldElement> {
+        let input_layer = &self.layers[0];
+        let mut values = self.input_values();
+        for (x, ty) in values.iter_mut().zip(&input_layer.ty) {
+            if *ty == GateType::Input {
+                *x = FieldElement::random_with(rng);
+            }
+        }
+        values
+    }
+
+    /// `inputs` zero‑padded to the size of the input layer.
+    pub fn pad_inputs(&self, inputs: &[FieldElement]) -> Vec<FieldElement> {
+        let n = self.layers[0].len();
+        assert!(
+            inputs.len() <= n,
+            "{} inputs for {n} input slots",
+            inputs.len()
+        );
+        let mut layer0 = inputs.to_vec();
+        layer0.resize(n, FieldElement::zero());
+        layer0
+    }
+}
+
+/// One circuit layer. Gate ids are indices: gate `g` is `(ty[g], u[g], v[g])`,
+/// and every slot up to `1 << bit_length` is populated (unused ones with `Dummy`).
+pub struct Layer {
+    pub ty: Vec<GateType>,
+    pub u: Vec<usize>,
+    pub v: Vec<usize>,
+    pub bit_length: usize,
+    /// Field constants referenced by `Const`, `ScalarMul` and `AddConst` gates.
+    pub constants: Vec<FieldElement>,
+    /// Closed‑form wiring predicates, if the layer is regular enough to have them.
+    pub predicate: Option<Box<dyn WiringPredicate>>,
+}
+
+impl Layer {
+    /// `1 << bit_length` dummy gates.
+    pub fn dummy(bit_length: usize) -> Self {
+        let n = 1 << bit_length;
+        Self {
+            ty: vec![GateType::Dummy; n],
+            u: vec![0; n],
+            v: vec![0; n],
+            bit_length,
+            constants: Vec::new(),
+            predicate: None,
+        }
+    }
+
+    /// Densify a sparse `id → gate` table; ids absent from `gates` become dummies.
+    ///
+    /// Panics if an id does not fit in `bit_length` bits.
+    pub fn from_sparse(gates: &BTreeMap<usize, Gate>, bit_length: usize) -> Self {
+        let mut layer = Self::dummy(bit_length);
+        for (&g, gate) in gates {
+            assert!(
+                g < layer.len(),
+                "gate id {g} does not fit in {bit_length} bits"
+            );
+            layer.set(g, gate);
+        }
+        layer
+    }
+
+    /// Number of slots, i.e. `1 << bit_length`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ty.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ty.is_empty()
+    }
+
+    #[inline]
+    pub fn gate(&self, g: usize) -> Gate {
+        Gate {
+            ty: self.ty[g],
+            u: self.u[g],
+            v: self.v[g],
+        }
+    }
+
+    #[inline]
+    pub fn set(&mut self, g: usize, gate: &Gate) {
+        self.ty[g] = gate.ty;
+        self.u[g] = gate.u;
+        self.v[g] = gate.v;
+    }
+
+    /// Add `c` to the constant pool and return its index.
+    pub fn push_constant(&mut self, c: FieldElement) -> usize {
+        self.constants.push(c);
+        self.constants.len() - 1
+    }
+
+    /// `(id, gate)` for every slot, dummies included.
+    pub fn gates(&self) -> impl Iterator<Item = (usize, Gate)> + '_ {
+        (0..self.len()).map(|g| (g, self.gate(g)))
+    }
+}
@@ -0,0 +1,218 @@
+//! Lay out a gate DAG as a layered circuit, inserting relays for long wires.
+
+use std::collections::HashMap;
+
+use field::FieldElement;
+
+use crate::gate::{Gate, GateType};
+use crate::layer::{bit_length, Circuit, Layer};
+
+/// Node ids read by `gate` (`u`/`v` name earlier DAG nodes, not slots).
+/// `Sum` gates read a range, so they are not supported here; the constant of a
+/// `Const`, `ScalarMul` or `AddConst` node indexes the table handed to
+/// [`layered`].
+pub(crate) fn operands(gate: &Gate) -> impl Iterator<Item = usize> {
+    let arity = match gate.ty {
+        GateType::Input | GateType::Dummy | GateType::Const => 0,
+        GateType::Not | GateType::Relay | GateType::DirectRelay => 1,
+        GateType::ScalarMul | GateType::AddConst => 1,
+        _ => 2,
+    };
+    [gate.u, gate.v].into_iter().take(arity)
+}
+
+/// As‑soon‑as‑possible levels: inputs at 0, every gate one above its deepest operand.
+pub(crate) fn asap(nodes: &[Gate]) -> Vec<usize> {
+    let mut level = Vec::with_capacity(nodes.len());
+    for gate in nodes {
+        let l = operands(gate).map(|op| level[op] + 1).max().unwrap_or(0);
+        level.push(l);
+    }
+    level
+}
+
+/// Move gates of a valid placement `level` up to just below their first
+/// reader, or to the output layer, together with the operands only they read,
+/// whenever that does not add slots: values such as `s + cᵢ` for many `i`, or
+/// sums of inputs, are then computed where they are read instead of being
+/// relayed there. Moves that keep the count make room for later ones.
+pub(crate) fn sink(nodes: &[Gate], level: &mut [usize], outputs: &[usize]) {
+    let depth = outputs.iter().map(|&n| level[n]).max().unwrap_or(0).max(1);
+    let mut is_output = vec![false; nodes.len()];
+    for &n in outputs {
+        is_output[n] = true;
+    }
+    let distinct = |n: usize| {
+        let mut ops: Vec<_> = operands(&nodes[n]).collect();
+        ops.dedup();
+        ops
+    };
+    let mut live = is_output.clone();
+    let mut readers = vec![Vec::new(); nodes.len()];
+    for n in (0..nodes.len()).rev() {
+        if live[n] {
+            for op in distinct(n) {
+                live[op] = true;
+                readers[op].push(n);
+            }
+        }
+    }
+    // last level reading `n`, other than `reader`
+    let read_until = |level: &[usize], n: usize, reader: usize| {
+        if is_output[n] {
+            return depth + 1;
+        }
+        readers[n]
+            .iter()
+            .filter(|&&r| r != reader)
+            .map(|&r| level[r])
+            .max()
+            .unwrap_or(0)
+    };
+
+    for n in (0..nodes.len()).rev() {
+        if !live[n] || nodes[n].ty == GateType::Input {
+            continue;
+        }
+        let mut first = readers[n].iter().map(|&r| level[r]).min();
+        if is_output[n] {
+            first = Some(first.map_or(depth + 1, |f| f.min(depth + 1)));
+        }
+        let Some(target) = first.map(|f| f - 1) else {
+            continue;
+        };
+        if target <= level[n] {
+            continue;
+        }
+        // change in the number of slots, starting with `n`'s own
+        let mut delta = level[n] as isize - target as isize;
+        let mut moves = Vec::new();
+        let mut stack = vec![(n, level[n], target)];
+        while let Some((m, old, new)) = stack.pop() {
+            moves.push((m, new));
+            for op in distinct(m) {
+                let exclusive =
+                    readers[op].len() == 1 && !is_output[op] && nodes[op].ty != GateType::Input;
+                if exclusive && new - 1 > level[op] {
+                    // [level, old) becomes [new - 1, new)
+                    delta += 1 - (old - level[op]) as isize;
+                    stack.push((op, level[op], new - 1));
+                } else {
+                    let others = read_until(level, op, m);
+                    delta += (others.max(new) - others.max(old)) as isize;
+                }
+            }
+        }
+        if delta <= 0 {
+            for (m, l) in moves {
+                level[m] = l;
+            }
+        }
+    }
+}
+
+/// Build a layered circuit from `nodes` (topologically ordered; `Input` nodes
+/// carry their value in `u` as usual) placed at `level`, whose output layer is
+/// `outputs` in order. Constant‑pool nodes index `constants`, which are copied
+/// into the pool of the layer each node lands in.
+///
+/// Layer 0 holds every input node in node order. A value computed at level `l`
+/// and last read at level `m > l + 1` is carried through levels `l+1..m` by
+/// `Relay` gates; nodes nobody reads are dropped.
+pub(crate) fn layered(
+    nodes: &[Gate],
+    level: &[usize],
+    outputs: &[usize],
+    constants: &[FieldElement],
+) -> Circuit {
+    let depth = outputs.iter().map(|&n| level[n]).max().unwrap_or(0).max(1);
+
+    // only nodes feeding an output are laid out
+    let mut live = vec![false; nodes.len()];
+    for &n in outputs {
+        live[n] = true;
+    }
+    for n in (0..nodes.len()).rev() {
+        if live[n] {
+            for src in operands(&nodes[n]) {
+                live[src] = true;
+            }
+        }
+    }
+
+    // last level at which each node must still occupy a slot (exclusive)
+    let mut last_use = vec![None::<usize>; nodes.len()];
+    for (n, gate) in nodes.iter().enumerate().filter(|&(n, _)| live[n]) {
+        for src in operands(gate) {
+            debug_assert!(level[src] < level[n], "operand not below its gate");
+            last_use[src] = last_use[src].max(Some(level[n]));
+        }
+    }
+    for &n in outputs {
+        last_use[n] = last_use[n].max(Some(depth));
+    }
+
+    // slots[l] = nodes resident in layer l, in node order
+    let mut slots = vec![Vec::new(); depth];
+    for (n, gate) in nodes.iter().enumerate() {
+        let mut end = last_use[n].unwrap_or(0);
+        if gate.ty == GateType::Input {
+            end = end.max(1); // inputs keep their layer‑0 slot even if unread
+        }
+        for layer in slots.iter_mut().take(end).skip(level[n]) {
+            layer.push(n);
+        }
+    }
+
+    let place = |n: usize, l: usize, prev: &HashMap<usize, usize>, layer: &mut Layer| -> Gate {
+        if level[n] == l {
+            let ty = nodes[n].ty;
+            let mut ops = operands(&nodes[n]).map(|op| prev[&op]);
+            let u = ops.next().unwrap_or(0);
+            let (u, v) = match ty {
+                GateType::Const => (layer.push_constant(constants[nodes[n].u]), 0),
+                GateType::ScalarMul | GateType::AddConst => {
+                    (u, layer.push_constant(constants[nodes[n].v]))
+                }
+                _ => (u, ops.next().unwrap_or(u)),
+            };
+            Gate { ty, u, v }
+        } else {
+            Gate {
+                ty: GateType::Relay,
+                u: prev[&n],
+                v: 0,
+            }
+        }
+    };
+
+    let mut layers = Vec::with_capacity(depth + 1);
+    let mut prev = HashMap::new();
+    for (l, resident) in slots.iter().enumerate() {
+        let mut layer = Layer::dummy(bit_length(resident.len()));
+        let mut pos = HashMap::with_capacity(resident.len());
+        for (g, &n) in resident.iter().enumerate() {
+            let gate = if l == 0 {
+                nodes[n].clone()
+            } else {
+                place(n, l, &prev, &mut layer)
+            };
+            layer.set(g, &gate);
+            pos.insert(n, g);
+        }
+        layers.push(layer);
+        prev = pos;
+    }
+
+    let mut out = Layer::dummy(bit_length(outputs.len()));
+    for (g, &n) in outputs.iter().enumerate() {
+        let gate = place(n, depth, &prev, &mut out);
+        out.set(g, &gate);
+    }
+    layers.push(out);
+
+    Circuit {
+        layers,
+        assertions: Vec::new(),
+    }
+}
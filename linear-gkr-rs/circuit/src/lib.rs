@@ -1,10 +1,37 @@
 //! Public API for circuits + parsing utilities.
 
+mod bristol;
+mod builder;
+mod data_parallel;
+mod dsl;
+mod export;
 mod gate;
+mod layer;
+mod levelize;
+mod optimize;
 mod parse;
+mod r1cs;
+mod relevel;
+mod stats;
+mod structured;
+mod wiring;
 
+pub use bristol::{load_bristol, Bristol};
+pub use builder::{Builder, Wire};
+pub use data_parallel::DataParallel;
+pub use dsl::{compile, Program};
+pub use export::{to_dot, to_json};
 pub use gate::{Gate, GateType};
-pub use parse::{load_from_path, Circuit, Layer};
+pub use layer::{bit_length, Circuit, Layer};
+pub use optimize::{optimize, LayerChanges, OptimizeReport};
+pub use parse::{
+    load_from_path, save_to_path, write_text, Error as ParseError, LayerReader, MAX_BIT_LENGTH,
+};
+pub use r1cs::{load_circom, load_wtns, read_wtns, Constraint, LinearCombination, R1cs};
+pub use relevel::{relevel, RelevelReport};
+pub use stats::{CircuitStats, LayerStats};
+pub use structured::{BinaryTree, ElementWise};
+pub use wiring::{Layered, Term, Wiring, WiringPredicate};
 
 /// Top‑level error type for the crate.
 #[derive(thiserror::Error, Debug)]
@@ -0,0 +1,272 @@
+//! Optimization passes over layered circuits.
+//!
+//! [`optimize`] runs, in order:
+//!
+//! 1. dead‑gate elimination: gates that no live gate of the next layer reads
+//!    become dummies, from the outputs down; dummies that a live gate reads
+//!    become constant zeros, so that they survive compaction;
+//! 2. deduplication: gates computing the same `(ty, u, v)` (with operands of
+//!    commutative gates sorted and constants compared by value) are merged
+//!    into the first, from the inputs up so merges below expose more above;
+//! 3. compaction: the remaining gates of each layer are renumbered
+//!    `0, 1, …` in id order and `bit_length` shrinks to fit them.
+//!
+//! The input and output layers keep their ids, since they are the circuit's
+//! interface, and so does any layer read by a `Sum` gate, whose operand range
+//! must stay contiguous. Those layers only lose dead gates.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use field::FieldElement;
+
+use crate::gate::{Gate, GateType};
+use crate::layer::{bit_length, Circuit, Layer};
+
+/// A gate with its constant resolved out of the layer's pool.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct Node {
+    ty: GateType,
+    u: usize,
+    v: usize,
+    constant: Option<FieldElement>,
+}
+
+impl Node {
+    fn new(layer: &Layer, g: usize) -> Option<Self> {
+        let Gate { ty, u, v } = layer.gate(g);
+        let (u, v, constant) = match ty {
+            GateType::Dummy => return None,
+            GateType::Const => (0, 0, Some(layer.constants[u])),
+            GateType::ScalarMul | GateType::AddConst => (u, 0, Some(layer.constants[v])),
+            _ => (u, v, None),
+        };
+        Some(Self { ty, u, v, constant })
+    }
+
+    fn gate(&self) -> Gate {
+        Gate {
+            ty: self.ty,
+            u: self.u,
+            v: self.v,
+        }
+    }
+
+    /// Operands renamed through `map` (old id → new id of the layer below).
+    fn remap(&mut self, map: &[usize]) {
+        match self.ty {
+            GateType::Input | GateType::Dummy | GateType::Const => {}
+            // pinned layers map to themselves, so the range is unchanged
+            GateType::Sum => {}
+            GateType::Not | GateType::Relay | GateType::DirectRelay => self.u = map[self.u],
+            GateType::ScalarMul | GateType::AddConst => self.u = map[self.u],
+            GateType::Add | GateType::Mul | GateType::Minus | GateType::Xor | GateType::Naab => {
+                (self.u, self.v) = (map[self.u], map[self.v]);
+            }
+        }
+    }
+
+    /// `self` with the operands of commutative gates in order, for comparison.
+    fn canonical(mut self) -> Self {
+        if matches!(self.ty, GateType::Add | GateType::Mul | GateType::Xor) && self.u > self.v {
+            (self.u, self.v) = (self.v, self.u);
+        }
+        self
+    }
+}
+
+/// What [`optimize`] did to one layer.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LayerChanges {
+    /// Dead gates removed.
+    pub dead: usize,
+    /// Duplicates merged into an earlier gate.
+    pub merged: usize,
+    pub bit_length_before: usize,
+    pub bit_length_after: usize,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OptimizeReport {
+    pub layers: Vec<LayerChanges>,
+}
+
+impl OptimizeReport {
+    pub fn dead(&self) -> usize {
+        self.layers.iter().map(|l| l.dead).sum()
+    }
+
+    pub fn merged(&self) -> usize {
+        self.layers.iter().map(|l| l.merged).sum()
+    }
+
+    pub fn slots_before(&self) -> usize {
+        self.layers.iter().map(|l| 1 << l.bit_length_before).sum()
+    }
+
+    pub fn slots_after(&self) -> usize {
+        self.layers.iter().map(|l| 1 << l.bit_length_after).sum()
+    }
+}
+
+impl fmt::Display for OptimizeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, l) in self.layers.iter().enumerate() {
+            if l.dead == 0 && l.merged == 0 && l.bit_length_before == l.bit_length_after {
+                continue;
+            }
+            writeln!(
+                f,
+                "layer {i}: {} dead, {} merged, bit_length {} -> {}",
+                l.dead, l.merged, l.bit_length_before, l.bit_length_after
+            )?;
+        }
+        write!(
+            f,
+            "removed {} dead and {} duplicate gates; {} -> {} slots",
+            self.dead(),
+            self.merged(),
+            self.slots_before(),
+            self.slots_after()
+        )
+    }
+}
+
+/// Optimize `c` (see the module docs). The result computes the same outputs
+/// from the same inputs; layers whose gates come out unchanged are passed
+/// through as they are, wiring predicates included.
+pub fn optimize(c: Circuit) -> (Circuit, OptimizeReport) {
+    let depth = c.layers.len();
+    let top = depth - 1;
+    let mut nodes: Vec<Vec<Option<Node>>> = c
+        .layers
+        .iter()
+        .map(|layer| (0..layer.len()).map(|g| Node::new(layer, g)).collect())
+        .collect();
+    let pinned: Vec<bool> = (0..depth)
+        .map(|i| i == 0 || i == top || c.layers[i + 1].ty.contains(&GateType::Sum))
+        .collect();
+    let mut report = OptimizeReport {
+        layers: c
+            .layers
+            .iter()
+            .map(|l| LayerChanges {
+                bit_length_before: l.bit_length,
+                ..Default::default()
+            })
+            .collect(),
+    };
+
+    eliminate_dead(&mut nodes, &pinned, &mut report);
+    merge_duplicates(&mut nodes, &pinned, &mut report);
+    compact(&mut nodes, &pinned);
+
+    let assertions = c.assertions;
+    let layers = c
+        .layers
+        .into_iter()
+        .zip(nodes)
+        .enumerate()
+        .map(|(i, (original, nodes))| {
+            let bits = if pinned[i] {
+                original.bit_length
+            } else {
+                bit_length(nodes.iter().flatten().count())
+            };
+            report.layers[i].bit_length_after = bits;
+            let unchanged = bits == original.bit_length
+                && nodes
+                    .iter()
+                    .enumerate()
+                    .all(|(g, node)| *node == Node::new(&original, g));
+            if unchanged {
+                return original;
+            }
+            let mut layer = Layer::dummy(bits);
+            for (g, node) in nodes.iter().enumerate() {
+                let Some(node) = node else { continue };
+                let mut gate = node.gate();
+                match (node.ty, node.constant) {
+                    (GateType::Const, Some(k)) => gate.u = layer.push_constant(k),
+                    (_, Some(k)) => gate.v = layer.push_constant(k),
+                    _ => {}
+                }
+                layer.set(g, &gate);
+            }
+            layer
+        })
+        .collect();
+    (Circuit { layers, assertions }, report)
+}
+
+/// Pass 1: from the outputs down, drop gates that no live gate reads, and
+/// give dummies that live gates read, which hold zero, a node of their own.
+fn eliminate_dead(nodes: &mut [Vec<Option<Node>>], pinned: &[bool], report: &mut OptimizeReport) {
+    for i in (1..nodes.len() - 1).rev() {
+        let mut live = vec![false; nodes[i].len()];
+        for node in nodes[i + 1].iter().flatten() {
+            for u in node.gate().inputs() {
+                live[u] = true;
+            }
+        }
+        for (slot, live) in nodes[i].iter_mut().zip(live) {
+            if !live && slot.take().is_some() {
+                report.layers[i].dead += 1;
+            }
+            // pinned layers keep their ids, so their dummies stay in place
+            if live && slot.is_none() && !pinned[i] {
+                *slot = Some(Node {
+                    ty: GateType::Const,
+                    u: 0,
+                    v: 0,
+                    constant: Some(FieldElement::from(0u64)),
+                });
+            }
+        }
+    }
+}
+
+/// Pass 2: from the inputs up, merge gates equal up to operand order into the
+/// first of them, redirecting the layer above.
+fn merge_duplicates(nodes: &mut [Vec<Option<Node>>], pinned: &[bool], report: &mut OptimizeReport) {
+    let mut alias: Vec<usize> = (0..nodes[0].len()).collect();
+    for i in 1..nodes.len() {
+        let mut seen = HashMap::new();
+        let mut next: Vec<usize> = (0..nodes[i].len()).collect();
+        for (g, slot) in nodes[i].iter_mut().enumerate() {
+            let Some(node) = slot else { continue };
+            node.remap(&alias);
+            if pinned[i] {
+                continue;
+            }
+            let first = *seen.entry(node.canonical()).or_insert(g);
+            if first != g {
+                next[g] = first;
+                *slot = None;
+                report.layers[i].merged += 1;
+            }
+        }
+        alias = next;
+    }
+}
+
+/// Pass 3: renumber every unpinned layer's gates compactly, in id order.
+fn compact(nodes: &mut [Vec<Option<Node>>], pinned: &[bool]) {
+    let mut map: Vec<usize> = (0..nodes[0].len()).collect();
+    for i in 1..nodes.len() {
+        for node in nodes[i].iter_mut().flatten() {
+            node.remap(&map);
+        }
+        map = (0..nodes[i].len()).collect();
+        if pinned[i] {
+            continue;
+        }
+        let mut compacted = vec![None; nodes[i].len()];
+        let kept = nodes[i].iter().enumerate().filter(|(_, n)| n.is_some());
+        for (new, (old, &node)) in kept.enumerate() {
+            map[old] = new;
+            compacted[new] = node;
+        }
+        nodes[i] = compacted;
+    }
+}
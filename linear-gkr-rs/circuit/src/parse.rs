@@ -1,7 +1,12 @@
 use std::collections::BTreeMap;
-use std::io::{BufRead, BufReader, Read};
+use std::io::{BufRead, BufReader, Lines, Read, Write};
+use std::str::FromStr;
+
+use ark_ff::PrimeField;
+use field::{FieldElement, IntoField};
 
 use crate::gate::{Gate, GateType};
+use crate::layer::{bit_length, Circuit, Layer};
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -11,81 +16,216 @@ pub enum Error {
     Io(#[from] std::io::Error),
     #[error("int error")]
     Int(#[from] std::num::ParseIntError),
+    #[error("{0:?} gates have no code in the text format")]
+    Unwritable(GateType),
+    #[error("{0} asserted outputs have no place in the text format")]
+    UnwritableAssertions(usize),
 }
 
-/// split on ASCII‑white and yield successive tokens
-fn tokens<R: Read>(r: R) -> impl Iterator<Item = String> {
-    BufReader::new(r)
-        .lines()
-        .filter_map(|l| l.ok())
-        .flat_map(|l| l.split_whitespace().map(str::to_owned).collect::<Vec<_>>())
+/// Widest layer the reader accepts: gate ids must be below
+/// `1 << MAX_BIT_LENGTH`, since every slot up to the largest id is allocated.
+pub const MAX_BIT_LENGTH: usize = 30;
+
+/// split on ASCII‑white and yield successive tokens, one line buffered at a time
+struct Tokens<R: Read> {
+    lines: Lines<BufReader<R>>,
+    line: std::vec::IntoIter<String>,
+}
+
+impl<R: Read> Tokens<R> {
+    fn new(r: R) -> Self {
+        Self {
+            lines: BufReader::new(r).lines(),
+            line: Vec::new().into_iter(),
+        }
+    }
+
+    /// next token parsed as `T`; `what` names the field for error messages
+    fn next<T>(&mut self, what: &'static str) -> Result<T, Error>
+    where
+        T: FromStr,
+        Error: From<T::Err>,
+    {
+        Ok(self.raw(what)?.parse()?)
+    }
+
+    fn raw(&mut self, what: &'static str) -> Result<String, Error> {
+        loop {
+            if let Some(t) = self.line.next() {
+                return Ok(t);
+            }
+            let l = self.lines.next().ok_or(Error::Parse(what))??;
+            self.line = l
+                .split_whitespace()
+                .map(str::to_owned)
+                .collect::<Vec<_>>()
+                .into_iter();
+        }
+    }
 }
 
-pub struct Circuit {
-    pub layers: Vec<Layer>,
+/// Decimal field literal, reduced mod p.
+fn field_literal(t: &str) -> Result<FieldElement, Error> {
+    if t.is_empty() || !t.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(Error::Parse("invalid field literal"));
+    }
+    Ok(t.fe())
 }
 
-pub struct Layer {
-    pub gates: BTreeMap<usize, Gate>,
-    pub bit_length: usize,
+/// Streaming circuit reader: parses the depth header up front and then yields
+/// one [`Layer`] per call to `next`, so only the layer being read is resident.
+pub struct LayerReader<R: Read> {
+    tok: Tokens<R>,
+    depth: usize,
+    read: usize,
 }
 
-pub fn load_from_path<P: AsRef<std::path::Path>>(p: P) -> Result<Circuit, Error> {
-    let file = std::fs::File::open(p)?;
-    let mut tok = tokens(file);
+impl LayerReader<std::fs::File> {
+    pub fn open<P: AsRef<std::path::Path>>(p: P) -> Result<Self, Error> {
+        Self::new(std::fs::File::open(p)?)
+    }
+}
 
-    let depth: usize = tok.next().ok_or(Error::Parse("missing depth"))?.parse()?;
+impl<R: Read> LayerReader<R> {
+    pub fn new(r: R) -> Result<Self, Error> {
+        let mut tok = Tokens::new(r);
+        let depth = tok.next("missing depth")?;
+        Ok(Self {
+            tok,
+            depth,
+            read: 0,
+        })
+    }
 
-    let mut layers = Vec::with_capacity(depth);
+    /// Number of layers announced by the header.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
 
-    for _ in 0..depth {
-        let n: usize = tok
-            .next()
-            .ok_or(Error::Parse("layer header missing"))?
-            .parse()?;
+    fn read_layer(&mut self) -> Result<Layer, Error> {
+        let n: usize = self.tok.next("layer header missing")?;
 
         let mut gates = BTreeMap::<usize, Gate>::new();
+        let mut constants = Vec::new();
         let mut max_id = 0usize;
         for _ in 0..n {
-            // each gate = 4 ints
-            let ty: i32 = tok.next().ok_or(Error::Parse("ty"))?.parse()?;
-            let g: usize = tok.next().ok_or(Error::Parse("g"))?.parse()?;
-            let u: usize = tok.next().ok_or(Error::Parse("u"))?.parse()?;
-            let v: usize = tok.next().ok_or(Error::Parse("v"))?.parse()?;
-
-            let gate_type = match ty {
-                0 => GateType::Input,
-                1 => GateType::Add,
-                2 => GateType::Mul,
-                3 => GateType::Dummy,
+            // each gate = 4 tokens; constants are decimal field literals
+            let ty: i32 = self.tok.next("ty")?;
+            let g: usize = self.tok.next("g")?;
+            if g >> MAX_BIT_LENGTH != 0 {
+                return Err(Error::Parse("gate id too large"));
+            }
+            let (ty, u, v) = match ty {
+                0 => {
+                    // small inputs keep the value in `u`; wider literals become constants
+                    let lit: String = self.tok.raw("u")?;
+                    let v: usize = self.tok.next("v")?;
+                    match lit.parse::<usize>() {
+                        Ok(u) => (GateType::Input, u, v),
+                        Err(_) => {
+                            constants.push(field_literal(&lit)?);
+                            (GateType::Const, constants.len() - 1, 0)
+                        }
+                    }
+                }
+                1..=3 => {
+                    let u: usize = self.tok.next("u")?;
+                    let v: usize = self.tok.next("v")?;
+                    let ty = [GateType::Add, GateType::Mul, GateType::Dummy][ty as usize - 1];
+                    (ty, u, v)
+                }
+                4 => {
+                    constants.push(field_literal(&self.tok.raw("u")?)?);
+                    let _v: usize = self.tok.next("v")?;
+                    (GateType::Const, constants.len() - 1, 0)
+                }
+                5 | 6 => {
+                    let u: usize = self.tok.next("u")?;
+                    constants.push(field_literal(&self.tok.raw("v")?)?);
+                    let ty = if ty == 5 {
+                        GateType::ScalarMul
+                    } else {
+                        GateType::AddConst
+                    };
+                    (ty, u, constants.len() - 1)
+                }
                 _ => return Err(Error::Parse("unknown gate type")),
             };
 
-            gates.insert(
-                g,
-                Gate {
-                    ty: gate_type,
-                    u,
-                    v,
-                },
-            );
+            gates.insert(g, Gate { ty, u, v });
             max_id = max_id.max(g);
         }
 
-        // same rule as C++: bit_length = ⌈log₂(max_id+1)⌉
-        let bit_length = if n == 1 {
-            1 // will pad with dummy later
-        } else {
-            let bl = (max_id.next_power_of_two()).trailing_zeros() as usize;
-            if bl == 0 {
-                1
-            } else {
-                bl
-            }
-        };
+        // ids 0..=max_id must all be addressable
+        let bit_length = bit_length(max_id + 1);
+        let mut layer = Layer::from_sparse(&gates, bit_length);
+        layer.constants = constants;
+        Ok(layer)
+    }
+}
+
+impl<R: Read> Iterator for LayerReader<R> {
+    type Item = Result<Layer, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.read == self.depth {
+            return None;
+        }
+        self.read += 1;
+        Some(self.read_layer())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.depth - self.read;
+        (left, Some(left))
+    }
+}
+
+pub fn load_from_path<P: AsRef<std::path::Path>>(p: P) -> Result<Circuit, Error> {
+    let layers = LayerReader::open(p)?.collect::<Result<Vec<_>, _>>()?;
+    Ok(Circuit {
+        layers,
+        assertions: Vec::new(),
+    })
+}
 
-        layers.push(Layer { gates, bit_length });
+/// Write `c` in the text format read by [`LayerReader`], every slot
+/// included. Reading it back gives the same gates with the same values, but
+/// not always the same constant pools: each constant is written out at every
+/// gate using it, so a shared one comes back once per use and an unused one
+/// not at all. Closed‑form predicates are not written either.
+///
+/// Only the gate types with a code (0–6) can be written, and circuits with
+/// assertions cannot, since the format has no place for them.
+pub fn write_text<W: Write>(c: &Circuit, mut w: W) -> Result<(), Error> {
+    if !c.assertions.is_empty() {
+        return Err(Error::UnwritableAssertions(c.assertions.len()));
     }
+    let decimal = |x: &FieldElement| x.into_bigint().to_string();
+    writeln!(w, "{}", c.layers.len())?;
+    for layer in &c.layers {
+        write!(w, "{}", layer.len())?;
+        for (g, gate) in layer.gates() {
+            let (code, u, v) = match gate.ty {
+                GateType::Input => (0, gate.u.to_string(), gate.v.to_string()),
+                GateType::Add => (1, gate.u.to_string(), gate.v.to_string()),
+                GateType::Mul => (2, gate.u.to_string(), gate.v.to_string()),
+                GateType::Dummy => (3, gate.u.to_string(), gate.v.to_string()),
+                GateType::Const => (4, decimal(&layer.constants[gate.u]), "0".into()),
+                GateType::ScalarMul => (5, gate.u.to_string(), decimal(&layer.constants[gate.v])),
+                GateType::AddConst => (6, gate.u.to_string(), decimal(&layer.constants[gate.v])),
+                ty => return Err(Error::Unwritable(ty)),
+            };
+            write!(w, "  {code} {g} {u} {v}")?;
+        }
+        writeln!(w)?;
+    }
+    Ok(())
+}
 
-    Ok(Circuit { layers })
+pub fn save_to_path<P: AsRef<std::path::Path>>(c: &Circuit, p: P) -> Result<(), Error> {
+    let mut text = Vec::new();
+    write_text(c, &mut text)?;
+    std::fs::write(p, text)?;
+    Ok(())
 }
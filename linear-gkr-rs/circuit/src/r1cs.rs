@@ -0,0 +1,310 @@
+//! Import of Circom constraint systems: binary `.r1cs` files plus `.wtns`
+//! witnesses (<https://github.com/iden3/r1csfile>, <https://github.com/iden3/snarkjs>).
+//!
+//! A constraint system `A·z ∘ B·z − C·z = 0` is compiled into four layers over
+//! the witness `z`:
+//!
+//! 1. `ScalarMul` every term `coeff · z_j`, grouped by (constraint, matrix);
+//! 2. `Sum` each group into `A_i·z`, `B_i·z`, `C_i·z`;
+//! 3. `Mul` the `A`/`B` sums, `Relay` the `C` sum;
+//! 4. `Minus`, so output `i` is zero iff constraint `i` holds.
+//!
+//! One more output, relayed from `z₀` and offset by `AddConst −1`, is zero iff
+//! the constant wire is `1`; without it the all‑zero witness would satisfy
+//! every constraint.
+
+use std::io::Read;
+
+use ark_ff::{BigInteger, One, PrimeField};
+use field::FieldElement;
+
+use crate::gate::{Gate, GateType};
+use crate::layer::{bit_length, Circuit, Layer};
+use crate::Error;
+
+/// Sparse linear combination `Σ coeff · z[wire]`.
+pub type LinearCombination = Vec<(usize, FieldElement)>;
+
+/// One constraint `(a·z) · (b·z) = c·z`.
+pub struct Constraint {
+    pub a: LinearCombination,
+    pub b: LinearCombination,
+    pub c: LinearCombination,
+}
+
+pub struct R1cs {
+    /// Witness length, including the leading constant `1`.
+    pub num_wires: usize,
+    pub num_pub_out: usize,
+    pub num_pub_in: usize,
+    pub num_prv_in: usize,
+    pub constraints: Vec<Constraint>,
+}
+
+fn err(msg: impl Into<String>) -> Error {
+    Error::Parse(msg.into())
+}
+
+/// Little‑endian cursor over a whole file.
+struct Bytes<'a>(&'a [u8]);
+
+impl<'a> Bytes<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], Error> {
+        if self.0.len() < n {
+            return Err(err("unexpected end of file"));
+        }
+        let (head, tail) = self.0.split_at(n);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn u32(&mut self) -> Result<usize, Error> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()) as usize)
+    }
+
+    fn u64(&mut self) -> Result<usize, Error> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()) as usize)
+    }
+
+    /// Canonical little‑endian field element of `n8` bytes.
+    fn fe(&mut self, n8: usize) -> Result<FieldElement, Error> {
+        let bytes = self.take(n8)?;
+        let fe = FieldElement::from_le_bytes_mod_order(bytes);
+        if fe.into_bigint().to_bytes_le() != bytes {
+            return Err(err("field element not reduced"));
+        }
+        Ok(fe)
+    }
+
+    /// Field header `n8 ‖ prime`; only BN254 Fr is accepted.
+    fn field(&mut self) -> Result<usize, Error> {
+        let n8 = self.u32()?;
+        let prime = self.take(n8)?;
+        if prime != &FieldElement::MODULUS.to_bytes_le()[..] {
+            return Err(err("prime is not the BN254 scalar field"));
+        }
+        Ok(n8)
+    }
+}
+
+/// Split an iden3 binary container (`magic ‖ version ‖ sections`) into
+/// `(section type, body)` pairs.
+fn sections<'a>(data: &'a [u8], magic: &[u8; 4]) -> Result<Vec<(usize, &'a [u8])>, Error> {
+    let mut b = Bytes(data);
+    if b.take(4)? != magic {
+        return Err(err(format!(
+            "missing {:?} magic",
+            String::from_utf8_lossy(magic)
+        )));
+    }
+    let _version = b.u32()?;
+    let n = b.u32()?;
+    (0..n)
+        .map(|_| {
+            let ty = b.u32()?;
+            let size = b.u64()?;
+            Ok((ty, b.take(size)?))
+        })
+        .collect()
+}
+
+fn section<'a>(secs: &[(usize, &'a [u8])], ty: usize) -> Result<Bytes<'a>, Error> {
+    secs.iter()
+        .find(|(t, _)| *t == ty)
+        .map(|&(_, b)| Bytes(b))
+        .ok_or_else(|| err(format!("missing section {ty}")))
+}
+
+impl R1cs {
+    pub fn from_reader<R: Read>(mut r: R) -> Result<Self, Error> {
+        let mut data = Vec::new();
+        r.read_to_end(&mut data)?;
+        let secs = sections(&data, b"r1cs")?;
+
+        let mut h = section(&secs, 1)?;
+        let n8 = h.field()?;
+        let num_wires = h.u32()?;
+        let num_pub_out = h.u32()?;
+        let num_pub_in = h.u32()?;
+        let num_prv_in = h.u32()?;
+        let _num_labels = h.u64()?;
+        let num_constraints = h.u32()?;
+
+        let mut c = section(&secs, 2)?;
+        let mut lc = || -> Result<LinearCombination, Error> {
+            (0..c.u32()?)
+                .map(|_| {
+                    let wire = c.u32()?;
+                    if wire >= num_wires {
+                        return Err(err(format!("wire {wire} out of range")));
+                    }
+                    Ok((wire, c.fe(n8)?))
+                })
+                .collect()
+        };
+        let constraints = (0..num_constraints)
+            .map(|_| {
+                Ok(Constraint {
+                    a: lc()?,
+                    b: lc()?,
+                    c: lc()?,
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Ok(Self {
+            num_wires,
+            num_pub_out,
+            num_pub_in,
+            num_prv_in,
+            constraints,
+        })
+    }
+
+    pub fn load<P: AsRef<std::path::Path>>(p: P) -> Result<Self, Error> {
+        Self::from_reader(std::fs::File::open(p)?)
+    }
+
+    /// Every term in circuit order: constraint‑major, then `a`, `b`, `c`.
+    fn terms(&self) -> impl Iterator<Item = &LinearCombination> {
+        self.constraints.iter().flat_map(|k| [&k.a, &k.b, &k.c])
+    }
+
+    /// Compile to a layered circuit whose outputs are `A·z ∘ B·z − C·z`
+    /// followed by `z₀ − 1`, all asserted to be zero.
+    ///
+    /// Layer 0 is `z`; its values are supplied by [`R1cs::assignment`] rather
+    /// than written into the circuit.
+    pub fn to_circuit(&self) -> Circuit {
+        let num_terms: usize = self.terms().map(Vec::len).sum();
+        let input = Gate {
+            ty: GateType::Input,
+            u: 0,
+            v: 0,
+        };
+        let mut layer0 = Layer::dummy(bit_length(self.num_wires));
+        for g in 0..self.num_wires {
+            layer0.set(g, &input);
+        }
+
+        // coeff · z_j, recording where each linear combination's terms start
+        let mut products = Layer::dummy(bit_length(num_terms + 1));
+        let mut starts = Vec::with_capacity(3 * self.constraints.len() + 1);
+        let mut t = 0;
+        for lc in self.terms() {
+            starts.push(t);
+            for &(wire, coeff) in lc {
+                let gate = Gate {
+                    ty: GateType::ScalarMul,
+                    u: wire,
+                    v: products.push_constant(coeff),
+                };
+                products.set(t, &gate);
+                t += 1;
+            }
+        }
+        starts.push(t);
+
+        let m = self.constraints.len();
+        let relay = |u| Gate {
+            ty: GateType::Relay,
+            u,
+            v: 0,
+        };
+        let mut sums = Layer::dummy(bit_length(3 * m + 1));
+        for (g, range) in starts.windows(2).enumerate() {
+            let gate = Gate {
+                ty: GateType::Sum,
+                u: range[0],
+                v: range[1],
+            };
+            sums.set(g, &gate);
+        }
+
+        let mut ab_c = Layer::dummy(bit_length(2 * m + 1));
+        let mut out = Layer::dummy(bit_length(m + 1));
+        for i in 0..m {
+            let (a, b, c) = (3 * i, 3 * i + 1, 3 * i + 2);
+            ab_c.set(
+                2 * i,
+                &Gate {
+                    ty: GateType::Mul,
+                    u: a,
+                    v: b,
+                },
+            );
+            ab_c.set(2 * i + 1, &relay(c));
+            out.set(
+                i,
+                &Gate {
+                    ty: GateType::Minus,
+                    u: 2 * i,
+                    v: 2 * i + 1,
+                },
+            );
+        }
+
+        // z₀ − 1
+        products.set(t, &relay(0));
+        sums.set(3 * m, &relay(t));
+        ab_c.set(2 * m, &relay(3 * m));
+        let minus_one = out.push_constant(-FieldElement::one());
+        out.set(
+            m,
+            &Gate {
+                ty: GateType::AddConst,
+                u: 2 * m,
+                v: minus_one,
+            },
+        );
+
+        Circuit {
+            assertions: (0..out.len()).collect(),
+            layers: vec![layer0, products, sums, ab_c, out],
+        }
+    }
+
+    /// Layer‑0 assignment for [`R1cs::to_circuit`]: the witness itself, once
+    /// its length and its leading constant `1` are checked.
+    pub fn assignment(&self, witness: &[FieldElement]) -> Result<Vec<FieldElement>, Error> {
+        if witness.len() != self.num_wires {
+            return Err(err(format!(
+                "witness has {} values, constraint system has {} wires",
+                witness.len(),
+                self.num_wires
+            )));
+        }
+        if !witness.first().is_some_and(One::is_one) {
+            return Err(err("witness does not start with the constant 1"));
+        }
+        Ok(witness.to_vec())
+    }
+}
+
+/// Read a `.wtns` witness.
+pub fn read_wtns<R: Read>(mut r: R) -> Result<Vec<FieldElement>, Error> {
+    let mut data = Vec::new();
+    r.read_to_end(&mut data)?;
+    let secs = sections(&data, b"wtns")?;
+
+    let mut h = section(&secs, 1)?;
+    let n8 = h.field()?;
+    let n = h.u32()?;
+    let mut w = section(&secs, 2)?;
+    (0..n).map(|_| w.fe(n8)).collect()
+}
+
+pub fn load_wtns<P: AsRef<std::path::Path>>(p: P) -> Result<Vec<FieldElement>, Error> {
+    read_wtns(std::fs::File::open(p)?)
+}
+
+/// Load `r1cs` and `wtns` and compile them: the circuit plus its layer‑0
+/// assignment, ready for `Prover::evaluate_with_inputs`.
+pub fn load_circom<P: AsRef<std::path::Path>, Q: AsRef<std::path::Path>>(
+    r1cs: P,
+    wtns: Q,
+) -> Result<(Circuit, Vec<FieldElement>), Error> {
+    let cs = R1cs::load(r1cs)?;
+    let inputs = cs.assignment(&load_wtns(wtns)?)?;
+    Ok((cs.to_circuit(), inputs))
+}
@@ -0,0 +1,368 @@
+//! Re‑leveling: lay a circuit out again with fewer relays and less padding.
+//!
+//! [`relevel`] reads the circuit back into a gate DAG, dropping `Relay` and
+//! `DirectRelay` gates (a relay is its operand) and expanding `Sum` gates into
+//! balanced `Add` trees. Every gate then gets a level between the deepest of
+//! its operands and the shallowest of its readers, keeping the depth of the
+//! ASAP schedule: starting from ASAP, gates are moved one at a time to the
+//! level that most lowers the total padded size `Σ 1 << bit_length` and, among
+//! those, the number of slots, until no move helps. The DAG is laid out with
+//! the same relay insertion as the DSL and `Builder`.
+//!
+//! The input layer, the output slots and their assertions are kept as they
+//! are.
+
+use std::fmt;
+
+use field::FieldElement;
+
+use crate::gate::{Gate, GateType};
+use crate::layer::{bit_length, Circuit, Layer};
+use crate::levelize::{self, operands};
+
+/// Sweeps over all gates before giving up on further improvement.
+const MAX_SWEEPS: usize = 16;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RelevelReport {
+    pub depth_before: usize,
+    pub depth_after: usize,
+    pub relays_before: usize,
+    pub relays_after: usize,
+    /// `Σ 1 << bit_length` over all layers.
+    pub slots_before: usize,
+    pub slots_after: usize,
+}
+
+impl fmt::Display for RelevelReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "depth {} -> {}, relays {} -> {}, slots {} -> {}",
+            self.depth_before,
+            self.depth_after,
+            self.relays_before,
+            self.relays_after,
+            self.slots_before,
+            self.slots_after
+        )
+    }
+}
+
+fn relays(c: &Circuit) -> usize {
+    let is_relay = |t: &GateType| matches!(t, GateType::Relay | GateType::DirectRelay);
+    c.layers
+        .iter()
+        .map(|l| l.ty.iter().filter(|t| is_relay(t)).count())
+        .sum()
+}
+
+fn slots(c: &Circuit) -> usize {
+    c.layers.iter().map(Layer::len).sum()
+}
+
+/// The circuit as a DAG in `levelize` form.
+struct Dag {
+    nodes: Vec<Gate>,
+    constants: Vec<FieldElement>,
+    /// Node of every output slot, `None` for dummies.
+    outputs: Vec<Option<usize>>,
+}
+
+impl Dag {
+    fn push(&mut self, ty: GateType, u: usize, v: usize) -> usize {
+        self.nodes.push(Gate { ty, u, v });
+        self.nodes.len() - 1
+    }
+
+    fn constant(&mut self, c: FieldElement) -> usize {
+        self.constants.push(c);
+        let idx = self.constants.len() - 1;
+        self.push(GateType::Const, idx, 0)
+    }
+
+    fn new(c: &Circuit) -> Self {
+        let mut dag = Self {
+            nodes: Vec::new(),
+            constants: Vec::new(),
+            outputs: Vec::new(),
+        };
+        // every layer‑0 slot is an input, so that the layout keeps all of them
+        let mut prev: Vec<Option<usize>> = (0..c.layers[0].len())
+            .map(|g| Some(dag.push(GateType::Input, g, 0)))
+            .collect();
+        let mut zero = None;
+        for layer in &c.layers[1..] {
+            let mut wire = |dag: &mut Self, g: usize| match prev[g] {
+                Some(n) => n,
+                // dummies read as zero
+                None => *zero.get_or_insert_with(|| dag.constant(FieldElement::from(0u64))),
+            };
+            let cur = (0..layer.len())
+                .map(|g| {
+                    let Gate { ty, u, v } = layer.gate(g);
+                    Some(match ty {
+                        GateType::Dummy => return None,
+                        GateType::Relay | GateType::DirectRelay => wire(&mut dag, u),
+                        GateType::Input => dag.constant(FieldElement::from(u as u64)),
+                        GateType::Const => dag.constant(layer.constants[u]),
+                        GateType::ScalarMul | GateType::AddConst => {
+                            let u = wire(&mut dag, u);
+                            dag.constants.push(layer.constants[v]);
+                            dag.push(ty, u, dag.constants.len() - 1)
+                        }
+                        GateType::Not => {
+                            let u = wire(&mut dag, u);
+                            dag.push(ty, u, 0)
+                        }
+                        GateType::Sum => {
+                            let mut terms: Vec<_> = (u..v).map(|g| wire(&mut dag, g)).collect();
+                            if terms.is_empty() {
+                                return Some(dag.constant(FieldElement::from(0u64)));
+                            }
+                            while terms.len() > 1 {
+                                terms = terms
+                                    .chunks(2)
+                                    .map(|pair| match *pair {
+                                        [a, b] => dag.push(GateType::Add, a, b),
+                                        [a] => a,
+                                        _ => unreachable!(),
+                                    })
+                                    .collect();
+                            }
+                            terms[0]
+                        }
+                        GateType::Add
+                        | GateType::Mul
+                        | GateType::Minus
+                        | GateType::Xor
+                        | GateType::Naab => {
+                            let (u, v) = (wire(&mut dag, u), wire(&mut dag, v));
+                            dag.push(ty, u, v)
+                        }
+                    })
+                })
+                .collect();
+            prev = cur;
+        }
+        dag.outputs = prev;
+        dag
+    }
+}
+
+/// Levels of the DAG's nodes and the bookkeeping to move them.
+struct Schedule<'a> {
+    nodes: &'a [Gate],
+    level: Vec<usize>,
+    /// Nodes feeding an output; the others are not laid out.
+    live: Vec<bool>,
+    /// Live readers of every node.
+    readers: Vec<Vec<usize>>,
+    is_output: Vec<bool>,
+    depth: usize,
+    /// Slots in use per level below the output layer.
+    count: Vec<usize>,
+}
+
+impl<'a> Schedule<'a> {
+    fn new(dag: &'a Dag) -> Self {
+        let nodes = &dag.nodes[..];
+        let mut level = Vec::with_capacity(nodes.len());
+        for gate in nodes {
+            let lo = operands(gate).map(|op| level[op] + 1).max();
+            // constants go to level 1 at the earliest, like any gate
+            level.push(lo.unwrap_or(usize::from(gate.ty != GateType::Input)));
+        }
+        let mut is_output = vec![false; nodes.len()];
+        for &n in dag.outputs.iter().flatten() {
+            is_output[n] = true;
+        }
+        let mut live = is_output.clone();
+        let mut readers = vec![Vec::new(); nodes.len()];
+        for n in (0..nodes.len()).rev() {
+            if !live[n] {
+                continue;
+            }
+            for op in operands(&nodes[n]) {
+                live[op] = true;
+                readers[op].push(n);
+            }
+        }
+        let depth = dag
+            .outputs
+            .iter()
+            .flatten()
+            .map(|&n| level[n])
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let mut s = Self {
+            nodes,
+            level,
+            live,
+            readers,
+            is_output,
+            depth,
+            count: vec![0; depth],
+        };
+        for n in 0..nodes.len() {
+            s.add_span(n, 1);
+        }
+        s
+    }
+
+    /// Levels `[start, end)` below the output layer where `n` holds a slot.
+    fn span(&self, n: usize) -> (usize, usize) {
+        let mut end = self.readers[n].iter().map(|&r| self.level[r]).max();
+        if self.is_output[n] {
+            end = Some(self.depth);
+        }
+        let start = self.level[n];
+        let end = match end {
+            Some(end) => end,
+            None if self.nodes[n].ty == GateType::Input => 1,
+            None => start, // nobody reads it: not laid out
+        };
+        (start.min(self.depth), end.min(self.depth))
+    }
+
+    fn add_span(&mut self, n: usize, delta: isize) {
+        let (start, end) = self.span(n);
+        for c in &mut self.count[start..end.max(start)] {
+            *c = c.wrapping_add_signed(delta);
+        }
+    }
+
+    /// `(padded size, slots)` of levels `range`, the output layer excluded.
+    fn cost(&self, range: std::ops::Range<usize>) -> (usize, usize) {
+        let counts = &self.count[range];
+        let padded = counts.iter().map(|&c| 1 << bit_length(c)).sum();
+        (padded, counts.iter().sum())
+    }
+
+    /// Put `n` at `l`, updating the slots of `n` and its operands.
+    fn set_level(&mut self, n: usize, l: usize) {
+        let mut ops: Vec<_> = operands(&self.nodes[n]).collect();
+        ops.dedup();
+        for &op in &ops {
+            self.add_span(op, -1);
+        }
+        self.add_span(n, -1);
+        self.level[n] = l;
+        self.add_span(n, 1);
+        for &op in &ops {
+            self.add_span(op, 1);
+        }
+    }
+
+    /// Move `n` to its best level; whether that improved the cost.
+    fn improve(&mut self, n: usize) -> bool {
+        let gate = &self.nodes[n];
+        if gate.ty == GateType::Input || !self.live[n] {
+            return false;
+        }
+        let lo = operands(gate)
+            .map(|op| self.level[op] + 1)
+            .max()
+            .unwrap_or(1);
+        let mut hi = self.readers[n].iter().map(|&r| self.level[r] - 1).min();
+        if self.is_output[n] {
+            hi = Some(hi.map_or(self.depth, |h| h.min(self.depth)));
+        }
+        let Some(hi) = hi else { return false };
+        if lo >= hi {
+            return false;
+        }
+        // every level a move can touch
+        let first = operands(gate)
+            .map(|op| self.level[op])
+            .min()
+            .unwrap_or(lo)
+            .min(lo);
+        let range = first..self.depth;
+        let current = self.level[n];
+        let mut best = (self.cost(range.clone()), current);
+        for l in lo..=hi {
+            if l == current {
+                continue;
+            }
+            self.set_level(n, l);
+            let cost = self.cost(range.clone());
+            if cost < best.0 {
+                best = (cost, l);
+            }
+        }
+        self.set_level(n, best.1);
+        best.1 != current
+    }
+}
+
+/// Re‑level `c` (see the module docs). If the new layout is not smaller in
+/// padded size, then in relays, `c` is returned as it is. Wiring predicates
+/// do not survive re‑leveling.
+pub fn relevel(c: Circuit) -> (Circuit, RelevelReport) {
+    let mut report = RelevelReport {
+        depth_before: c.layers.len(),
+        relays_before: relays(&c),
+        slots_before: slots(&c),
+        ..Default::default()
+    };
+    let keep = |c: Circuit, mut report: RelevelReport| {
+        report.depth_after = report.depth_before;
+        report.relays_after = report.relays_before;
+        report.slots_after = report.slots_before;
+        (c, report)
+    };
+    if c.layers.len() < 2 {
+        return keep(c, report);
+    }
+
+    let dag = Dag::new(&c);
+    let mut schedule = Schedule::new(&dag);
+    for _ in 0..MAX_SWEEPS {
+        let mut moved = false;
+        for n in (0..dag.nodes.len()).rev() {
+            moved |= schedule.improve(n);
+        }
+        if !moved {
+            break;
+        }
+    }
+
+    let outputs: Vec<_> = dag.outputs.iter().flatten().copied().collect();
+    let mut out = levelize::layered(&dag.nodes, &schedule.level, &outputs, &dag.constants);
+    // restore the input layer's gates and the output layer's slot positions
+    let input = &c.layers[0];
+    let layer0 = &mut out.layers[0];
+    layer0.bit_length = input.bit_length;
+    layer0.ty.clone_from(&input.ty);
+    layer0.u.clone_from(&input.u);
+    layer0.v.clone_from(&input.v);
+    layer0.constants.clone_from(&input.constants);
+    let top = out
+        .layers
+        .pop()
+        .expect("layered circuits have an output layer");
+    let mut output = Layer::dummy(c.layers[c.layers.len() - 1].bit_length);
+    output.constants = top.constants.clone();
+    for (k, g) in dag
+        .outputs
+        .iter()
+        .enumerate()
+        .filter_map(|(g, n)| n.map(|_| g))
+        .enumerate()
+    {
+        output.set(g, &top.gate(k));
+    }
+    out.layers.push(output);
+    out.assertions.clone_from(&c.assertions);
+
+    let (relays_after, slots_after) = (relays(&out), slots(&out));
+    if (slots_after, relays_after) >= (report.slots_before, report.relays_before) {
+        return keep(c, report);
+    }
+    report.depth_after = out.layers.len();
+    report.relays_after = relays_after;
+    report.slots_after = slots_after;
+    (out, report)
+}
@@ -0,0 +1,157 @@
+//! Structural statistics of a layered circuit.
+
+use std::borrow::Borrow;
+use std::convert::Infallible;
+use std::fmt;
+
+use crate::gate::GateType;
+use crate::layer::{Circuit, Layer};
+
+#[derive(Clone, Debug)]
+pub struct LayerStats {
+    pub bit_length: usize,
+    /// Gates per type, dummies included, in `GateType` order.
+    pub gates: Vec<(GateType, usize)>,
+    /// Gates other than `Dummy`.
+    pub active: usize,
+    /// `Relay` and `DirectRelay` gates, which only carry values upwards.
+    pub relays: usize,
+    pub constants: usize,
+    /// Non‑dummy gates that no gate of the next layer reads (always zero for
+    /// the output layer).
+    pub unused: usize,
+    /// Most reads of one gate by the next layer.
+    pub max_fan_out: usize,
+}
+
+impl LayerStats {
+    /// Statistics of `layer`, read by `next` unless it is the output layer.
+    pub fn new(layer: &Layer, next: Option<&Layer>) -> Self {
+        let mut fan_out = vec![0usize; layer.len()];
+        if let Some(next) = next {
+            for (_, gate) in next.gates() {
+                for u in gate.inputs() {
+                    fan_out[u] += 1;
+                }
+            }
+        }
+        let count = |f: &dyn Fn(GateType) -> bool| layer.ty.iter().filter(|&&t| f(t)).count();
+        Self {
+            bit_length: layer.bit_length,
+            gates: layer.gate_counts(),
+            active: count(&|t| t != GateType::Dummy),
+            relays: count(&|t| matches!(t, GateType::Relay | GateType::DirectRelay)),
+            constants: layer.constants.len(),
+            unused: match next {
+                Some(_) => (0..layer.len())
+                    .filter(|&g| layer.ty[g] != GateType::Dummy && fan_out[g] == 0)
+                    .count(),
+                None => 0,
+            },
+            max_fan_out: fan_out.iter().copied().max().unwrap_or(0),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CircuitStats {
+    pub layers: Vec<LayerStats>,
+}
+
+impl CircuitStats {
+    /// Statistics of the layers yielded by `layers`, input layer first, with
+    /// at most two of them held at once; e.g. over a [`crate::LayerReader`]
+    /// for circuits too large to load whole. Stops at the first error.
+    pub fn from_layers<L, E>(layers: impl IntoIterator<Item = Result<L, E>>) -> Result<Self, E>
+    where
+        L: Borrow<Layer>,
+    {
+        let mut stats = Vec::new();
+        let mut below: Option<L> = None;
+        for layer in layers {
+            let layer = layer?;
+            if let Some(prev) = below.replace(layer) {
+                stats.push(LayerStats::new(
+                    prev.borrow(),
+                    below.as_ref().map(Borrow::borrow),
+                ));
+            }
+        }
+        if let Some(top) = below {
+            stats.push(LayerStats::new(top.borrow(), None));
+        }
+        Ok(Self { layers: stats })
+    }
+
+    pub fn slots(&self) -> usize {
+        self.layers.iter().map(|l| 1 << l.bit_length).sum()
+    }
+
+    pub fn active(&self) -> usize {
+        self.layers.iter().map(|l| l.active).sum()
+    }
+
+    pub fn relays(&self) -> usize {
+        self.layers.iter().map(|l| l.relays).sum()
+    }
+
+    pub fn unused(&self) -> usize {
+        self.layers.iter().map(|l| l.unused).sum()
+    }
+}
+
+impl Layer {
+    /// Gates per type, dummies included, in `GateType` order.
+    pub fn gate_counts(&self) -> Vec<(GateType, usize)> {
+        let mut counts = [0usize; 256];
+        for &ty in &self.ty {
+            counts[ty as usize] += 1;
+        }
+        counts
+            .iter()
+            .enumerate()
+            .filter(|&(_, &n)| n > 0)
+            .map(|(ty, &n)| (GateType::try_from(ty as u8).expect("valid gate type"), n))
+            .collect()
+    }
+}
+
+impl Circuit {
+    pub fn stats(&self) -> CircuitStats {
+        CircuitStats::from_layers(self.layers.iter().map(Ok::<_, Infallible>))
+            .unwrap_or_else(|e| match e {})
+    }
+}
+
+impl fmt::Display for CircuitStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, l) in self.layers.iter().enumerate() {
+            let gates: Vec<_> = l
+                .gates
+                .iter()
+                .map(|(ty, n)| format!("{ty:?}={n}"))
+                .collect();
+            writeln!(
+                f,
+                "layer {i}: bit_length {}, {}/{} slots used, {}",
+                l.bit_length,
+                l.active,
+                1 << l.bit_length,
+                gates.join(" ")
+            )?;
+            writeln!(
+                f,
+                "  {} relays, {} constants, {} unused, max fan-out {}",
+                l.relays, l.constants, l.unused, l.max_fan_out
+            )?;
+        }
+        let (slots, active) = (self.slots(), self.active());
+        writeln!(f, "depth: {}", self.layers.len())?;
+        writeln!(
+            f,
+            "gates: {active} of {slots} slots ({:.1}%)",
+            100.0 * active as f64 / slots as f64
+        )?;
+        write!(f, "relays: {}, unused: {}", self.relays(), self.unused())
+    }
+}
@@ -0,0 +1,124 @@
+//! Layers with closed‑form wiring predicates.
+//!
+//! Both shapes apply one two‑input gate type to pairs of wires among the
+//! first `2^(b + 1)` of the layer below, where `b` is the layer's bit length,
+//! so every predicate is a single product of `eq` factors and evaluates in
+//! `O(bit_length)` field operations. The layer below may be wider; its wires
+//! past `2^(b + 1)` are never read.
+
+use ark_ff::One;
+use field::FieldElement;
+use polynomial::mle::eq3;
+
+use crate::gate::{Gate, GateType};
+use crate::layer::Layer;
+use crate::wiring::{Term, Wiring, WiringPredicate};
+
+/// `Wiring` of a gate type whose only term is `(u, v)`, scaled by `w`.
+fn scaled(ty: GateType, w: FieldElement) -> Wiring {
+    let t = Term::binary(ty, 0, 0).expect("structured layers take two‑input gate types");
+    Wiring {
+        cst: t.cst * w,
+        lin_u: t.lin_u * w,
+        lin_v: t.lin_v * w,
+        mul: t.mul * w,
+    }
+}
+
+/// Check that wire points `u`, `v` address a layer below wide enough for a
+/// structured layer of `g.len()` bits.
+fn check_widths(g: &[FieldElement], u: &[FieldElement], v: &[FieldElement]) {
+    assert!(
+        u.len() == v.len() && u.len() > g.len(),
+        "structured layer of {} bits over a layer of {} bits",
+        g.len(),
+        u.len()
+    );
+}
+
+/// `Π (1 − uₖ)(1 − vₖ)` over `k ≥ from`: the address bits above the wires a
+/// structured layer reads are zero.
+fn high_bits_zero(u: &[FieldElement], v: &[FieldElement], from: usize) -> FieldElement {
+    let one = FieldElement::one();
+    u[from..]
+        .iter()
+        .zip(&v[from..])
+        .map(|(&x, &y)| (one - x) * (one - y))
+        .product()
+}
+
+/// Gate `g` combines wires `2g` and `2g + 1`, halving the layer below.
+pub struct BinaryTree(pub GateType);
+
+impl WiringPredicate for BinaryTree {
+    fn evaluate(&self, g: &[FieldElement], u: &[FieldElement], v: &[FieldElement]) -> Wiring {
+        check_widths(g, u, v);
+        let b = g.len();
+        // u = (0, g, 0…), v = (1, g, 0…), least significant bit first
+        let w = (FieldElement::one() - u[0])
+            * v[0]
+            * eq3(g, &u[1..=b], &v[1..=b])
+            * high_bits_zero(u, v, b + 1);
+        scaled(self.0, w)
+    }
+}
+
+/// Gate `g` combines wire `g` and wire `g + 2^b` of the layer below, i.e. the
+/// lower and the upper half of its first `2^(b + 1)` wires.
+pub struct ElementWise(pub GateType);
+
+impl WiringPredicate for ElementWise {
+    fn evaluate(&self, g: &[FieldElement], u: &[FieldElement], v: &[FieldElement]) -> Wiring {
+        check_widths(g, u, v);
+        let b = g.len();
+        // u = (g, 0, 0…), v = (g, 1, 0…)
+        let w = (FieldElement::one() - u[b])
+            * v[b]
+            * eq3(g, &u[..b], &v[..b])
+            * high_bits_zero(u, v, b + 1);
+        scaled(self.0, w)
+    }
+}
+
+fn structured(
+    ty: GateType,
+    bit_length: usize,
+    wires: impl Fn(usize) -> (usize, usize),
+    predicate: Box<dyn WiringPredicate>,
+) -> Layer {
+    assert!(
+        Term::binary(ty, 0, 0).is_some(),
+        "{ty:?} is not a two‑input gate type"
+    );
+    let mut layer = Layer::dummy(bit_length);
+    for g in 0..layer.len() {
+        let (u, v) = wires(g);
+        layer.set(g, &Gate { ty, u, v });
+    }
+    layer.predicate = Some(predicate);
+    layer
+}
+
+impl Layer {
+    /// `1 << bit_length` gates `ty(V(2g), V(2g + 1))` over a layer of at
+    /// least `bit_length + 1` bits, with their closed‑form predicate attached.
+    pub fn binary_tree(ty: GateType, bit_length: usize) -> Self {
+        structured(
+            ty,
+            bit_length,
+            |g| (2 * g, 2 * g + 1),
+            Box::new(BinaryTree(ty)),
+        )
+    }
+
+    /// `1 << bit_length` gates `ty(V(g), V(g + 2^bit_length))` over a layer of
+    /// at least `bit_length + 1` bits, with their closed‑form predicate attached.
+    pub fn element_wise(ty: GateType, bit_length: usize) -> Self {
+        structured(
+            ty,
+            bit_length,
+            |g| (g, g | 1 << bit_length),
+            Box::new(ElementWise(ty)),
+        )
+    }
+}
@@ -0,0 +1,241 @@
+//! Gates as wiring‑predicate terms.
+//!
+//! Every gate `g` of a layer is written as a sum of terms over wire pairs of
+//! the previous layer,
+//!
+//! ```text
+//! V(g) = Σ_t  cst_t + lin_u_t·V(u_t) + lin_v_t·V(v_t) + mul_t·V(u_t)·V(v_t)
+//! ```
+//!
+//! so the layer's wiring predicates are the MLEs of the four coefficient
+//! tables indexed by `(g, u_t, v_t)`. Gates that ignore an input use wire `0`
+//! in its place, which keeps every term on exactly one `(u, v)` point.
+
+use std::ops::{Add, Mul};
+
+use ark_ff::{BigInteger, One, PrimeField, Zero};
+use field::FieldElement;
+use polynomial::mle::eq_table;
+use sha3::{Digest, Keccak256};
+
+use crate::gate::GateType;
+use crate::layer::{Circuit, Layer};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Term {
+    pub u: usize,
+    pub v: usize,
+    pub cst: FieldElement,
+    pub lin_u: FieldElement,
+    pub lin_v: FieldElement,
+    pub mul: FieldElement,
+}
+
+impl Term {
+    fn new(u: usize, v: usize) -> Self {
+        Self {
+            u,
+            v,
+            cst: FieldElement::zero(),
+            lin_u: FieldElement::zero(),
+            lin_v: FieldElement::zero(),
+            mul: FieldElement::zero(),
+        }
+    }
+
+    /// The term of a two‑input gate reading `(u, v)` (`Add`, `Mul`, `Minus`,
+    /// `Xor`, `Naab`); `None` for every other gate type.
+    pub fn binary(ty: GateType, u: usize, v: usize) -> Option<Self> {
+        let one = FieldElement::one();
+        let mut t = Self::new(u, v);
+        match ty {
+            GateType::Add => (t.lin_u, t.lin_v) = (one, one),
+            GateType::Mul => t.mul = one,
+            GateType::Minus => (t.lin_u, t.lin_v) = (one, -one),
+            GateType::Xor => (t.lin_u, t.lin_v, t.mul) = (one, one, -FieldElement::from(2u64)),
+            GateType::Naab => (t.lin_v, t.mul) = (one, -one),
+            _ => return None,
+        }
+        Some(t)
+    }
+}
+
+/// A layer's wiring predicates evaluated at one point (or a weighted sum of
+/// points), one per term kind.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Wiring {
+    pub cst: FieldElement,
+    pub lin_u: FieldElement,
+    pub lin_v: FieldElement,
+    pub mul: FieldElement,
+}
+
+impl Wiring {
+    /// Right‑hand side of the layer's final sum‑check equation.
+    pub fn eval(&self, v_u: FieldElement, v_v: FieldElement) -> FieldElement {
+        self.cst + self.lin_u * v_u + self.lin_v * v_v + self.mul * v_u * v_v
+    }
+}
+
+impl Add for Wiring {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            cst: self.cst + rhs.cst,
+            lin_u: self.lin_u + rhs.lin_u,
+            lin_v: self.lin_v + rhs.lin_v,
+            mul: self.mul + rhs.mul,
+        }
+    }
+}
+
+impl Mul<FieldElement> for Wiring {
+    type Output = Self;
+    fn mul(self, rhs: FieldElement) -> Self::Output {
+        Self {
+            cst: self.cst * rhs,
+            lin_u: self.lin_u * rhs,
+            lin_v: self.lin_v * rhs,
+            mul: self.mul * rhs,
+        }
+    }
+}
+
+/// Closed‑form wiring predicates for a layer with regular structure, so that
+/// verifying it does not take a pass over its gates.
+///
+/// An implementation must agree with the gates it is attached to at every
+/// point; [`Layer::wiring_sum`] is the reference to test it against.
+pub trait WiringPredicate: Send + Sync {
+    /// The predicates at `(g, u, v)`, each a point of the matching bit length.
+    fn evaluate(&self, g: &[FieldElement], u: &[FieldElement], v: &[FieldElement]) -> Wiring;
+}
+
+impl Layer {
+    /// Feed the terms of gate `g` to `f` (none for dummies).
+    pub fn terms(&self, g: usize, mut f: impl FnMut(Term)) {
+        let (ty, u, v) = (self.ty[g], self.u[g], self.v[g]);
+        if let Some(t) = Term::binary(ty, u, v) {
+            return f(t);
+        }
+        let one = FieldElement::one();
+        let mut t = Term::new(u, v);
+        match ty {
+            GateType::Dummy => return,
+            GateType::Add | GateType::Mul | GateType::Minus | GateType::Xor | GateType::Naab => {
+                unreachable!("two‑input gates are handled by Term::binary")
+            }
+            GateType::Not => (t.v, t.cst, t.lin_u) = (0, one, -one),
+            GateType::Relay | GateType::DirectRelay => (t.v, t.lin_u) = (0, one),
+            GateType::Input => (t.u, t.v, t.cst) = (0, 0, FieldElement::from(u as u64)),
+            GateType::Const => (t.u, t.v, t.cst) = (0, 0, self.constants[u]),
+            GateType::ScalarMul => (t.v, t.lin_u) = (0, self.constants[v]),
+            GateType::AddConst => (t.v, t.cst, t.lin_u) = (0, self.constants[v], one),
+            GateType::Sum => {
+                for idx in u..v {
+                    f(Term {
+                        lin_u: one,
+                        ..Term::new(idx, 0)
+                    });
+                }
+                return;
+            }
+        }
+        f(t)
+    }
+
+    /// `Σ_terms g[g]·u[u_t]·v[v_t]·coefficient` per term kind: the predicates
+    /// at the point whose `eq` tables are `g`, `u` and `v`, in one pass over
+    /// the gates.
+    pub fn wiring_sum(&self, g: &[FieldElement], u: &[FieldElement], v: &[FieldElement]) -> Wiring {
+        let mut acc = Wiring::default();
+        for (id, &w_g) in g.iter().enumerate().take(self.len()) {
+            self.terms(id, |t| {
+                let w = w_g * u[t.u] * v[t.v];
+                acc.cst += w * t.cst;
+                acc.lin_u += w * t.lin_u;
+                acc.lin_v += w * t.lin_v;
+                acc.mul += w * t.mul;
+            });
+        }
+        acc
+    }
+
+    /// The predicates at `(g, u, v)`: the layer's closed form if it has one,
+    /// otherwise [`Layer::wiring_sum`].
+    pub fn wiring_at(&self, g: &[FieldElement], u: &[FieldElement], v: &[FieldElement]) -> Wiring {
+        match &self.predicate {
+            Some(p) => p.evaluate(g, u, v),
+            None => {
+                let one = FieldElement::one();
+                self.wiring_sum(&eq_table(g, one), &eq_table(u, one), &eq_table(v, one))
+            }
+        }
+    }
+}
+
+/// Layer shapes and wiring terms, as consumed by the sum‑check prover.
+pub trait Layered {
+    /// Number of layers, the input layer included.
+    fn depth(&self) -> usize;
+
+    fn bit_length(&self, layer: usize) -> usize;
+
+    /// Feed every term of layer `layer` to `f` together with its gate id.
+    fn for_each_term(&self, layer: usize, f: &mut dyn FnMut(usize, Term));
+
+    /// Output slots asserted to be zero, in increasing order.
+    fn assertions(&self) -> Vec<usize>;
+
+    /// Keccak‑256 of the layer sizes, the assertions and every layer's terms,
+    /// reduced mod p. Proofs absorb it before their first challenge, so a
+    /// proof is bound to the circuit it was made for.
+    fn digest(&self) -> FieldElement {
+        let mut h = Keccak256::new();
+        h.update(b"linear-gkr-circuit");
+        h.update((self.depth() as u64).to_be_bytes());
+        for i in 0..self.depth() {
+            h.update((self.bit_length(i) as u64).to_be_bytes());
+        }
+        let assertions = self.assertions();
+        h.update((assertions.len() as u64).to_be_bytes());
+        for a in assertions {
+            h.update((a as u64).to_be_bytes());
+        }
+        // one sub‑digest per layer keeps the layers' term lists apart
+        for i in 1..self.depth() {
+            let mut layer = Keccak256::new();
+            self.for_each_term(i, &mut |g, t| {
+                for x in [g, t.u, t.v] {
+                    layer.update((x as u64).to_be_bytes());
+                }
+                for x in [t.cst, t.lin_u, t.lin_v, t.mul] {
+                    layer.update(x.into_bigint().to_bytes_be());
+                }
+            });
+            h.update(layer.finalize());
+        }
+        FieldElement::from_be_bytes_mod_order(&h.finalize())
+    }
+}
+
+impl Layered for Circuit {
+    fn depth(&self) -> usize {
+        self.layers.len()
+    }
+
+    fn bit_length(&self, layer: usize) -> usize {
+        self.layers[layer].bit_length
+    }
+
+    fn for_each_term(&self, layer: usize, f: &mut dyn FnMut(usize, Term)) {
+        let layer = &self.layers[layer];
+        for g in 0..layer.len() {
+            layer.terms(g, |t| f(g, t));
+        }
+    }
+
+    fn assertions(&self) -> Vec<usize> {
+        self.assertions.clone()
+    }
+}
@@ -0,0 +1,63 @@
+use circuit::{bit_length, GateType, Layer, LayerReader};
+
+fn parse(text: &str) -> Vec<Layer> {
+    LayerReader::new(text.as_bytes())
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap()
+}
+
+#[test]
+fn bit_length_is_ceil_log2() {
+    let expected = [
+        (1, 1),
+        (2, 1),
+        (3, 2),
+        (4, 2),
+        (5, 3),
+        (8, 3),
+        (9, 4),
+        (1024, 10),
+        (1025, 11),
+    ];
+    for (slots, bits) in expected {
+        assert_eq!(bit_length(slots), bits, "slots = {slots}");
+    }
+}
+
+#[test]
+fn power_of_two_max_id_gets_extra_bit() {
+    // max_id = 4 → 5 slots → 3 bits (previously 2)
+    let layers = parse("1\n5 0 0 1 0 0 1 2 0 0 2 3 0 0 3 4 0 0 4 5 0");
+    assert_eq!(layers[0].bit_length, 3);
+    assert_eq!(layers[0].len(), 8);
+    assert_eq!(layers[0].ty[4], GateType::Input);
+    assert_eq!(layers[0].u[4], 5);
+    assert!(layers[0].ty[5..].iter().all(|&t| t == GateType::Dummy));
+
+    // max_id = 2 → 3 slots → 2 bits (previously 1)
+    let layers = parse("1\n3 0 0 1 0 0 1 2 0 0 2 3 0");
+    assert_eq!(layers[0].bit_length, 2);
+    assert_eq!(layers[0].len(), 4);
+}
+
+#[test]
+fn single_gate_layer_sized_by_id() {
+    let layers = parse("1\n1 0 0 7 0");
+    assert_eq!(layers[0].bit_length, 1);
+
+    // a lone gate with a large id still needs room for that id
+    let layers = parse("1\n1 0 3 7 0");
+    assert_eq!(layers[0].bit_length, 2);
+    assert_eq!(layers[0].u[3], 7);
+}
+
+#[test]
+fn exact_power_of_two_slots_unchanged() {
+    // max_id = 7 → 8 slots → 3 bits, no padding needed
+    let gates: String = (0..8).map(|g| format!(" 0 {g} {g} 0")).collect();
+    let layers = parse(&format!("1\n8{gates}"));
+    assert_eq!(layers[0].bit_length, 3);
+    assert_eq!(layers[0].len(), 8);
+    assert!(layers[0].ty.iter().all(|&t| t == GateType::Input));
+}
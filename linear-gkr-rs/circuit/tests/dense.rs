@@ -0,0 +1,68 @@
+//! Dense layers hold exactly the gates of the sparse tables they come from.
+
+use std::collections::BTreeMap;
+
+use circuit::{Gate, GateType, Layer};
+
+/// Every third id of `0..n`, as sums and products of the ids around it.
+fn table(n: usize) -> BTreeMap<usize, Gate> {
+    (0..n)
+        .step_by(3)
+        .map(|g| {
+            let ty = if g % 2 == 0 {
+                GateType::Add
+            } else {
+                GateType::Mul
+            };
+            (
+                g,
+                Gate {
+                    ty,
+                    u: g / 2,
+                    v: g + 1,
+                },
+            )
+        })
+        .collect()
+}
+
+fn key(gate: &Gate) -> (GateType, usize, usize) {
+    (gate.ty, gate.u, gate.v)
+}
+
+#[test]
+fn from_sparse_places_gates_at_their_ids() {
+    for bits in 1..6 {
+        let sparse = table(1 << bits);
+        let layer = Layer::from_sparse(&sparse, bits);
+        assert_eq!(layer.len(), 1 << bits);
+        for (g, gate) in layer.gates() {
+            match sparse.get(&g) {
+                Some(expected) => assert_eq!(key(&gate), key(expected), "gate {g}"),
+                None => assert_eq!(gate.ty, GateType::Dummy, "gate {g}"),
+            }
+        }
+
+        // and the non‑dummy slots give the table back
+        let back: BTreeMap<_, _> = layer
+            .gates()
+            .filter(|(_, gate)| gate.ty != GateType::Dummy)
+            .map(|(g, gate)| (g, key(&gate)))
+            .collect();
+        let sparse: BTreeMap<_, _> = sparse.iter().map(|(&g, gate)| (g, key(gate))).collect();
+        assert_eq!(back, sparse, "bit_length = {bits}");
+    }
+}
+
+#[test]
+fn from_sparse_of_nothing_is_all_dummies() {
+    let layer = Layer::from_sparse(&BTreeMap::new(), 3);
+    assert_eq!(layer.len(), 8);
+    assert!(layer.ty.iter().all(|&t| t == GateType::Dummy));
+}
+
+#[test]
+#[should_panic(expected = "does not fit in 2 bits")]
+fn from_sparse_rejects_ids_past_the_bit_length() {
+    Layer::from_sparse(&table(7), 2);
+}
@@ -0,0 +1,173 @@
+//! DOT and JSON views of a small circuit, compared against snapshots.
+
+use circuit::{to_dot, to_json, Circuit, LayerReader};
+
+/// Inputs 3, 5, 7, 2; then `x0 + x1`, `x1·x2`, `4·x3` and a dummy; then the
+/// product of the first two, the third plus 9, and the first plus the dummy.
+fn circuit() -> Circuit {
+    let text = "3
+         4  0 0 3 0  0 1 5 0  0 2 7 0  0 3 2 0
+         3  1 0 0 1  2 1 1 2  5 2 3 4
+         3  2 0 0 1  6 1 2 9  1 2 3 0";
+    let layers = LayerReader::new(text.as_bytes())
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    Circuit {
+        layers,
+        assertions: Vec::new(),
+    }
+}
+
+const DOT: &str = r#"digraph circuit {
+  rankdir=BT;
+  node [style=filled];
+  subgraph layer0 {
+    rank=same;
+    l0_0 [label="Input 0:0", fillcolor=palegreen];
+    l0_1 [label="Input 0:1", fillcolor=palegreen];
+    l0_2 [label="Input 0:2", fillcolor=palegreen];
+    l0_3 [label="Input 0:3", fillcolor=palegreen];
+  }
+  subgraph layer1 {
+    rank=same;
+    l1_0 [label="Add 1:0", fillcolor=lightblue];
+    l1_1 [label="Mul 1:1", fillcolor=salmon];
+    l1_2 [label="ScalarMul 1:2", fillcolor=orange];
+    l1_3 [label="Dummy 1:3", fillcolor=white];
+  }
+  l0_0 -> l1_0;
+  l0_1 -> l1_0;
+  l0_1 -> l1_1;
+  l0_2 -> l1_1;
+  l0_3 -> l1_2;
+  subgraph layer2 {
+    rank=same;
+    l2_0 [label="Mul 2:0", fillcolor=salmon];
+    l2_1 [label="AddConst 2:1", fillcolor=orange];
+    l2_2 [label="Add 2:2", fillcolor=lightblue];
+  }
+  l1_0 -> l2_0;
+  l1_1 -> l2_0;
+  l1_2 -> l2_1;
+  l1_3 -> l2_2;
+  l1_0 -> l2_2;
+}
+"#;
+
+/// Layers 1 and up: no edges into layer 1, whose operands are out of range.
+const DOT_UPPER: &str = r#"digraph circuit {
+  rankdir=BT;
+  node [style=filled];
+  subgraph layer1 {
+    rank=same;
+    l1_0 [label="Add 1:0", fillcolor=lightblue];
+    l1_1 [label="Mul 1:1", fillcolor=salmon];
+    l1_2 [label="ScalarMul 1:2", fillcolor=orange];
+    l1_3 [label="Dummy 1:3", fillcolor=white];
+  }
+  subgraph layer2 {
+    rank=same;
+    l2_0 [label="Mul 2:0", fillcolor=salmon];
+    l2_1 [label="AddConst 2:1", fillcolor=orange];
+    l2_2 [label="Add 2:2", fillcolor=lightblue];
+  }
+  l1_0 -> l2_0;
+  l1_1 -> l2_0;
+  l1_2 -> l2_1;
+  l1_3 -> l2_2;
+  l1_0 -> l2_2;
+}
+"#;
+
+const JSON_UPPER: &str = r#"[
+  {
+    "layer": 1,
+    "bit_length": 2,
+    "gates": [
+      {
+        "id": 0,
+        "type": "Add",
+        "inputs": [
+          0,
+          1
+        ]
+      },
+      {
+        "id": 1,
+        "type": "Mul",
+        "inputs": [
+          1,
+          2
+        ]
+      },
+      {
+        "id": 2,
+        "type": "ScalarMul",
+        "inputs": [
+          3
+        ],
+        "constant": "4"
+      }
+    ]
+  },
+  {
+    "layer": 2,
+    "bit_length": 2,
+    "gates": [
+      {
+        "id": 0,
+        "type": "Mul",
+        "inputs": [
+          0,
+          1
+        ]
+      },
+      {
+        "id": 1,
+        "type": "AddConst",
+        "inputs": [
+          2
+        ],
+        "constant": "9"
+      },
+      {
+        "id": 2,
+        "type": "Add",
+        "inputs": [
+          3,
+          0
+        ]
+      }
+    ]
+  }
+]"#;
+
+#[test]
+fn dot_snapshot() {
+    let c = circuit();
+    // the dummy of layer 1 is drawn because layer 2 reads it; the padding
+    // of layer 2 is not
+    assert_eq!(to_dot(&c, 0..3), DOT);
+    assert_eq!(to_dot(&c, 1..9), DOT_UPPER);
+    assert_eq!(
+        to_dot(&c, 5..9),
+        "digraph circuit {\n  rankdir=BT;\n  node [style=filled];\n}\n"
+    );
+}
+
+#[test]
+fn json_snapshot() {
+    let c = circuit();
+    assert_eq!(to_json(&c, 1..3), JSON_UPPER);
+    // inputs carry their value, dummies are left out
+    let inputs: serde_json::Value = serde_json::from_str(&to_json(&c, 0..1)).unwrap();
+    let constants: Vec<_> = inputs[0]["gates"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|g| g["constant"].as_str().unwrap())
+        .collect();
+    assert_eq!(constants, ["3", "5", "7", "2"]);
+    assert_eq!(to_json(&c, 3..3), "[]");
+}
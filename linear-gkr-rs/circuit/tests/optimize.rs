@@ -0,0 +1,267 @@
+//! `optimize` keeps every output, also where gates read dummies, and its
+//! result can be written back in the text format.
+
+use std::collections::BTreeMap;
+
+use ark_ff::Zero;
+use circuit::{
+    load_from_path, optimize, write_text, Builder, Circuit, Gate, GateType, Layer, LayerReader,
+    ParseError,
+};
+use field::{seeded_rng, ChaCha20Rng, FieldElement, FieldExt, RngCore};
+
+fn parse(text: &[u8]) -> Circuit {
+    let layers = LayerReader::new(text)
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    Circuit {
+        layers,
+        assertions: Vec::new(),
+    }
+}
+
+/// Every layer's values from its terms; layer 0 is `inputs`.
+fn evaluate(c: &Circuit, inputs: &[FieldElement]) -> Vec<Vec<FieldElement>> {
+    let mut values = vec![inputs.to_vec()];
+    for layer in &c.layers[1..] {
+        let prev = values.last().unwrap();
+        let cur = (0..layer.len())
+            .map(|g| {
+                let mut value = FieldElement::zero();
+                layer.terms(g, |t| {
+                    let (x, y) = (prev[t.u], prev[t.v]);
+                    value += t.cst + t.lin_u * x + t.lin_v * y + t.mul * x * y;
+                });
+                value
+            })
+            .collect();
+        values.push(cur);
+    }
+    values
+}
+
+fn outputs(c: &Circuit, inputs: &[FieldElement]) -> Vec<FieldElement> {
+    evaluate(c, inputs).pop().unwrap()
+}
+
+fn assert_same_layers(a: &Circuit, b: &Circuit) {
+    assert_eq!(a.layers.len(), b.layers.len());
+    for (i, (x, y)) in a.layers.iter().zip(&b.layers).enumerate() {
+        assert_eq!(x.bit_length, y.bit_length, "layer {i}");
+        assert_eq!((&x.ty, &x.u, &x.v), (&y.ty, &y.u, &y.v), "layer {i}");
+        assert_eq!(x.constants, y.constants, "layer {i}");
+    }
+}
+
+#[test]
+fn gates_reading_dummies_keep_reading_zero() {
+    // layer 1: a dead product, x0 + x1, a dummy, x2·x3; the outputs read the
+    // dummy, which sits above a gate that compaction removes
+    let c = parse(
+        b"3
+          4  0 0 3 0  0 1 5 0  0 2 7 0  0 3 2 0
+          3  2 0 0 1  1 1 0 1  2 3 2 3
+          2  1 0 1 2  2 1 3 1",
+    );
+    let inputs = c.input_values();
+    assert_eq!(outputs(&c, &inputs), field(&[8, 112]));
+
+    let (opt, report) = optimize(c);
+    assert_eq!(outputs(&opt, &inputs), field(&[8, 112]));
+    assert_eq!((report.dead(), report.merged()), (1, 0));
+    // the dummy became a zero constant between the two remaining gates
+    let layer = &opt.layers[1];
+    assert_eq!(
+        layer.ty[..3],
+        [GateType::Add, GateType::Const, GateType::Mul]
+    );
+    assert_eq!(layer.constants, [FieldElement::zero()]);
+}
+
+#[test]
+fn read_dummies_share_one_zero() {
+    // two dummies of layer 1 read by layer 2, which is not the output layer
+    let c = parse(
+        b"4
+          2  0 0 3 0  0 1 5 0
+          2  1 0 0 1  2 3 0 1
+          3  1 0 1 2  1 1 0 2  2 2 0 0
+          2  1 0 0 1  2 1 1 2",
+    );
+    let inputs = c.input_values();
+    let before = outputs(&c, &inputs);
+    let (opt, report) = optimize(c);
+    assert_eq!(outputs(&opt, &inputs), before);
+    assert_eq!(report.merged(), 1);
+    assert_eq!(
+        opt.layers[1]
+            .ty
+            .iter()
+            .filter(|&&t| t == GateType::Const)
+            .count(),
+        1
+    );
+}
+
+fn field(values: &[u64]) -> Vec<FieldElement> {
+    values.iter().copied().map(FieldElement::from).collect()
+}
+
+/// A random layer of `2^bits` slots over `below` slots: dummies, and the
+/// text format's gates reading any slot, dummies included.
+fn random_layer(rng: &mut ChaCha20Rng, bits: usize, below: usize) -> Layer {
+    let mut gates = BTreeMap::new();
+    let mut constants = Vec::new();
+    let pick = |rng: &mut ChaCha20Rng| rng.next_u64() as usize % below;
+    for g in 0..1 << bits {
+        let (u, v) = (pick(rng), pick(rng));
+        let ty = match rng.next_u64() % 8 {
+            0 | 1 => continue,
+            2 => GateType::Add,
+            3 | 4 => GateType::Mul,
+            5 => GateType::Const,
+            6 => GateType::ScalarMul,
+            _ => GateType::AddConst,
+        };
+        let gate = match ty {
+            GateType::Add | GateType::Mul => Gate { ty, u, v },
+            _ => {
+                // few distinct constants, so that duplicates come up
+                constants.push(FieldElement::from(rng.next_u64() % 3));
+                let k = constants.len() - 1;
+                match ty {
+                    GateType::Const => Gate { ty, u: k, v: 0 },
+                    _ => Gate { ty, u, v: k },
+                }
+            }
+        };
+        gates.insert(g, gate);
+    }
+    let mut layer = Layer::from_sparse(&gates, bits);
+    layer.constants = constants;
+    layer
+}
+
+fn random_circuit(rng: &mut ChaCha20Rng) -> Circuit {
+    let depth = 2 + rng.next_u64() as usize % 4;
+    let inputs: BTreeMap<_, _> = (0..4)
+        .map(|g| {
+            let gate = Gate {
+                ty: GateType::Input,
+                u: g,
+                v: 0,
+            };
+            (g, gate)
+        })
+        .collect();
+    let mut layers = vec![Layer::from_sparse(&inputs, 2)];
+    for _ in 1..depth {
+        let bits = 1 + rng.next_u64() as usize % 3;
+        let below = layers.last().unwrap().len();
+        layers.push(random_layer(rng, bits, below));
+    }
+    Circuit {
+        layers,
+        assertions: Vec::new(),
+    }
+}
+
+#[test]
+fn random_circuits_keep_their_outputs() {
+    let mut rng = seeded_rng(45);
+    let (mut dead, mut merged) = (0, 0);
+    for k in 0..300 {
+        let c = random_circuit(&mut rng);
+        let inputs: Vec<_> = (0..4)
+            .map(|_| FieldElement::random_with(&mut rng))
+            .collect();
+        let before = outputs(&c, &inputs);
+        let (opt, report) = optimize(c);
+        assert_eq!(outputs(&opt, &inputs), before, "circuit {k}");
+        (dead, merged) = (dead + report.dead(), merged + report.merged());
+    }
+    assert!(dead > 0 && merged > 0);
+}
+
+#[test]
+fn optimized_circuits_are_written_back() {
+    let c = load_from_path("../examples/test_circuit.txt").unwrap();
+    let inputs = c.input_values();
+    let before = outputs(&c, &inputs);
+    let (opt, _) = optimize(c);
+
+    let mut text = Vec::new();
+    write_text(&opt, &mut text).unwrap();
+    let read = parse(&text);
+    assert_same_layers(&read, &opt);
+    assert_eq!(outputs(&read, &read.input_values()), before);
+
+    // trailing dummies and wide literals
+    let wide = b"2
+          3  0 0 3 0  0 1 1267650600228229401496703205376 0  3 3 0 0
+          2  5 0 1 21888242871839275222246405745257275088548364400416034343698204186575808495616  4 1 9 0";
+    let c = parse(wide);
+    let mut text = Vec::new();
+    write_text(&c, &mut text).unwrap();
+    assert_same_layers(&parse(&text), &c);
+}
+
+#[test]
+fn gates_without_a_code_are_not_written() {
+    let mut b = Builder::new();
+    let x = b.inputs(2);
+    let d = b.sub(x[0], x[1]);
+    b.output(d);
+    let err = write_text(&b.build(), Vec::new()).unwrap_err();
+    assert!(matches!(err, ParseError::Unwritable(GateType::Minus)));
+    assert_eq!(
+        err.to_string(),
+        "Minus gates have no code in the text format"
+    );
+}
+
+#[test]
+fn circuits_with_assertions_are_not_written() {
+    let mut b = Builder::new();
+    let x = b.inputs(2);
+    let s = b.add(x[0], x[1]);
+    let z = b.mul(x[0], x[1]);
+    b.output(s);
+    b.assert_zero(z);
+    let err = write_text(&b.build(), Vec::new()).unwrap_err();
+    assert!(matches!(err, ParseError::UnwritableAssertions(1)));
+    assert_eq!(
+        err.to_string(),
+        "1 asserted outputs have no place in the text format"
+    );
+}
+
+#[test]
+fn shared_constants_are_written_once_per_use() {
+    // two scalings by the same pooled constant
+    let mut c = parse(b"2  2 0 0 3 0  0 1 5 0  2 1 0 0 1  1 1 0 1");
+    let layer = &mut c.layers[1];
+    let k = layer.push_constant(FieldElement::from(7u64));
+    for g in 0..2 {
+        layer.set(
+            g,
+            &Gate {
+                ty: GateType::ScalarMul,
+                u: g,
+                v: k,
+            },
+        );
+    }
+
+    let mut text = Vec::new();
+    write_text(&c, &mut text).unwrap();
+    let read = parse(&text);
+    assert_eq!(read.layers[1].ty, c.layers[1].ty);
+    assert_eq!(read.layers[1].v, [0, 1]);
+    assert_eq!(read.layers[1].constants, field(&[7, 7]));
+    assert_eq!(
+        outputs(&read, &read.input_values()),
+        outputs(&c, &c.input_values())
+    );
+}
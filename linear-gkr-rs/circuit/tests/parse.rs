@@ -0,0 +1,126 @@
+//! Streaming parsing with `LayerReader` and its errors on malformed input.
+
+use std::io::Read;
+
+use circuit::{GateType, LayerReader, ParseError, MAX_BIT_LENGTH};
+
+/// A circuit text of `depth` layers, each adding gate 0 below to itself next
+/// to a dummy, produced on demand while counting the bytes handed out.
+struct Generated {
+    depth: usize,
+    pending: Vec<u8>,
+    layers: usize,
+    produced: usize,
+}
+
+impl Generated {
+    fn new(depth: usize) -> Self {
+        Self {
+            depth,
+            pending: format!("{depth}\n1 0 0 7 0\n").into_bytes(),
+            layers: 1,
+            produced: 0,
+        }
+    }
+}
+
+impl Read for Generated {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.pending.is_empty() && self.layers < self.depth {
+            self.pending = b"2 1 0 0 0 3 1 0 0\n".to_vec();
+            self.layers += 1;
+        }
+        let n = buf.len().min(self.pending.len());
+        buf[..n].copy_from_slice(&self.pending[..n]);
+        self.pending.drain(..n);
+        self.produced += n;
+        Ok(n)
+    }
+}
+
+#[test]
+fn layers_are_read_one_at_a_time() {
+    let depth = 10_000;
+    let mut source = Generated::new(depth);
+    let mut reader = LayerReader::new(&mut source).unwrap();
+    assert_eq!(reader.depth(), depth);
+    let first = reader.next().unwrap().unwrap();
+    assert_eq!(first.ty[0], GateType::Input);
+    assert_eq!(reader.size_hint(), (depth - 1, Some(depth - 1)));
+    let second = reader.next().unwrap().unwrap();
+    assert_eq!(second.ty[..], [GateType::Add, GateType::Dummy]);
+    drop(reader);
+    // only a buffer's worth of the ~180 kB text has been pulled in
+    assert!(
+        source.produced < 16 * 1024,
+        "{} bytes read",
+        source.produced
+    );
+
+    let reader = LayerReader::new(Generated::new(depth)).unwrap();
+    let mut count = 0;
+    for layer in reader {
+        assert_eq!(layer.unwrap().bit_length, 1);
+        count += 1;
+    }
+    assert_eq!(count, depth);
+}
+
+fn errors(text: &str) -> Vec<ParseError> {
+    match LayerReader::new(text.as_bytes()) {
+        Ok(reader) => reader.filter_map(Result::err).collect(),
+        Err(e) => vec![e],
+    }
+}
+
+#[test]
+fn truncated_input_is_an_error() {
+    assert!(matches!(
+        errors("")[..],
+        [ParseError::Parse("missing depth")]
+    ));
+    // second layer missing
+    assert!(matches!(
+        errors("2\n1 0 0 7 0\n")[..],
+        [ParseError::Parse("layer header missing")]
+    ));
+    // two gates announced, one given
+    assert!(matches!(
+        errors("1\n2 0 0 7 0\n")[..],
+        [ParseError::Parse("ty")]
+    ));
+    // gate cut off after its id
+    assert!(matches!(errors("1\n1 0 0")[..], [ParseError::Parse("u")]));
+}
+
+#[test]
+fn malformed_tokens_are_errors() {
+    assert!(matches!(errors("x\n")[..], [ParseError::Int(_)]));
+    assert!(matches!(
+        errors("1\n1 9 0 0 0\n")[..],
+        [ParseError::Parse("unknown gate type")]
+    ));
+    assert!(matches!(
+        errors("1\n1 4 0 12a 0\n")[..],
+        [ParseError::Parse("invalid field literal")]
+    ));
+}
+
+#[test]
+fn oversized_gate_ids_are_errors() {
+    // reading on would allocate a slot for every id up to this one
+    for g in [1usize << MAX_BIT_LENGTH, usize::MAX] {
+        assert!(
+            matches!(
+                errors(&format!("1\n1 0 {g} 7 0\n"))[..],
+                [ParseError::Parse("gate id too large")]
+            ),
+            "gate id {g}"
+        );
+    }
+    // an id past usize is a malformed token
+    assert!(matches!(
+        errors("1\n1 0 18446744073709551616 7 0\n")[..],
+        [ParseError::Int(_)]
+    ));
+}
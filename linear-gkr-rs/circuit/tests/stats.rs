@@ -0,0 +1,76 @@
+//! Unused gates and fan‑out of a circuit with a dead gate.
+
+use circuit::{Circuit, CircuitStats, GateType, LayerReader, ParseError};
+
+/// Layer 1 computes `x0 + x1`, `x1·x2`, `x2·x3` and `7·x1`; layer 2 reads all
+/// but `x2·x3`, and `x0 + x1` twice.
+const TEXT: &str = "3
+     4  0 0 3 0  0 1 5 0  0 2 7 0  0 3 2 0
+     4  1 0 0 1  2 1 1 2  2 2 2 3  5 3 1 7
+     2  2 0 0 1  1 1 0 3";
+
+fn circuit() -> Circuit {
+    let layers = LayerReader::new(TEXT.as_bytes())
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    Circuit {
+        layers,
+        assertions: Vec::new(),
+    }
+}
+
+#[test]
+fn dead_gates_are_unused() {
+    let stats = circuit().stats();
+    let unused: Vec<_> = stats.layers.iter().map(|l| l.unused).collect();
+    let fan_out: Vec<_> = stats.layers.iter().map(|l| l.max_fan_out).collect();
+    // x1 feeds three gates of layer 1; `x0 + x1` two of layer 2
+    assert_eq!(unused, [0, 1, 0]);
+    assert_eq!(fan_out, [3, 2, 0]);
+
+    let middle = &stats.layers[1];
+    assert_eq!(
+        middle.gates,
+        [
+            (GateType::Add, 1),
+            (GateType::Mul, 2),
+            (GateType::ScalarMul, 1)
+        ]
+    );
+    assert_eq!((middle.active, middle.relays, middle.constants), (4, 0, 1));
+    assert_eq!(
+        (
+            stats.slots(),
+            stats.active(),
+            stats.relays(),
+            stats.unused()
+        ),
+        (10, 10, 0, 1)
+    );
+    let text = stats.to_string();
+    assert!(text.contains("  0 relays, 1 constants, 1 unused, max fan-out 2\n"));
+    assert!(text.ends_with("relays: 0, unused: 1"));
+}
+
+#[test]
+fn the_output_layer_has_no_unused_gates() {
+    // with layer 1 on top, nothing reads `x2·x3`, but outputs are never unused
+    let mut c = circuit();
+    c.layers.truncate(2);
+    let stats = c.stats();
+    assert_eq!(stats.layers[1].unused, 0);
+    assert_eq!(stats.layers[1].max_fan_out, 0);
+    assert_eq!(stats.unused(), 0);
+}
+
+#[test]
+fn stats_stream_from_a_reader() {
+    let streamed = CircuitStats::from_layers(LayerReader::new(TEXT.as_bytes()).unwrap()).unwrap();
+    assert_eq!(streamed.to_string(), circuit().stats().to_string());
+
+    // the first bad layer ends the pass
+    let truncated = &TEXT[..TEXT.rfind("1 1 0 3").unwrap()];
+    let err = CircuitStats::from_layers(LayerReader::new(truncated.as_bytes()).unwrap());
+    assert!(matches!(err, Err(ParseError::Parse("ty"))));
+}
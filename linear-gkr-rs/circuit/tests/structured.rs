@@ -0,0 +1,68 @@
+//! Closed‑form predicates agree with [`Layer::wiring_sum`] over their gates.
+
+use ark_ff::One;
+use circuit::{GateType, Layer};
+use field::{rand_vec, seeded_rng, FieldElement};
+use polynomial::mle::eq_table;
+
+const TWO_INPUT: [GateType; 5] = [
+    GateType::Add,
+    GateType::Mul,
+    GateType::Minus,
+    GateType::Xor,
+    GateType::Naab,
+];
+
+/// The layer's predicate and its gates' `wiring_sum` at random points, with
+/// the layer below `extra` bits wider than the layer needs.
+fn assert_closed_form(make: fn(GateType, usize) -> Layer, extra: usize) {
+    let one = FieldElement::one();
+    let mut rng = seeded_rng(34);
+    for ty in TWO_INPUT {
+        for b in 0..4 {
+            let layer = make(ty, b);
+            let predicate = layer.predicate.as_ref().expect("structured layer");
+            let below = b + 1 + extra;
+            for _ in 0..4 {
+                let (g, u, v) = (
+                    rand_vec(&mut rng, b),
+                    rand_vec(&mut rng, below),
+                    rand_vec(&mut rng, below),
+                );
+                let sum =
+                    layer.wiring_sum(&eq_table(&g, one), &eq_table(&u, one), &eq_table(&v, one));
+                assert_eq!(
+                    predicate.evaluate(&g, &u, &v),
+                    sum,
+                    "{ty:?}, {b} bits over {below}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn binary_tree_matches_its_gates() {
+    assert_closed_form(Layer::binary_tree, 0);
+}
+
+#[test]
+fn element_wise_matches_its_gates() {
+    assert_closed_form(Layer::element_wise, 0);
+}
+
+#[test]
+fn wider_layers_below_are_read_at_their_low_wires() {
+    for extra in 1..3 {
+        assert_closed_form(Layer::binary_tree, extra);
+        assert_closed_form(Layer::element_wise, extra);
+    }
+}
+
+#[test]
+#[should_panic(expected = "structured layer of 2 bits over a layer of 2 bits")]
+fn narrower_layers_below_are_rejected() {
+    let layer = Layer::binary_tree(GateType::Mul, 2);
+    let r = vec![FieldElement::one(); 2];
+    layer.wiring_at(&r, &r, &r);
+}
@@ -0,0 +1,215 @@
+use std::path::PathBuf;
+
+use circuit::{
+    load_from_path, optimize, relevel, save_to_path, to_dot, to_json, Circuit, CircuitStats,
+    LayerReader,
+};
+use clap::{Parser, Subcommand, ValueEnum};
+use field::seeded_rng;
+
+use prover::{estimate, estimate_layers, ClaimReduction, CostReport, Prover, Trace};
+
+/// Inspect circuits without proving them.
+#[derive(Parser)]
+#[command(author, version, about)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Per-layer gate counts, sum-check rounds, proof size and estimated
+    /// prover/verifier field operations
+    Cost {
+        /// Circuit description file (txt)
+        #[arg(short, long)]
+        circuit: PathBuf,
+        /// Estimate for line-restriction claim reduction instead of random
+        /// linear combinations
+        #[arg(long)]
+        line: bool,
+    },
+    /// Per-layer gate counts by type, slot usage, relays, unused gates and
+    /// fan-out
+    Stats {
+        /// Circuit description file (txt)
+        #[arg(short, long)]
+        circuit: PathBuf,
+    },
+    /// Write the layered DAG as Graphviz DOT or JSON
+    Export {
+        /// Circuit description file (txt)
+        #[arg(short, long)]
+        circuit: PathBuf,
+        #[arg(short, long, value_enum, default_value_t = Format::Dot)]
+        format: Format,
+        /// First layer to include
+        #[arg(long, default_value_t = 0)]
+        from: usize,
+        /// Last layer to include (default: the output layer)
+        #[arg(long)]
+        to: Option<usize>,
+        /// Output file (default: stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Evaluate the circuit and write every gate's type, inputs and value
+    Trace {
+        /// Circuit description file (txt)
+        #[arg(short, long)]
+        circuit: PathBuf,
+        #[arg(short, long, value_enum, default_value_t = TraceFormat::Csv)]
+        format: TraceFormat,
+        /// Stop evaluating after this layer
+        #[arg(long)]
+        up_to: Option<usize>,
+        /// Evaluate on random inputs drawn from this seed instead of the
+        /// values in the circuit file
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Output file (default: stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Remove dead and duplicate gates, renumber and shrink layers, and report
+    /// the changes and the estimated savings
+    Optimize {
+        /// Circuit description file (txt)
+        #[arg(short, long)]
+        circuit: PathBuf,
+        /// Write the optimized circuit to this file (txt)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Re-level the circuit to cut relays and padding, and report the changes
+    /// and the estimated savings
+    Relevel {
+        /// Circuit description file (txt)
+        #[arg(short, long)]
+        circuit: PathBuf,
+    },
+    /// Compare two traces (CSV or JSON) and report the first divergent gate
+    Diff { left: PathBuf, right: PathBuf },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Dot,
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum TraceFormat {
+    Csv,
+    Json,
+}
+
+fn write_output(output: Option<PathBuf>, text: String) -> anyhow::Result<()> {
+    match output {
+        Some(path) => std::fs::write(path, text)?,
+        None => print!("{text}"),
+    }
+    Ok(())
+}
+
+fn print_savings(before: &CostReport, after: &Circuit) {
+    let after = estimate(after, ClaimReduction::Combine);
+    println!(
+        "proof: {} -> {} bytes",
+        before.proof_bytes(),
+        after.proof_bytes()
+    );
+    println!(
+        "prover: {} -> {} field ops",
+        before.prover.total(),
+        after.prover.total()
+    );
+}
+
+fn main() -> anyhow::Result<()> {
+    match Args::parse().command {
+        Command::Cost { circuit, line } => {
+            let reduction = if line {
+                ClaimReduction::Line
+            } else {
+                ClaimReduction::Combine
+            };
+            let layers = LayerReader::open(&circuit)?;
+            println!("{}", estimate_layers(layers, &[], reduction)?);
+        }
+        Command::Stats { circuit } => {
+            let layers = LayerReader::open(&circuit)?;
+            println!("{}", CircuitStats::from_layers(layers)?);
+        }
+        Command::Export {
+            circuit,
+            format,
+            from,
+            to,
+            output,
+        } => {
+            let c = load_from_path(&circuit)?;
+            let layers = from..to.map_or(c.layers.len(), |t| t + 1);
+            let text = match format {
+                Format::Dot => to_dot(&c, layers),
+                Format::Json => to_json(&c, layers),
+            };
+            write_output(output, text)?;
+        }
+        Command::Trace {
+            circuit,
+            format,
+            up_to,
+            seed,
+            output,
+        } => {
+            let c = load_from_path(&circuit)?;
+            let last = up_to.unwrap_or(c.layers.len() - 1);
+            anyhow::ensure!(
+                last < c.layers.len(),
+                "circuit has {} layers",
+                c.layers.len()
+            );
+            let inputs = match seed {
+                Some(seed) => c.random_inputs(&mut seeded_rng(seed)),
+                None => c.input_values(),
+            };
+            let trace = Trace::new(&c, &Prover::evaluate_up_to(&c, &inputs, last));
+            let text = match format {
+                TraceFormat::Csv => trace.to_csv(),
+                TraceFormat::Json => trace.to_json(),
+            };
+            write_output(output, text)?;
+        }
+        Command::Optimize { circuit, output } => {
+            let c = load_from_path(&circuit)?;
+            let before = estimate(&c, ClaimReduction::Combine);
+            let (c, report) = optimize(c);
+            println!("{report}");
+            print_savings(&before, &c);
+            if let Some(path) = output {
+                save_to_path(&c, path)?;
+            }
+        }
+        Command::Relevel { circuit } => {
+            let c = load_from_path(&circuit)?;
+            let before = estimate(&c, ClaimReduction::Combine);
+            let (c, report) = relevel(c);
+            println!("{report}");
+            print_savings(&before, &c);
+        }
+        Command::Diff { left, right } => {
+            let left = Trace::parse(&std::fs::read_to_string(left)?)?;
+            let right = Trace::parse(&std::fs::read_to_string(right)?)?;
+            match left.diff(&right) {
+                Some(d) => {
+                    println!("{d}");
+                    std::process::exit(1);
+                }
+                None => println!("traces are identical"),
+            }
+        }
+    }
+    Ok(())
+}
@@ -1,7 +1,10 @@
 use circuit::load_from_path;
 use clap::Parser;
-use prover::Prover;
-use verifier::Verifier;
+use field::seeded_rng;
+use std::time::Instant;
+
+use prover::{ClaimReduction, Keccak256Transcript, Prover};
+use verifier::{Verifier, VerifierKey};
 
 /// Replacement for C++ `main_slow_track.cpp`.
 #[derive(Parser)]
@@ -9,6 +12,14 @@ struct Opts {
     /// Path to circuit text file
     #[clap(long)]
     circuit: String,
+    /// Reduce each layer's two claims to one via the line through both points
+    /// instead of a random linear combination
+    #[clap(long)]
+    line: bool,
+    /// Evaluate on random inputs drawn from this seed instead of the values
+    /// in the circuit file; rerun with the same seed to replay a run
+    #[clap(long)]
+    seed: Option<u64>,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -28,21 +39,50 @@ fn main() -> anyhow::Result<()> {
         println!(
             "  - Layer {}: {} gates, bit_length = {}",
             i,
-            layer.gates.len(),
+            layer.len(),
             layer.bit_length
         );
     }
 
+    let inputs = match opts.seed {
+        Some(seed) => {
+            println!("Drawing random inputs from seed {seed}");
+            circuit.random_inputs(&mut seeded_rng(seed))
+        }
+        None => circuit.input_values(),
+    };
+
     println!("\nStarting prover evaluation...");
-    let prover = Prover::evaluate(&circuit);
+    let prover = Prover::evaluate_with_inputs(&circuit, &inputs);
     println!("Prover evaluation complete:");
     println!("  - Number of layers evaluated: {}", prover.values.len());
     for (i, layer) in prover.values.iter().enumerate() {
         println!("  - Layer {}: {} values", i, layer.len());
     }
 
+    let reduction = if opts.line {
+        ClaimReduction::Line
+    } else {
+        ClaimReduction::Combine
+    };
+    println!("\nProving ({reduction:?} claim reduction)...");
+    let start = Instant::now();
+    let proof =
+        prover.prove_with_reduction(&circuit, reduction, &mut Keccak256Transcript::default());
+    println!("  - Prover time: {:?}", start.elapsed());
+    println!(
+        "  - Proof size: {} field elements",
+        proof.num_field_elements()
+    );
+
     println!("\nStarting verifier...");
-    let ok = Verifier::verify(&circuit, &prover);
+    let vk = VerifierKey::new(&circuit);
+    let ok = Verifier::verify_proof(
+        &vk,
+        &inputs,
+        &proof,
+        &mut Keccak256Transcript::default(),
+    );
     println!(
         "Verification {}",
         if ok { "successful! ✅" } else { "failed! ❌" }
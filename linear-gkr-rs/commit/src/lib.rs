@@ -0,0 +1,159 @@
+//! Hyrax‑style commitments to multilinear polynomials over BN254 G1.
+//!
+//! A table of `2^n` values is laid out as a `2^⌊n/2⌋ × 2^⌈n/2⌉` matrix `M`
+//! (low variables index columns, high variables index rows) and each row is
+//! committed with a Pedersen vector commitment `Σ_j M[i][j]·G_j`. Since
+//! `Ṽ(r) = L·M·R` with `L = eq(r_hi, ·)` and `R = eq(r_lo, ·)`, an opening
+//! is the vector `w = L·M`: the verifier checks `Σ w_j·G_j = Σ L_i·C_i` and
+//! `Ṽ(r) = ⟨w, R⟩`, i.e. `O(2^{n/2})` work and proof size.
+//!
+//! The commitments are binding but not hiding.
+
+use ark_bn254::{Fq, G1Affine, G1Projective};
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_ff::{BigInteger, One, PrimeField};
+use field::FieldElement;
+use polynomial::mle::eq_table;
+use sha3::{Digest, Keccak256};
+
+/// Split of `num_vars` variables into (column, row) variables.
+fn split(num_vars: usize) -> (usize, usize) {
+    let lo = num_vars.div_ceil(2);
+    (lo, num_vars - lo)
+}
+
+/// Pedersen bases, derived by hashing to the curve so nobody knows their
+/// discrete logarithms.
+#[derive(Clone)]
+pub struct Generators {
+    g: Vec<G1Affine>,
+}
+
+impl Generators {
+    /// Enough bases for tables of up to `2^max_vars` values.
+    pub fn new(max_vars: usize) -> Self {
+        let n = 1 << split(max_vars).0;
+        let g = (0u64..n as u64)
+            .map(|i| {
+                (0u64..)
+                    .find_map(|ctr| {
+                        let mut h = Keccak256::new();
+                        h.update(b"linear-gkr/commit");
+                        h.update(i.to_le_bytes());
+                        h.update(ctr.to_le_bytes());
+                        let x = Fq::from_be_bytes_mod_order(&h.finalize());
+                        // G1 has cofactor 1: every curve point is in the group
+                        G1Affine::get_point_from_x_unchecked(x, false)
+                    })
+                    .unwrap()
+            })
+            .collect();
+        Self { g }
+    }
+
+    fn bases(&self, n: usize) -> &[G1Affine] {
+        assert!(n <= self.g.len(), "generators too small for {n} columns");
+        &self.g[..n]
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Commitment {
+    pub rows: Vec<G1Affine>,
+}
+
+/// Opening of a committed table at one point.
+#[derive(Clone, Debug)]
+pub struct Opening {
+    /// `L·M`, one entry per column.
+    pub combined: Vec<FieldElement>,
+}
+
+/// Commit to `values`, whose length must be a power of two.
+pub fn commit(gens: &Generators, values: &[FieldElement]) -> Commitment {
+    assert!(
+        values.len().is_power_of_two(),
+        "table not padded to hypercube"
+    );
+    let (lo, _) = split(values.len().trailing_zeros() as usize);
+    let bases = gens.bases(1 << lo);
+    let rows: Vec<_> = values
+        .chunks(1 << lo)
+        .map(|row| G1Projective::msm(bases, row).unwrap())
+        .collect();
+    Commitment {
+        rows: G1Projective::normalize_batch(&rows),
+    }
+}
+
+/// Open `values` at `point`; the value itself is `mle::evaluate(values, point)`.
+pub fn open(values: &[FieldElement], point: &[FieldElement]) -> Opening {
+    assert_eq!(values.len(), 1 << point.len(), "point does not match table");
+    let (lo, _) = split(point.len());
+    let l = eq_table(&point[lo..], FieldElement::one());
+    let mut combined = vec![FieldElement::from(0u64); 1 << lo];
+    for (row, &w) in values.chunks(1 << lo).zip(&l) {
+        for (c, &x) in combined.iter_mut().zip(row) {
+            *c += w * x;
+        }
+    }
+    Opening { combined }
+}
+
+impl Commitment {
+    /// `true` if `opening` shows that the committed table evaluates to
+    /// `value` at `point`.
+    pub fn verify(
+        &self,
+        gens: &Generators,
+        point: &[FieldElement],
+        value: FieldElement,
+        opening: &Opening,
+    ) -> bool {
+        let (lo, hi) = split(point.len());
+        if self.rows.len() != 1 << hi || opening.combined.len() != 1 << lo {
+            return false;
+        }
+        let l = eq_table(&point[lo..], FieldElement::one());
+        let r = eq_table(&point[..lo], FieldElement::one());
+        let lhs = G1Projective::msm(gens.bases(1 << lo), &opening.combined).unwrap();
+        let rhs = G1Projective::msm(&self.rows, &l).unwrap();
+        let inner: FieldElement = opening.combined.iter().zip(&r).map(|(&w, &x)| w * x).sum();
+        lhs == rhs && inner == value
+    }
+
+    /// `Σ cⱼ·Cⱼ`, a commitment to `Σ cⱼ·Mⱼ`; all terms must have the same shape.
+    pub fn combine(terms: &[(FieldElement, &Commitment)]) -> Commitment {
+        let n = terms[0].1.rows.len();
+        assert!(
+            terms.iter().all(|(_, c)| c.rows.len() == n),
+            "shape mismatch"
+        );
+        let rows: Vec<_> = (0..n)
+            .map(|i| {
+                let bases: Vec<_> = terms.iter().map(|(_, c)| c.rows[i]).collect();
+                let scalars: Vec<_> = terms.iter().map(|&(s, _)| s).collect();
+                G1Projective::msm(&bases, &scalars).unwrap()
+            })
+            .collect();
+        Commitment {
+            rows: G1Projective::normalize_batch(&rows),
+        }
+    }
+
+    /// Field encoding for transcripts: each coordinate as two 128‑bit halves.
+    pub fn to_field_elements(&self) -> Vec<FieldElement> {
+        let mut out = Vec::with_capacity(4 * self.rows.len());
+        for p in &self.rows {
+            for coord in [p.x, p.y] {
+                let bytes = coord.into_bigint().to_bytes_le();
+                for half in bytes.chunks(16) {
+                    out.push(FieldElement::from(u128::from_le_bytes(
+                        half.try_into().unwrap(),
+                    )));
+                }
+            }
+        }
+        out
+    }
+}
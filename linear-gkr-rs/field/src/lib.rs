@@ -5,10 +5,13 @@
 use ark_bn254::Fr as Fp;
 use ark_ff::{PrimeField, UniformRand};
 use ark_serialize::CanonicalSerialize;
-use ark_std::rand::{thread_rng, RngCore};
+use ark_std::rand::{thread_rng, SeedableRng};
 use std::str::FromStr;
 use ark_ff::BigInteger;
 
+pub use ark_std::rand::RngCore;
+pub use rand_chacha::ChaCha20Rng;
+
 /// Public re‑export so the rest of the workspace can `use field::FieldElement`.
 pub type FieldElement = Fp;
 
@@ -27,7 +30,7 @@ impl IntoField for u64 {
     }
 }
 
-impl<'a> IntoField for &'a str {
+impl IntoField for &str {
     #[inline]
     fn fe(self) -> FieldElement {
         FieldElement::from_str(self).expect("invalid field literal")
@@ -39,7 +42,8 @@ impl<'a> IntoField for &'a str {
 // -----------------------------------------------------------------------------
 
 pub trait FieldExt: PrimeField + UniformRand + CanonicalSerialize {
-    /// Cryptographically random element using thread‑local RNG.
+    /// Cryptographically random element using thread‑local RNG; not
+    /// reproducible, see [`seeded_rng`] for runs that must be replayable.
     fn random() -> Self {
         Self::rand(&mut thread_rng())
     }
@@ -64,6 +68,20 @@ pub trait FieldExt: PrimeField + UniformRand + CanonicalSerialize {
 
 impl FieldExt for Fp {}
 
+// -----------------------------------------------------------------------------
+// Reproducible randomness
+// -----------------------------------------------------------------------------
+
+/// ChaCha20 RNG seeded with `seed`: the same seed replays the same values.
+pub fn seeded_rng(seed: u64) -> ChaCha20Rng {
+    ChaCha20Rng::seed_from_u64(seed)
+}
+
+/// `n` uniformly random field elements drawn from `rng`.
+pub fn rand_vec<R: RngCore + ?Sized>(rng: &mut R, n: usize) -> Vec<FieldElement> {
+    (0..n).map(|_| FieldElement::random_with(rng)).collect()
+}
+
 // -----------------------------------------------------------------------------
 // Standalone helper – needed by verifier, etc.
 // -----------------------------------------------------------------------------
@@ -2,6 +2,7 @@
 
 use field::FieldElement as F;
 use std::ops::{Add, AddAssign};
+pub mod mle;
 pub mod quad;
 pub use quad::{QuadraticPoly, QuintuplePoly};
 
@@ -160,3 +161,21 @@ impl AddAssign for Quintuple {
         *self = *self + rhs;
     }
 }
+
+/// Value at `x` of the degree‑`d` polynomial taking `evals[i]` at `x = i` for
+/// `i = 0..=d` (Lagrange interpolation).
+pub fn interpolate(evals: &[F], x: F) -> F {
+    let points: Vec<F> = (0..evals.len() as u64).map(F::from).collect();
+    let mut acc = F::from(0u64);
+    for (i, &y) in evals.iter().enumerate() {
+        let (mut num, mut den) = (F::from(1u64), F::from(1u64));
+        for (j, &p) in points.iter().enumerate() {
+            if i != j {
+                num *= x - p;
+                den *= points[i] - p;
+            }
+        }
+        acc += y * num / den;
+    }
+    acc
+}
@@ -0,0 +1,82 @@
+//! Multilinear extensions of value tables over the boolean hypercube.
+//!
+//! Variable `i` of a point binds bit `i` of the table index (least significant
+//! first), matching how gate ids are laid out in a layer.
+
+use ark_ff::{One, Zero};
+use field::FieldElement;
+
+/// `eq(r, x)` for every `x ∈ {0,1}^{r.len()}`, scaled by `scale`.
+pub fn eq_table(r: &[FieldElement], scale: FieldElement) -> Vec<FieldElement> {
+    let mut out = vec![FieldElement::zero(); 1 << r.len()];
+    out[0] = scale;
+    for (i, &ri) in r.iter().enumerate() {
+        for j in 0..(1 << i) {
+            let base = out[j];
+            out[j | (1 << i)] = base * ri;
+            out[j] = base - out[j | (1 << i)];
+        }
+    }
+    out
+}
+
+/// `eq(a, b) = Π (aᵢbᵢ + (1 − aᵢ)(1 − bᵢ))`.
+pub fn eq(a: &[FieldElement], b: &[FieldElement]) -> FieldElement {
+    debug_assert_eq!(a.len(), b.len());
+    a.iter()
+        .zip(b)
+        .map(|(&x, &y)| x * y + (FieldElement::one() - x) * (FieldElement::one() - y))
+        .product()
+}
+
+/// `eq(r, x)` for the single vertex `x` (bit `i` of `x` is coordinate `i`).
+pub fn eq_at(r: &[FieldElement], x: usize) -> FieldElement {
+    r.iter()
+        .enumerate()
+        .map(|(i, &ri)| {
+            if x >> i & 1 == 1 {
+                ri
+            } else {
+                FieldElement::one() - ri
+            }
+        })
+        .product()
+}
+
+/// `Π (aᵢbᵢcᵢ + (1 − aᵢ)(1 − bᵢ)(1 − cᵢ))`: the MLE of "all three points are
+/// the same hypercube vertex".
+pub fn eq3(a: &[FieldElement], b: &[FieldElement], c: &[FieldElement]) -> FieldElement {
+    debug_assert!(a.len() == b.len() && a.len() == c.len());
+    let one = FieldElement::one();
+    a.iter()
+        .zip(b)
+        .zip(c)
+        .map(|((&x, &y), &z)| x * y * z + (one - x) * (one - y) * (one - z))
+        .product()
+}
+
+/// Ṽ(r) for the table `values` of length `1 << r.len()`.
+pub fn evaluate(values: &[FieldElement], r: &[FieldElement]) -> FieldElement {
+    debug_assert_eq!(values.len(), 1 << r.len(), "table not padded to hypercube");
+    let mut cur = values.to_vec();
+    for &ri in r {
+        fold(&mut cur, ri);
+    }
+    cur[0]
+}
+
+/// Bind the lowest variable of `table` to `r`, halving it in place.
+pub fn fold(table: &mut Vec<FieldElement>, r: FieldElement) {
+    let half = table.len() / 2;
+    for j in 0..half {
+        let (lo, hi) = (table[2 * j], table[2 * j + 1]);
+        table[j] = lo + (hi - lo) * r;
+    }
+    table.truncate(half);
+}
+
+/// The point `(1 − t)·a + t·b` on the line through `a` and `b`.
+pub fn line(a: &[FieldElement], b: &[FieldElement], t: FieldElement) -> Vec<FieldElement> {
+    debug_assert_eq!(a.len(), b.len());
+    a.iter().zip(b).map(|(&x, &y)| x + (y - x) * t).collect()
+}
@@ -0,0 +1,138 @@
+//! Circuit evaluation and the verifier's wiring sums over dense `ty`/`u`/`v`
+//! columns, against the same gates walked as sparse `id → gate` tables, as
+//! layers were stored before.
+//!
+//! `cargo bench -p prover --bench layers`
+
+use std::collections::BTreeMap;
+
+use ark_ff::One;
+use circuit::{Circuit, Gate, GateType, Layer, Term, Wiring};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use field::{rand_vec, seeded_rng, FieldElement};
+use polynomial::mle::eq_table;
+use prover::Prover;
+
+const DEPTH: usize = 8;
+
+/// `DEPTH` layers of `1 << bits` alternating sums and products over the
+/// layer below, with every fourth slot left out of the table.
+fn tables(bits: usize) -> Vec<BTreeMap<usize, Gate>> {
+    let n = 1 << bits;
+    let inputs = (0..n)
+        .map(|g| {
+            (
+                g,
+                Gate {
+                    ty: GateType::Input,
+                    u: g,
+                    v: 0,
+                },
+            )
+        })
+        .collect();
+    let layers = (1..DEPTH).map(|i| {
+        (0..n)
+            .filter(|g| g % 4 != 3)
+            .map(|g| {
+                let ty = if (g + i) % 2 == 0 {
+                    GateType::Add
+                } else {
+                    GateType::Mul
+                };
+                let (u, v) = ((g * 7 + i) % n, (g * 13 + 5 * i) % n);
+                (g, Gate { ty, u, v })
+            })
+            .collect()
+    });
+    std::iter::once(inputs).chain(layers).collect()
+}
+
+/// The previous evaluation loop: one pass per layer over its table.
+fn evaluate_sparse(
+    tables: &[BTreeMap<usize, Gate>],
+    inputs: &[FieldElement],
+) -> Vec<Vec<FieldElement>> {
+    let mut values = vec![inputs.to_vec()];
+    for table in &tables[1..] {
+        let prev = values.last().unwrap();
+        let mut cur = vec![FieldElement::from(0u64); prev.len()];
+        for (&g, gate) in table {
+            cur[g] = match gate.ty {
+                GateType::Add => prev[gate.u] + prev[gate.v],
+                GateType::Mul => prev[gate.u] * prev[gate.v],
+                _ => unreachable!("only sums and products in the benchmark"),
+            };
+        }
+        values.push(cur);
+    }
+    values
+}
+
+/// [`Layer::wiring_sum`] over a table.
+fn wiring_sum_sparse(
+    table: &BTreeMap<usize, Gate>,
+    g: &[FieldElement],
+    u: &[FieldElement],
+    v: &[FieldElement],
+) -> Wiring {
+    let mut acc = Wiring::default();
+    for (&id, gate) in table {
+        let t = Term::binary(gate.ty, gate.u, gate.v).expect("two‑input gate");
+        let w = g[id] * u[t.u] * v[t.v];
+        acc.cst += w * t.cst;
+        acc.lin_u += w * t.lin_u;
+        acc.lin_v += w * t.lin_v;
+        acc.mul += w * t.mul;
+    }
+    acc
+}
+
+fn evaluate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("evaluate");
+    for bits in [10, 14] {
+        let tables = tables(bits);
+        let circuit = Circuit {
+            layers: tables.iter().map(|t| Layer::from_sparse(t, bits)).collect(),
+            assertions: Vec::new(),
+        };
+        let inputs = rand_vec(&mut seeded_rng(27), 1 << bits);
+        assert_eq!(
+            Prover::evaluate_with_inputs(&circuit, &inputs).values,
+            evaluate_sparse(&tables, &inputs)
+        );
+
+        group.bench_with_input(BenchmarkId::new("dense", bits), &inputs, |b, x| {
+            b.iter(|| Prover::evaluate_with_inputs(black_box(&circuit), x))
+        });
+        group.bench_with_input(BenchmarkId::new("sparse", bits), &inputs, |b, x| {
+            b.iter(|| evaluate_sparse(black_box(&tables), x))
+        });
+    }
+    group.finish();
+}
+
+fn wiring_sum(c: &mut Criterion) {
+    let mut group = c.benchmark_group("wiring_sum");
+    for bits in [10, 14] {
+        let table = &tables(bits)[1];
+        let layer = Layer::from_sparse(table, bits);
+        let mut rng = seeded_rng(27);
+        let [g, u, v] = [0; 3].map(|_| eq_table(&rand_vec(&mut rng, bits), FieldElement::one()));
+        assert_eq!(
+            layer.wiring_sum(&g, &u, &v),
+            wiring_sum_sparse(table, &g, &u, &v)
+        );
+
+        group.bench_function(BenchmarkId::new("dense", bits), |b| {
+            b.iter(|| black_box(&layer).wiring_sum(&g, &u, &v))
+        });
+        group.bench_function(BenchmarkId::new("sparse", bits), |b| {
+            b.iter(|| wiring_sum_sparse(black_box(table), &g, &u, &v))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, evaluate, wiring_sum);
+criterion_main!(benches);
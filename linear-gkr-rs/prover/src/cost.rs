@@ -0,0 +1,362 @@
+//! Cost estimates for proving and verifying a circuit, read off its shape
+//! without evaluating it.
+//!
+//! Field‑operation counts follow this crate's prover and the `verifier`
+//! crate step by step (eq tables, the two passes over each layer's terms,
+//! sum‑check rounds and folds, claim reduction). Work outside those loops is
+//! left out, so the counts are estimates, meant for comparing circuits and
+//! reductions rather than for predicting timings. Transcript work is counted
+//! separately, as absorb and challenge calls, and the one‑time hashing of the
+//! circuit into a verifier key as bytes hashed.
+
+use std::borrow::Borrow;
+use std::convert::Infallible;
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul};
+
+use circuit::{Circuit, GateType, Layer};
+
+use crate::proof::ClaimReduction;
+
+/// Bytes per serialized field element.
+pub const FIELD_BYTES: usize = 32;
+
+/// Field multiplications and additions (subtractions included).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FieldOps {
+    pub mul: u64,
+    pub add: u64,
+}
+
+impl FieldOps {
+    fn new(mul: usize, add: usize) -> Self {
+        Self {
+            mul: mul as u64,
+            add: add as u64,
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.mul + self.add
+    }
+}
+
+impl Add for FieldOps {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            mul: self.mul + rhs.mul,
+            add: self.add + rhs.add,
+        }
+    }
+}
+
+impl AddAssign for FieldOps {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Mul<usize> for FieldOps {
+    type Output = Self;
+    fn mul(self, rhs: usize) -> Self::Output {
+        Self {
+            mul: self.mul * rhs as u64,
+            add: self.add * rhs as u64,
+        }
+    }
+}
+
+impl fmt::Display for FieldOps {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} mul + {} add", self.mul, self.add)
+    }
+}
+
+/// Transcript calls: one absorb per message, one challenge per random value.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TranscriptOps {
+    pub absorbs: usize,
+    pub challenges: usize,
+}
+
+impl AddAssign for TranscriptOps {
+    fn add_assign(&mut self, rhs: Self) {
+        self.absorbs += rhs.absorbs;
+        self.challenges += rhs.challenges;
+    }
+}
+
+/// Costs attributable to one layer. For a layer above the inputs they are
+/// those of the sum‑check that reduces its claim to the layer below.
+#[derive(Clone, Debug)]
+pub struct LayerCost {
+    pub bit_length: usize,
+    /// Gates per type, dummies included, in `GateType` order.
+    pub gates: Vec<(GateType, usize)>,
+    /// Wiring‑predicate terms (see `circuit::Term`).
+    pub terms: usize,
+    /// Sum‑check rounds, `2·b` for `b` bits of the layer below.
+    pub rounds: usize,
+    /// Degree of every round polynomial.
+    pub degree: usize,
+    /// Whether the verifier uses a closed‑form predicate instead of a pass
+    /// over the terms when checking a proof. The terms are still hashed into
+    /// the circuit digest (see [`CostReport::key_hash_bytes`]).
+    pub closed_form: bool,
+    pub proof_elements: usize,
+    pub prover: FieldOps,
+    pub verifier: FieldOps,
+    pub transcript: TranscriptOps,
+}
+
+/// Estimated costs of proving `c`, as returned by [`estimate`].
+#[derive(Clone, Debug)]
+pub struct CostReport {
+    pub reduction: ClaimReduction,
+    /// Indexed by layer; layer 0 has no rounds and its checks are counted in
+    /// the totals only.
+    pub layers: Vec<LayerCost>,
+    /// Field elements in the proof, outputs included.
+    pub proof_elements: usize,
+    pub prover: FieldOps,
+    /// Per proof, once the verifier key is built.
+    pub verifier: FieldOps,
+    pub transcript: TranscriptOps,
+    /// Bytes Keccak‑256 hashes for the circuit digest when a `VerifierKey` is
+    /// built: the layer sizes, the assertions and every term of every layer,
+    /// closed‑form ones included. Paid once per circuit, not per proof.
+    pub key_hash_bytes: usize,
+}
+
+impl CostReport {
+    pub fn proof_bytes(&self) -> usize {
+        self.proof_elements * FIELD_BYTES
+    }
+}
+
+/// `eq_table` over `bits` variables.
+fn eq_table(bits: usize) -> FieldOps {
+    let n = (1usize << bits) - 1;
+    FieldOps::new(n, n)
+}
+
+/// `mle::evaluate` over `bits` variables: one fold per variable.
+fn evaluate(bits: usize) -> FieldOps {
+    let n = (1usize << bits) - 1;
+    FieldOps::new(n, 2 * n)
+}
+
+/// `interpolate` through `points` evaluations.
+fn interpolate(points: usize) -> FieldOps {
+    // per point: 2 products over the others, a division and an accumulation
+    let k = points.saturating_sub(1);
+    FieldOps::new(points * (2 * k + 2), points * (2 * k + 1))
+}
+
+/// Bytes hashed per term for the digest: `g`, `u`, `v` and 4 coefficients.
+const TERM_HASH_BYTES: usize = 3 * 8 + 4 * FIELD_BYTES;
+
+fn term_count(layer: &Layer) -> usize {
+    let mut n = 0;
+    for g in 0..layer.len() {
+        layer.terms(g, |_| n += 1);
+    }
+    n
+}
+
+/// Estimate the costs of proving `c` with `reduction` and of checking the
+/// proof against a full input layer.
+pub fn estimate(c: &Circuit, reduction: ClaimReduction) -> CostReport {
+    estimate_layers(
+        c.layers.iter().map(Ok::<_, Infallible>),
+        &c.assertions,
+        reduction,
+    )
+    .unwrap_or_else(|e| match e {})
+}
+
+/// [`estimate`] for the circuit whose layers `layers` yields, input layer
+/// first, with `assertions`; each layer is dropped once its cost is counted,
+/// e.g. over a `circuit::LayerReader` for circuits too large to load whole.
+/// Stops at the first error.
+pub fn estimate_layers<L, E>(
+    layers: impl IntoIterator<Item = Result<L, E>>,
+    assertions: &[usize],
+    reduction: ClaimReduction,
+) -> Result<CostReport, E>
+where
+    L: Borrow<Layer>,
+{
+    let mut costs = Vec::new();
+    let mut below = None;
+    for layer in layers {
+        let layer = layer?;
+        let layer = layer.borrow();
+        costs.push(layer_cost(layer, below, reduction));
+        below = Some(layer.bit_length);
+    }
+    let layers = costs;
+    let top = layers.last().expect("at least one layer");
+
+    // circuit digest and inputs in, then the outputs; the top claim from them,
+    // and the input layer at two points
+    let b_top = top.bit_length;
+    let mut verifier = evaluate(b_top) + evaluate(layers[0].bit_length) * 2 + FieldOps::new(2, 1);
+    let mut prover = FieldOps::default();
+    let mut transcript = TranscriptOps {
+        absorbs: 2,
+        challenges: b_top,
+    };
+    for cost in &layers {
+        prover += cost.prover;
+        verifier += cost.verifier;
+        transcript += cost.transcript;
+    }
+    let outputs = (1 << b_top) - assertions.len();
+    let proof_elements = outputs + layers.iter().map(|l| l.proof_elements).sum::<usize>();
+
+    // domain tag, depth, bit lengths, assertions, then one sub‑digest per layer
+    let header = b"linear-gkr-circuit".len() + 8 * (2 + layers.len() + assertions.len());
+    let key_hash_bytes = header
+        + layers[1..]
+            .iter()
+            .map(|l| l.terms * TERM_HASH_BYTES + 32)
+            .sum::<usize>();
+    Ok(CostReport {
+        reduction,
+        layers,
+        proof_elements,
+        prover,
+        verifier,
+        transcript,
+        key_hash_bytes,
+    })
+}
+
+/// Costs of `layer`; for a layer above the inputs, of the sum‑check down to
+/// the layer below, of `below` bits.
+fn layer_cost(layer: &Layer, below: Option<usize>, reduction: ClaimReduction) -> LayerCost {
+    let mut cost = LayerCost {
+        bit_length: layer.bit_length,
+        gates: layer.gate_counts(),
+        terms: term_count(layer),
+        rounds: 0,
+        degree: 2,
+        closed_form: layer.predicate.is_some(),
+        proof_elements: 0,
+        prover: FieldOps::default(),
+        verifier: FieldOps::default(),
+        transcript: TranscriptOps::default(),
+    };
+    let Some(b) = below else {
+        return cost;
+    };
+    let b_out = cost.bit_length;
+    let n = 1usize << b;
+    let terms = cost.terms;
+    cost.rounds = 2 * b;
+
+    // β(g) from two eq tables, then for each phase a pass over the terms
+    // and a sum‑check over three tables of size n: 4 mul + 9 add per pair
+    // for the round polynomial, 3 folds of 1 mul + 2 add
+    let betas = eq_table(b_out) * 2 + FieldOps::new(0, 1 << b_out);
+    let sumcheck = FieldOps::new(7, 15) * (n - 1);
+    let phase1 = FieldOps::new(4, 4) * terms + sumcheck;
+    let phase2 = eq_table(b) + FieldOps::new(5, 4) * terms + sumcheck;
+    cost.prover = betas + phase1 + phase2;
+
+    // per round: p(0) + p(1) and p(r); then the wiring predicates
+    cost.verifier = FieldOps::new(2, 5) * cost.rounds;
+    if !cost.closed_form {
+        // β(g), eq(r_u), eq(r_v), then g·u·v and 4 coefficients per term
+        cost.verifier += eq_table(b_out) * 2
+            + FieldOps::new(0, 1 << b_out)
+            + eq_table(b) * 2
+            + FieldOps::new(6, 4) * terms;
+    }
+    cost.verifier += FieldOps::new(4, 3);
+
+    cost.proof_elements = 3 * cost.rounds + 2;
+    cost.transcript = TranscriptOps {
+        absorbs: cost.rounds + 2,
+        challenges: cost.rounds,
+    };
+    match reduction {
+        ClaimReduction::Combine => {
+            cost.verifier += FieldOps::new(2, 1);
+            cost.transcript.challenges += 2;
+        }
+        ClaimReduction::Line => {
+            // q(2..=b) each at a point of the line through r_u and r_v
+            let extra = b.saturating_sub(1);
+            let point = FieldOps::new(b, 2 * b);
+            cost.prover += (point + evaluate(b)) * extra + point;
+            cost.verifier += interpolate(extra + 2) + point;
+            cost.proof_elements += extra;
+            cost.transcript.absorbs += 1;
+            cost.transcript.challenges += 1;
+        }
+    }
+    cost
+}
+
+impl fmt::Display for CostReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "claim reduction: {:?}", self.reduction)?;
+        for (i, l) in self.layers.iter().enumerate() {
+            let gates: Vec<_> = l
+                .gates
+                .iter()
+                .map(|(ty, n)| format!("{ty:?}={n}"))
+                .collect();
+            writeln!(
+                f,
+                "layer {i}: bit_length {}, {}",
+                l.bit_length,
+                gates.join(" ")
+            )?;
+            if i == 0 {
+                continue;
+            }
+            writeln!(
+                f,
+                "  {} terms, {} rounds of degree {}, {} proof elements{}",
+                l.terms,
+                l.rounds,
+                l.degree,
+                l.proof_elements,
+                if l.closed_form {
+                    ", closed-form wiring"
+                } else {
+                    ""
+                }
+            )?;
+            writeln!(f, "  prover: {}", l.prover)?;
+            writeln!(f, "  verifier: {}", l.verifier)?;
+        }
+        writeln!(
+            f,
+            "proof: {} field elements ({} bytes)",
+            self.proof_elements,
+            self.proof_bytes()
+        )?;
+        writeln!(f, "prover: {} ({} total)", self.prover, self.prover.total())?;
+        writeln!(
+            f,
+            "verifier: {} ({} total)",
+            self.verifier,
+            self.verifier.total()
+        )?;
+        writeln!(
+            f,
+            "verifier key: {} bytes hashed, once per circuit",
+            self.key_hash_bytes
+        )?;
+        write!(
+            f,
+            "transcript: {} absorbs, {} challenges",
+            self.transcript.absorbs, self.transcript.challenges
+        )
+    }
+}
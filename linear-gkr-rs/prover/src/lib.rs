@@ -1,8 +1,28 @@
 //! “Slow‑track” prover: brute‑force evaluation of every gate in the circuit.
 
+mod cost;
+mod poseidon;
+mod proof;
+mod statement;
+mod sumcheck;
+mod trace;
+mod transcript;
+
 use ark_ff::{One, Zero};
-use circuit::{Circuit, GateType};
+use circuit::{Circuit, DataParallel, GateType, Layered};
 use field::FieldElement;
+use polynomial::mle::{eq_table, evaluate, line};
+
+pub use cost::{
+    estimate, estimate_layers, CostReport, FieldOps, LayerCost, TranscriptOps, FIELD_BYTES,
+};
+pub use poseidon::{poseidon_hash, Permutation, PoseidonSponge, PoseidonTranscript, SPONGE_WIDTH};
+pub use proof::{Challenges, ClaimReduction, LayerProof, Proof};
+pub use statement::{CommittedTable, Statement, StatementProof};
+pub use trace::{Divergence, Trace, TraceError, TraceRow};
+pub use transcript::{
+    mimc_compress, mimc_constants, Keccak256Transcript, MimcTranscript, Transcript, MIMC_ROUNDS,
+};
 
 pub struct Prover {
     /// values[layer][gate_id] → field element
@@ -12,56 +32,59 @@ pub struct Prover {
 impl Prover {
     /// Evaluate every gate of `c` and store intermediate values.
     pub fn evaluate(c: &Circuit) -> Self {
-        let mut values: Vec<Vec<FieldElement>> = Vec::with_capacity(c.layers.len());
+        Self::evaluate_with_inputs(c, &c.input_values())
+    }
+
+    /// Like [`Prover::evaluate`], but layer 0 takes `inputs` (slot `g` ← `inputs[g]`,
+    /// missing trailing slots are zero) instead of the values written in the circuit.
+    pub fn evaluate_with_inputs(c: &Circuit, inputs: &[FieldElement]) -> Self {
+        Self::evaluate_up_to(c, inputs, c.layers.len() - 1)
+    }
+
+    /// [`Prover::evaluate_with_inputs`] stopping after layer `last`, e.g. to
+    /// inspect a prefix of a circuit whose upper layers misbehave; `values`
+    /// then ends at layer `last`.
+    pub fn evaluate_up_to(c: &Circuit, inputs: &[FieldElement], last: usize) -> Self {
+        assert!(
+            last < c.layers.len(),
+            "layer {last} of a {}-layer circuit",
+            c.layers.len()
+        );
+        let mut values: Vec<Vec<FieldElement>> = Vec::with_capacity(last + 1);
 
         // ──────────────── layer‑0 (public inputs / dummies) ────────────────
-        let input_layer = &c.layers[0];
-        let max_id = input_layer.gates.keys().max().copied().unwrap_or(0);
-        let mut layer0 = vec![FieldElement::zero(); max_id + 1];
-
-        for (&id, gate) in input_layer.gates.iter() {
-            layer0[id] = match gate.ty {
-                GateType::Input => FieldElement::from(gate.u as u64),
-                GateType::Dummy => FieldElement::zero(),
-                _ => panic!("only INPUT / DUMMY allowed in layer‑0"),
-            };
-        }
-        values.push(layer0);
+        values.push(c.pad_inputs(inputs));
 
         // ─────────────── subsequent layers ───────────────
-        for (i, layer) in c.layers.iter().enumerate().skip(1) {
-            let max_id = layer.gates.keys().max().copied().unwrap_or(0);
-            let mut cur = vec![FieldElement::zero(); max_id + 1];
-
-            for (&id, gate) in layer.gates.iter() {
-                let out = match gate.ty {
-                    GateType::Add => values[i - 1][gate.u] + values[i - 1][gate.v],
-                    GateType::Mul => values[i - 1][gate.u] * values[i - 1][gate.v],
+        for layer in &c.layers[1..=last] {
+            let prev = values.last().unwrap();
+            let cur = layer
+                .ty
+                .iter()
+                .zip(&layer.u)
+                .zip(&layer.v)
+                .map(|((ty, &u), &v)| match ty {
+                    GateType::Add => prev[u] + prev[v],
+                    GateType::Mul => prev[u] * prev[v],
                     GateType::Dummy => FieldElement::zero(),
-                    GateType::Input => FieldElement::from(gate.u as u64),
-                    GateType::DirectRelay | GateType::Relay => values[i - 1][gate.u],
-                    GateType::Sum => {
-                        let mut acc = FieldElement::zero();
-                        for idx in gate.u..gate.v {
-                            acc += &values[i - 1][idx];
-                        }
-                        acc
-                    }
-                    GateType::Not => FieldElement::one() - values[i - 1][gate.u],
-                    GateType::Minus => values[i - 1][gate.u] - values[i - 1][gate.v],
+                    GateType::Input => FieldElement::from(u as u64),
+                    GateType::DirectRelay | GateType::Relay => prev[u],
+                    GateType::Sum => prev[u..v].iter().sum(),
+                    GateType::Not => FieldElement::one() - prev[u],
+                    GateType::Minus => prev[u] - prev[v],
                     GateType::Xor => {
-                        let x = values[i - 1][gate.u];
-                        let y = values[i - 1][gate.v];
+                        let (x, y) = (prev[u], prev[v]);
                         x + y - FieldElement::from(2u64) * x * y
                     }
                     GateType::Naab => {
-                        let x = values[i - 1][gate.u];
-                        let y = values[i - 1][gate.v];
+                        let (x, y) = (prev[u], prev[v]);
                         y - x * y
                     }
-                };
-                cur[id] = out;
-            }
+                    GateType::Const => layer.constants[u],
+                    GateType::ScalarMul => layer.constants[v] * prev[u],
+                    GateType::AddConst => prev[u] + layer.constants[v],
+                })
+                .collect();
             values.push(cur);
         }
 
@@ -72,4 +95,170 @@ impl Prover {
     pub fn outputs(&self) -> &[FieldElement] {
         self.values.last().expect("at least one layer")
     }
+
+    /// Evaluate every copy of `dp` on its own inputs (`instances[c]` for copy
+    /// `c`, see [`DataParallel::pad_inputs`]) and lay the layers out copy‑major.
+    pub fn evaluate_data_parallel(dp: &DataParallel, instances: &[Vec<FieldElement>]) -> Self {
+        let layer0 = dp.pad_inputs(instances);
+        let mut values: Vec<Vec<FieldElement>> = (0..dp.depth())
+            .map(|i| Vec::with_capacity(1 << dp.bit_length(i)))
+            .collect();
+        for inputs in layer0.chunks(dp.template.layers[0].len()) {
+            let copy = Self::evaluate_with_inputs(&dp.template, inputs);
+            for (all, layer) in values.iter_mut().zip(copy.values) {
+                all.extend(layer);
+            }
+        }
+        Self { values }
+    }
+
+    /// Prove `dp`'s template on every witness of `witnesses` with a single
+    /// proof, as the copies of `dp`.
+    ///
+    /// For `n` witnesses of a circuit `c`, `dp` is
+    /// [`DataParallel::batch`]`(c, n)`: `n` rounded up to a power of two
+    /// copies, those past the last witness evaluated on zero inputs. The proof
+    /// is checked against the same `dp` with `Verifier::verify_batch_proof`,
+    /// and the outputs of witness `k` are the `k`‑th slice of
+    /// [`DataParallel::copy_outputs`] on [`Proof::output_layer`].
+    ///
+    /// Wiring terms are taken from the template once and the verifier's work
+    /// on the predicates does not grow with the batch.
+    pub fn prove_batch(
+        dp: &DataParallel,
+        witnesses: &[Vec<FieldElement>],
+        transcript: &mut impl Transcript,
+    ) -> Proof {
+        Self::evaluate_data_parallel(dp, witnesses).prove(dp, transcript)
+    }
+
+    /// Prove that the evaluated values are consistent with `c`, from the
+    /// outputs down to the input layer.
+    ///
+    /// The transcript is first bound to `c` and to the input layer (see
+    /// [`Transcript::bind`]), so the proof holds for these inputs only.
+    ///
+    /// Each layer runs one sum‑check reducing the claim `α·Ṽᵢ(r₀) + β·Ṽᵢ(r₁)`
+    /// to claims on `Ṽᵢ₋₁(r_u)` and `Ṽᵢ₋₁(r_v)`, which fresh `α`, `β` combine
+    /// into the next layer's claim; the output layer starts with `α = 1, β = 0`.
+    pub fn prove<C: Layered>(&self, c: &C, transcript: &mut impl Transcript) -> Proof {
+        self.prove_with_challenges(c, transcript).0
+    }
+
+    /// [`Prover::prove`] with the claims of every layer passed down by
+    /// `reduction` instead of a random linear combination.
+    pub fn prove_with_reduction<C: Layered>(
+        &self,
+        c: &C,
+        reduction: ClaimReduction,
+        transcript: &mut impl Transcript,
+    ) -> Proof {
+        transcript.bind(c.digest(), &self.values[0]);
+        self.prove_layers(c, reduction, &[], transcript).0
+    }
+
+    /// [`Prover::prove`], also returning each layer's challenges (in the order
+    /// of `Proof::layers`) for protocols that continue from them.
+    pub fn prove_with_challenges<C: Layered>(
+        &self,
+        c: &C,
+        transcript: &mut impl Transcript,
+    ) -> (Proof, Vec<Challenges>) {
+        transcript.bind(c.digest(), &self.values[0]);
+        self.prove_layers(c, ClaimReduction::Combine, &[], transcript)
+    }
+
+    /// [`Prover::prove_with_challenges`] without binding the transcript to
+    /// `c` and its inputs, for sub‑proofs whose circuit and input layer are
+    /// already determined by what the transcript has absorbed.
+    pub fn prove_unbound<C: Layered>(
+        &self,
+        c: &C,
+        transcript: &mut impl Transcript,
+    ) -> (Proof, Vec<Challenges>) {
+        self.prove_layers(c, ClaimReduction::Combine, &[], transcript)
+    }
+
+    /// [`Prover::prove_unbound`] sending none of the `hidden` outputs (in
+    /// increasing order) either, for proofs that vouch for them otherwise
+    /// (see [`Prover::prove_statement`]).
+    pub fn prove_unbound_hiding<C: Layered>(
+        &self,
+        c: &C,
+        hidden: &[usize],
+        transcript: &mut impl Transcript,
+    ) -> (Proof, Vec<Challenges>) {
+        self.prove_layers(c, ClaimReduction::Combine, hidden, transcript)
+    }
+
+    /// Prove every layer, sending the outputs other than the asserted ones
+    /// and `hidden` (both in increasing order).
+    fn prove_layers<C: Layered>(
+        &self,
+        c: &C,
+        reduction: ClaimReduction,
+        hidden: &[usize],
+        transcript: &mut impl Transcript,
+    ) -> (Proof, Vec<Challenges>) {
+        // asserted outputs are known to be zero and not sent
+        let assertions = c.assertions();
+        let outputs: Vec<_> = self
+            .outputs()
+            .iter()
+            .enumerate()
+            .filter(|(g, _)| {
+                assertions.binary_search(g).is_err() && hidden.binary_search(g).is_err()
+            })
+            .map(|(_, &x)| x)
+            .collect();
+        transcript.absorb(&[&[reduction.tag()][..], &outputs].concat());
+        let top = c.depth() - 1;
+        let r0 = transcript.challenges(c.bit_length(top));
+        let mut ch = Challenges {
+            alpha: FieldElement::one(),
+            beta: FieldElement::zero(),
+            r1: r0.clone(),
+            r0,
+            ru: Vec::new(),
+            rv: Vec::new(),
+        };
+
+        let mut layers = Vec::with_capacity(top);
+        let mut challenges = Vec::with_capacity(top);
+        for i in (1..=top).rev() {
+            let mut betas = eq_table(&ch.r0, ch.alpha);
+            for (b, e) in betas.iter_mut().zip(eq_table(&ch.r1, ch.beta)) {
+                *b += e;
+            }
+            let prev = &self.values[i - 1];
+            let (mut proof, ru, rv) = sumcheck::prove_layer(c, i, &betas, prev, transcript);
+            (ch.ru, ch.rv) = (ru, rv);
+            challenges.push(ch.clone());
+            match reduction {
+                ClaimReduction::Combine => {
+                    ch.alpha = transcript.challenge();
+                    ch.beta = transcript.challenge();
+                    ch.r0 = std::mem::take(&mut ch.ru);
+                    ch.r1 = std::mem::take(&mut ch.rv);
+                }
+                ClaimReduction::Line => {
+                    proof.line = (2..=ch.ru.len() as u64)
+                        .map(|t| evaluate(prev, &line(&ch.ru, &ch.rv, FieldElement::from(t))))
+                        .collect();
+                    transcript.absorb(&proof.line);
+                    let r = transcript.challenge();
+                    ch.r0 = line(&ch.ru, &ch.rv, r);
+                    ch.r1 = ch.r0.clone();
+                    (ch.alpha, ch.beta) = (FieldElement::one(), FieldElement::zero());
+                }
+            }
+            layers.push(proof);
+        }
+        let proof = Proof {
+            outputs,
+            layers,
+            reduction,
+        };
+        (proof, challenges)
+    }
 }
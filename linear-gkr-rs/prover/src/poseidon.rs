@@ -0,0 +1,151 @@
+//! Poseidon over BN254 Fr with circomlib's parameters (`x⁵` S‑box, 8 full
+//! rounds, round constants and MDS matrices from `light-poseidon`), as a
+//! fixed‑length hash, a duplex sponge and a Fiat–Shamir transcript.
+
+use std::sync::OnceLock;
+
+use ark_ff::{Field, PrimeField, Zero};
+use field::FieldElement;
+use light_poseidon::parameters::bn254_x5::get_poseidon_parameters;
+use light_poseidon::PoseidonParameters;
+use sha3::{Digest, Keccak256};
+
+use crate::transcript::Transcript;
+
+/// The Poseidon permutation of one width.
+pub struct Permutation {
+    params: PoseidonParameters<FieldElement>,
+}
+
+impl Permutation {
+    /// circomlib's permutation over `width` elements (`2..=13`).
+    pub fn new(width: usize) -> Self {
+        let params = u8::try_from(width)
+            .ok()
+            .and_then(|t| get_poseidon_parameters(t).ok())
+            .unwrap_or_else(|| panic!("no Poseidon parameters for width {width}"));
+        Self { params }
+    }
+
+    pub fn width(&self) -> usize {
+        self.params.width
+    }
+
+    pub fn permute(&self, state: &mut [FieldElement]) {
+        let p = &self.params;
+        assert_eq!(state.len(), p.width, "state does not match the width");
+        let half = p.full_rounds / 2;
+        for round in 0..p.full_rounds + p.partial_rounds {
+            for (i, x) in state.iter_mut().enumerate() {
+                *x += p.ark[round * p.width + i];
+            }
+            if round < half || round >= half + p.partial_rounds {
+                for x in state.iter_mut() {
+                    *x = x.pow([p.alpha]);
+                }
+            } else {
+                state[0] = state[0].pow([p.alpha]);
+            }
+            let mixed: Vec<_> = p
+                .mds
+                .iter()
+                .map(|row| row.iter().zip(state.iter()).map(|(&m, &x)| m * x).sum())
+                .collect();
+            state.copy_from_slice(&mixed);
+        }
+    }
+}
+
+/// circomlib's `Poseidon(n)`: permute `[0, x₁, …, xₙ]` and return the first
+/// element.
+pub fn poseidon_hash(inputs: &[FieldElement]) -> FieldElement {
+    let mut state = vec![FieldElement::zero()];
+    state.extend_from_slice(inputs);
+    Permutation::new(state.len()).permute(&mut state);
+    state[0]
+}
+
+/// Width of the sponge's permutation: rate 2, capacity 1.
+pub const SPONGE_WIDTH: usize = 3;
+
+fn sponge_permutation() -> &'static Permutation {
+    static PERMUTATION: OnceLock<Permutation> = OnceLock::new();
+    PERMUTATION.get_or_init(|| Permutation::new(SPONGE_WIDTH))
+}
+
+/// Duplex sponge over the width‑3 permutation. `state[0]` is the capacity and
+/// starts as the domain separator; elements are added into `state[1..]`, and
+/// the permutation runs whenever the rate is full and before every squeeze.
+/// Every [`absorb`](Self::absorb) is one message, preceded by its length plus
+/// one, so that no two sequences of messages and squeezes feed the
+/// permutation the same elements: `[x, 0]` is not `[x]`, and absorbing `[0]`
+/// or `[]` before a squeeze is not the same as squeezing.
+#[derive(Clone)]
+pub struct PoseidonSponge {
+    state: [FieldElement; SPONGE_WIDTH],
+    /// Elements added to the rate since the last permutation.
+    absorbed: usize,
+}
+
+impl PoseidonSponge {
+    pub fn new(domain: FieldElement) -> Self {
+        Self {
+            state: [domain, FieldElement::zero(), FieldElement::zero()],
+            absorbed: 0,
+        }
+    }
+
+    fn permute(&mut self) {
+        sponge_permutation().permute(&mut self.state);
+        self.absorbed = 0;
+    }
+
+    pub fn absorb(&mut self, values: &[FieldElement]) {
+        let n = FieldElement::from(values.len() as u64 + 1);
+        for &v in std::iter::once(&n).chain(values) {
+            if self.absorbed == SPONGE_WIDTH - 1 {
+                self.permute();
+            }
+            self.state[1 + self.absorbed] += v;
+            self.absorbed += 1;
+        }
+    }
+
+    pub fn squeeze(&mut self) -> FieldElement {
+        self.permute();
+        self.state[1]
+    }
+}
+
+/// Fiat–Shamir over a [`PoseidonSponge`]: messages are absorbed element by
+/// element and every challenge is a squeeze. The domain separator is
+/// `keccak256(label) mod p`.
+#[derive(Clone)]
+pub struct PoseidonTranscript {
+    sponge: PoseidonSponge,
+}
+
+impl PoseidonTranscript {
+    pub fn new(label: &[u8]) -> Self {
+        let domain = FieldElement::from_be_bytes_mod_order(&Keccak256::digest(label));
+        Self {
+            sponge: PoseidonSponge::new(domain),
+        }
+    }
+}
+
+impl Default for PoseidonTranscript {
+    fn default() -> Self {
+        Self::new(b"linear-gkr")
+    }
+}
+
+impl Transcript for PoseidonTranscript {
+    fn absorb(&mut self, values: &[FieldElement]) {
+        self.sponge.absorb(values);
+    }
+
+    fn challenge(&mut self) -> FieldElement {
+        self.sponge.squeeze()
+    }
+}
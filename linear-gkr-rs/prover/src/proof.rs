@@ -0,0 +1,120 @@
+//! Non‑interactive GKR proofs.
+
+use ark_ff::Zero;
+use field::FieldElement;
+use polynomial::mle::eq3;
+use polynomial::QuadraticPoly;
+
+/// Sum‑check messages for one layer.
+pub struct LayerProof {
+    /// One quadratic per variable of the layer below: first the `b` variables
+    /// of `u`, then the `b` variables of `v`, least significant first.
+    pub rounds: Vec<QuadraticPoly>,
+    /// Claimed `Ṽ(r_u)` and `Ṽ(r_v)` for the layer below.
+    pub v_u: FieldElement,
+    pub v_v: FieldElement,
+    /// With [`ClaimReduction::Line`], `q(2), …, q(b)` where `q(t) = Ṽ(ℓ(t))` on
+    /// the line `ℓ(t) = (1 − t)·r_u + t·r_v` (`q(0)`, `q(1)` are `v_u`, `v_v`);
+    /// empty otherwise.
+    pub line: Vec<FieldElement>,
+}
+
+/// How a layer's two claims `Ṽ(r_u)`, `Ṽ(r_v)` become the claim on which the
+/// next layer's sum‑check starts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ClaimReduction {
+    /// `α·Ṽ(r_u) + β·Ṽ(r_v)` for random `α`, `β`, so the next sum‑check
+    /// carries both points.
+    #[default]
+    Combine,
+    /// Restrict `Ṽ` to the line through `r_u` and `r_v` and continue from the
+    /// single point `ℓ(r)` for a random `r`: `b − 1` more field elements per
+    /// layer and `b` extra evaluations of the layer below for the prover.
+    Line,
+}
+
+impl ClaimReduction {
+    /// The mode as the transcript absorbs it, in one message with the
+    /// outputs, so that a proof cannot be replayed under the other mode.
+    pub fn tag(self) -> FieldElement {
+        FieldElement::from(self as u64)
+    }
+}
+
+pub struct Proof {
+    /// Every slot of the output layer not asserted to be zero, dummies
+    /// included.
+    pub outputs: Vec<FieldElement>,
+    /// One entry per non‑input layer, from the output layer down.
+    pub layers: Vec<LayerProof>,
+    pub reduction: ClaimReduction,
+}
+
+impl Proof {
+    /// Number of field elements in the proof, outputs included.
+    pub fn num_field_elements(&self) -> usize {
+        let layers: usize = self
+            .layers
+            .iter()
+            .map(|lp| 3 * lp.rounds.len() + 2 + lp.line.len())
+            .sum();
+        self.outputs.len() + layers
+    }
+
+    /// The whole output layer of `2^bit_length` slots: the sent outputs in
+    /// order, with zeros at `assertions`. `None` if `assertions` is not
+    /// strictly increasing and in range, or the number of outputs does not
+    /// add up.
+    pub fn output_layer(
+        &self,
+        bit_length: usize,
+        assertions: &[usize],
+    ) -> Option<Vec<FieldElement>> {
+        let n = 1 << bit_length;
+        let increasing = assertions.windows(2).all(|w| w[0] < w[1]);
+        if !increasing
+            || self.outputs.len() + assertions.len() != n
+            || assertions.last() >= Some(&n)
+        {
+            return None;
+        }
+        let mut sent = self.outputs.iter();
+        let mut asserted = assertions.iter().peekable();
+        let layer = (0..n)
+            .map(|g| match asserted.next_if_eq(&&g) {
+                Some(_) => FieldElement::zero(),
+                None => *sent.next().expect("counted above"),
+            })
+            .collect();
+        Some(layer)
+    }
+}
+
+/// Challenges for one layer: the incoming claim `α·Ṽᵢ(r₀) + β·Ṽᵢ(r₁)` and the
+/// sum‑check point `(r_u, r_v)` on the layer below.
+#[derive(Clone, Debug)]
+pub struct Challenges {
+    pub alpha: FieldElement,
+    pub beta: FieldElement,
+    pub r0: Vec<FieldElement>,
+    pub r1: Vec<FieldElement>,
+    pub ru: Vec<FieldElement>,
+    pub rv: Vec<FieldElement>,
+}
+
+impl Challenges {
+    /// The challenges as seen by the template of a data‑parallel layer whose
+    /// template has `b` bits over `bp` bits: the low bits of every point, with
+    /// `α`, `β` scaled by `eq` over the copy bits (see `circuit::DataParallel`).
+    pub fn local(&self, b: usize, bp: usize) -> Self {
+        let (ru_hi, rv_hi) = (&self.ru[bp..], &self.rv[bp..]);
+        Self {
+            alpha: self.alpha * eq3(&self.r0[b..], ru_hi, rv_hi),
+            beta: self.beta * eq3(&self.r1[b..], ru_hi, rv_hi),
+            r0: self.r0[..b].to_vec(),
+            r1: self.r1[..b].to_vec(),
+            ru: self.ru[..bp].to_vec(),
+            rv: self.rv[..bp].to_vec(),
+        }
+    }
+}
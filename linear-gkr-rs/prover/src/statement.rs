@@ -0,0 +1,256 @@
+//! Proof statements with private inputs and hidden outputs.
+//!
+//! A [`Statement`] names the input slots given to the verifier and the output
+//! slots revealed to it. The private rest of layer 0 is committed (see the
+//! `commit` crate) as the table `p` of its values in slot order, so with
+//! `s_j` the `j`‑th private slot
+//!
+//! ```text
+//! Ṽ₀(r) = Σ_{g public} eq(r, g)·x_g + Σ_j eq(r, s_j)·p_j
+//! ```
+//!
+//! where the verifier evaluates the first sum itself. The prover claims the
+//! second at the input claim's two points and proves the claims with one
+//! sum‑check of `Σ_y p̃(y)·S(y)`, `S(y) = Σ_k γ_k·eq(r_k, s_y)` for random
+//! `γ_k`, ending in an opening of `p̃`; the verifier evaluates `S̃` from the
+//! slot map. Private values can only land on private slots. Hidden outputs are
+//! committed the same way, with the slots neither revealed nor asserted, and
+//! claimed at the top layer's point.
+//!
+//! The transcript is bound to the circuit and the public part of layer 0,
+//! then absorbs the slot lists and both commitments before the first
+//! challenge. The commitments are not blinded, and neither are the claims and
+//! sum‑check messages over them: a statement proof keeps private values off
+//! the wire but is not zero‑knowledge.
+//!
+//! Statement proofs use [`ClaimReduction::Combine`](crate::ClaimReduction).
+//! With every input public and every output revealed ([`Statement::public`])
+//! nothing is committed and the GKR proof is the one [`Prover::prove`] makes,
+//! after the slot lists.
+
+use ark_ff::{One, Zero};
+use circuit::{Circuit, Layered};
+use commit::{commit, open, Commitment, Generators, Opening};
+use field::FieldElement;
+use polynomial::mle::eq_table;
+use polynomial::QuadraticPoly;
+
+use crate::{sumcheck, Proof, Prover, Transcript};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Statement {
+    /// Layer‑0 slots given to the verifier, in increasing order; the others
+    /// are private.
+    pub public_inputs: Vec<usize>,
+    /// Output slots whose values are sent, in increasing order; the others
+    /// are hidden, apart from the asserted ones, which are zero.
+    pub revealed_outputs: Vec<usize>,
+}
+
+/// The slots of `0..n` not in `slots` (increasing), or not in `skip` either.
+fn complement(n: usize, slots: &[usize], skip: &[usize]) -> Vec<usize> {
+    (0..n)
+        .filter(|g| slots.binary_search(g).is_err() && skip.binary_search(g).is_err())
+        .collect()
+}
+
+/// `values` at `slots`, in order and padded with zeros to a power of two.
+fn compact(values: &[FieldElement], slots: &[usize]) -> Vec<FieldElement> {
+    let mut table: Vec<_> = slots.iter().map(|&g| values[g]).collect();
+    table.resize(slots.len().next_power_of_two(), FieldElement::zero());
+    table
+}
+
+impl Statement {
+    /// Every input public and every output that is not asserted revealed.
+    pub fn public(c: &Circuit) -> Self {
+        let top = &c.layers[c.layers.len() - 1];
+        Self {
+            public_inputs: (0..c.layers[0].len()).collect(),
+            revealed_outputs: complement(top.len(), &c.assertions, &[]),
+        }
+    }
+
+    /// Whether the statement fits `c`: slots increasing and in range, and no
+    /// asserted output revealed.
+    pub fn is_valid(&self, c: &Circuit) -> bool {
+        let fits = |slots: &[usize], n: usize| {
+            slots.windows(2).all(|w| w[0] < w[1]) && slots.last().is_none_or(|&g| g < n)
+        };
+        let top = &c.layers[c.layers.len() - 1];
+        fits(&self.public_inputs, c.layers[0].len())
+            && fits(&self.revealed_outputs, top.len())
+            && self
+                .revealed_outputs
+                .iter()
+                .all(|g| c.assertions.binary_search(g).is_err())
+    }
+
+    /// Layer‑0 slots that are not public.
+    pub fn private_inputs(&self, c: &Circuit) -> Vec<usize> {
+        complement(c.layers[0].len(), &self.public_inputs, &[])
+    }
+
+    /// Output slots neither revealed nor asserted.
+    pub fn hidden_outputs(&self, c: &Circuit) -> Vec<usize> {
+        let top = &c.layers[c.layers.len() - 1];
+        complement(top.len(), &self.revealed_outputs, &c.assertions)
+    }
+
+    /// The public inputs' values out of a layer‑0 assignment (missing
+    /// trailing slots are zero).
+    pub fn public_values(&self, inputs: &[FieldElement]) -> Vec<FieldElement> {
+        let value = |g: usize| inputs.get(g).copied().unwrap_or_default();
+        self.public_inputs.iter().map(|&g| value(g)).collect()
+    }
+
+    /// Bind `transcript` to `c` (by its `Layered::digest`), the public
+    /// inputs' `values` (in order) and the statement: layer 0 with the private
+    /// slots zeroed goes to [`Transcript::bind`], then the two slot lists are
+    /// absorbed.
+    pub fn bind(
+        &self,
+        c: &Circuit,
+        digest: FieldElement,
+        values: &[FieldElement],
+        transcript: &mut impl Transcript,
+    ) {
+        let mut layer0 = vec![FieldElement::zero(); c.layers[0].len()];
+        for (&g, &x) in self.public_inputs.iter().zip(values) {
+            layer0[g] = x;
+        }
+        transcript.bind(digest, &layer0);
+        for slots in [&self.public_inputs, &self.revealed_outputs] {
+            let slots: Vec<_> = slots
+                .iter()
+                .map(|&g| FieldElement::from(g as u64))
+                .collect();
+            transcript.absorb(&slots);
+        }
+    }
+
+    /// Pedersen bases for commitments to `c`'s input and output layers.
+    pub fn generators(c: &Circuit) -> Generators {
+        let top = &c.layers[c.layers.len() - 1];
+        Generators::new(c.layers[0].bit_length.max(top.bit_length))
+    }
+}
+
+/// A table committed in slot order, its claimed evaluations at the verifier's
+/// points as a table over the whole layer, and the sum‑check reducing them to
+/// one opening of the committed table.
+#[derive(Clone, Debug)]
+pub struct CommittedTable {
+    pub commitment: Commitment,
+    /// `Σ_j eq(r, s_j)·p_j` at each point `r`.
+    pub evals: Vec<FieldElement>,
+    pub rounds: Vec<QuadraticPoly>,
+    /// `p̃(ρ)` at the sum‑check point `ρ`.
+    pub value: FieldElement,
+    pub opening: Opening,
+}
+
+impl CommittedTable {
+    /// Prove the evaluations at `points` of the layer holding `table[j]` at
+    /// `slots[j]` and zeros elsewhere; `commitment` is `commit(table)` and
+    /// already absorbed.
+    pub fn prove(
+        commitment: Commitment,
+        table: &[FieldElement],
+        slots: &[usize],
+        points: &[&[FieldElement]],
+        transcript: &mut impl Transcript,
+    ) -> Self {
+        let eqs: Vec<_> = points
+            .iter()
+            .map(|r| eq_table(r, FieldElement::one()))
+            .collect();
+        let evals: Vec<_> = eqs
+            .iter()
+            .map(|eq| slots.iter().zip(table).map(|(&g, &x)| eq[g] * x).sum())
+            .collect();
+        transcript.absorb(&evals);
+        let gammas = transcript.challenges(points.len());
+        let mut weights = vec![FieldElement::zero(); table.len()];
+        for (w, &g) in weights.iter_mut().zip(slots) {
+            *w = eqs.iter().zip(&gammas).map(|(eq, &y)| y * eq[g]).sum();
+        }
+
+        let mut values = table.to_vec();
+        let mut zeros = vec![FieldElement::zero(); table.len()];
+        let mut rounds = Vec::new();
+        let rho = sumcheck::run(
+            &mut values,
+            &mut weights,
+            &mut zeros,
+            &mut rounds,
+            transcript,
+        );
+        let value = values[0];
+        transcript.absorb(&[value]);
+        Self {
+            commitment,
+            evals,
+            rounds,
+            value,
+            opening: open(table, &rho),
+        }
+    }
+}
+
+pub struct StatementProof {
+    /// Sends the revealed outputs only.
+    pub gkr: Proof,
+    /// Private inputs, claimed at the input claim's two points; `None` if
+    /// every input is public.
+    pub private_inputs: Option<CommittedTable>,
+    /// Hidden outputs, claimed at the top layer's point; `None` if no output
+    /// is hidden.
+    pub hidden_outputs: Option<CommittedTable>,
+}
+
+impl Prover {
+    /// Prove the evaluated values consistent with `c` for `statement` (see
+    /// the module docs).
+    pub fn prove_statement(
+        &self,
+        c: &Circuit,
+        statement: &Statement,
+        transcript: &mut impl Transcript,
+    ) -> StatementProof {
+        assert!(statement.is_valid(c), "statement does not fit the circuit");
+        let gens = Statement::generators(c);
+        let hidden = statement.hidden_outputs(c);
+        let slots = [statement.private_inputs(c), hidden.clone()];
+        let values = [&self.values[0][..], self.outputs()];
+        let tables = [0, 1].map(|k| (!slots[k].is_empty()).then(|| compact(values[k], &slots[k])));
+        let commitments = tables
+            .each_ref()
+            .map(|t| t.as_ref().map(|t| commit(&gens, t)));
+        let public = statement.public_values(&self.values[0]);
+        statement.bind(c, c.digest(), &public, transcript);
+        for commitment in commitments.iter().flatten() {
+            transcript.absorb(&commitment.to_field_elements());
+        }
+
+        let (gkr, challenges) = self.prove_unbound_hiding(c, &hidden, transcript);
+        let last = challenges
+            .last()
+            .expect("circuit has a layer above the inputs");
+        let points = [
+            vec![&last.ru[..], &last.rv[..]],
+            vec![&challenges[0].r0[..]],
+        ];
+        let [private_inputs, hidden_outputs] = [0, 1].map(|k| {
+            let (table, commitment) = (tables[k].as_ref()?, commitments[k].clone()?);
+            Some(CommittedTable::prove(
+                commitment, table, &slots[k], &points[k], transcript,
+            ))
+        });
+        StatementProof {
+            gkr,
+            private_inputs,
+            hidden_outputs,
+        }
+    }
+}
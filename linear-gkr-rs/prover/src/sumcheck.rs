@@ -0,0 +1,101 @@
+//! Linear‑time sum‑check for one layer (two phases, as in Libra).
+//!
+//! With `β(g) = α·eq(r₀, g) + β·eq(r₁, g)` the layer claim is
+//!
+//! ```text
+//! Σ_{u,v} Σ_{terms t} β(g_t)·eq(u, u_t)·eq(v, v_t)·(cst + lin_u·V(u) + lin_v·V(v) + mul·V(u)·V(v))
+//! ```
+//!
+//! Summing out `v` first leaves `Σ_u V(u)·A₁(u) + A₀(u)`, whose tables are
+//! filled in one pass over the terms; once `u` is bound to `r_u` the same is
+//! done for `v` with tables `B₁`, `B₀` weighted by `eq(r_u, u_t)`.
+
+use ark_ff::{One, Zero};
+use circuit::Layered;
+use field::FieldElement;
+use polynomial::mle::{eq_table, fold};
+use polynomial::QuadraticPoly;
+
+use crate::proof::LayerProof;
+use crate::transcript::Transcript;
+
+/// Prove the claim on layer `layer` weighted by `betas`, returning the proof
+/// and the points `(r_u, r_v)` at which the layer below must be opened.
+pub(crate) fn prove_layer<C: Layered + ?Sized>(
+    c: &C,
+    layer: usize,
+    betas: &[FieldElement],
+    prev: &[FieldElement],
+    transcript: &mut impl Transcript,
+) -> (LayerProof, Vec<FieldElement>, Vec<FieldElement>) {
+    let n = 1 << c.bit_length(layer - 1);
+    debug_assert_eq!(prev.len(), n);
+    let mut rounds = Vec::with_capacity(2 * c.bit_length(layer - 1));
+
+    // phase 1: u
+    let (mut a1, mut a0) = (vec![FieldElement::zero(); n], vec![FieldElement::zero(); n]);
+    c.for_each_term(layer, &mut |g, t| {
+        let (w, vv) = (betas[g], prev[t.v]);
+        a1[t.u] += w * (t.lin_u + t.mul * vv);
+        a0[t.u] += w * (t.cst + t.lin_v * vv);
+    });
+    let mut v = prev.to_vec();
+    let ru = run(&mut v, &mut a1, &mut a0, &mut rounds, transcript);
+    let v_u = v[0];
+    transcript.absorb(&[v_u]);
+
+    // phase 2: v, with u fixed at r_u
+    let eq_u = eq_table(&ru, FieldElement::one());
+    let (mut b1, mut b0) = (vec![FieldElement::zero(); n], vec![FieldElement::zero(); n]);
+    c.for_each_term(layer, &mut |g, t| {
+        let w = betas[g] * eq_u[t.u];
+        b1[t.v] += w * (t.lin_v + t.mul * v_u);
+        b0[t.v] += w * (t.cst + t.lin_u * v_u);
+    });
+    let mut v = prev.to_vec();
+    let rv = run(&mut v, &mut b1, &mut b0, &mut rounds, transcript);
+    let v_v = v[0];
+    transcript.absorb(&[v_v]);
+
+    (
+        LayerProof {
+            rounds,
+            v_u,
+            v_v,
+            line: Vec::new(),
+        },
+        ru,
+        rv,
+    )
+}
+
+/// Sum‑check of `Σ_x V(x)·T₁(x) + T₀(x)`, binding the lowest variable first;
+/// returns the challenges, leaving each table folded to its final value.
+pub(crate) fn run(
+    v: &mut Vec<FieldElement>,
+    t1: &mut Vec<FieldElement>,
+    t0: &mut Vec<FieldElement>,
+    rounds: &mut Vec<QuadraticPoly>,
+    transcript: &mut impl Transcript,
+) -> Vec<FieldElement> {
+    let mut r = Vec::new();
+    while v.len() > 1 {
+        let mut p = QuadraticPoly::default();
+        for j in 0..v.len() / 2 {
+            let (v0, dv) = (v[2 * j], v[2 * j + 1] - v[2 * j]);
+            let (x0, dx) = (t1[2 * j], t1[2 * j + 1] - t1[2 * j]);
+            let (c0, dc) = (t0[2 * j], t0[2 * j + 1] - t0[2 * j]);
+            p.a += dv * dx;
+            p.b += v0 * dx + dv * x0 + dc;
+            p.c += v0 * x0 + c0;
+        }
+        transcript.absorb(&[p.a, p.b, p.c]);
+        let x = transcript.challenge();
+        fold(v, x);
+        fold(t1, x);
+        fold(t0, x);
+        rounds.push(p);
+        r.push(x);
+    }
+    r
+}
@@ -0,0 +1,268 @@
+//! Witness traces: every evaluated gate with its type, wiring, input values
+//! and value, as CSV or JSON, and a diff that finds where two traces of the
+//! same circuit first disagree.
+
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+use ark_ff::PrimeField;
+use circuit::{Circuit, GateType};
+use field::FieldElement;
+use serde::{Deserialize, Serialize};
+
+use crate::Prover;
+
+#[derive(thiserror::Error, Debug)]
+pub enum TraceError {
+    #[error("line {line}: {msg}")]
+    Csv { line: usize, msg: String },
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("invalid field element {0:?}")]
+    Field(String),
+}
+
+/// One gate of a trace.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceRow {
+    pub layer: usize,
+    pub gate: usize,
+    pub ty: GateType,
+    pub u: usize,
+    pub v: usize,
+    /// Values of the previous layer's gates that this gate reads.
+    pub inputs: Vec<FieldElement>,
+    pub value: FieldElement,
+}
+
+/// Evaluated gates in layer order, then gate order. Layer 0 lists every slot
+/// (dummies there hold supplied inputs); above it dummies are left out.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Trace {
+    pub rows: Vec<TraceRow>,
+}
+
+/// Where two traces first differ; `None` on a side that has no such gate.
+#[derive(Debug)]
+pub struct Divergence {
+    pub left: Option<TraceRow>,
+    pub right: Option<TraceRow>,
+}
+
+impl Divergence {
+    /// `(layer, gate)` of the divergent gate.
+    pub fn position(&self) -> (usize, usize) {
+        let row = self.left.as_ref().or(self.right.as_ref());
+        let row = row.expect("a divergence has at least one side");
+        (row.layer, row.gate)
+    }
+}
+
+fn decimal(x: &FieldElement) -> String {
+    x.into_bigint().to_string()
+}
+
+fn parse_field(s: &str) -> Result<FieldElement, TraceError> {
+    FieldElement::from_str(s).map_err(|_| TraceError::Field(s.to_string()))
+}
+
+fn parse_gate_type(s: &str) -> Option<GateType> {
+    (0..=u8::MAX)
+        .map_while(|t| GateType::try_from(t).ok())
+        .find(|ty| format!("{ty:?}") == s)
+}
+
+/// [`TraceRow`] as written in JSON, with field elements in decimal.
+#[derive(Serialize, Deserialize)]
+struct JsonRow {
+    layer: usize,
+    gate: usize,
+    #[serde(rename = "type")]
+    ty: GateType,
+    u: usize,
+    v: usize,
+    inputs: Vec<String>,
+    value: String,
+}
+
+impl Trace {
+    /// Trace of the layers evaluated by `p` (see [`Prover::evaluate_up_to`]).
+    pub fn new(c: &Circuit, p: &Prover) -> Self {
+        let mut rows = Vec::new();
+        for (i, values) in p.values.iter().enumerate() {
+            let layer = &c.layers[i];
+            for (g, gate) in layer.gates() {
+                if i > 0 && gate.ty == GateType::Dummy {
+                    continue;
+                }
+                let inputs = match i {
+                    0 => Vec::new(),
+                    _ => gate.inputs().iter().map(|&u| p.values[i - 1][u]).collect(),
+                };
+                rows.push(TraceRow {
+                    layer: i,
+                    gate: g,
+                    ty: gate.ty,
+                    u: gate.u,
+                    v: gate.v,
+                    inputs,
+                    value: values[g],
+                });
+            }
+        }
+        Self { rows }
+    }
+
+    /// `layer,gate,type,u,v,inputs,value`, inputs separated by `;`.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("layer,gate,type,u,v,inputs,value\n");
+        for r in &self.rows {
+            let inputs: Vec<_> = r.inputs.iter().map(decimal).collect();
+            writeln!(
+                out,
+                "{},{},{:?},{},{},{},{}",
+                r.layer,
+                r.gate,
+                r.ty,
+                r.u,
+                r.v,
+                inputs.join(";"),
+                decimal(&r.value)
+            )
+            .unwrap();
+        }
+        out
+    }
+
+    pub fn from_csv(text: &str) -> Result<Self, TraceError> {
+        let mut rows = Vec::new();
+        for (n, line) in text.lines().enumerate().skip(1) {
+            let err = |msg: &str| TraceError::Csv {
+                line: n + 1,
+                msg: msg.to_string(),
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let cols: Vec<_> = line.split(',').map(str::trim).collect();
+            let [layer, gate, ty, u, v, inputs, value] = cols[..] else {
+                return Err(err("expected 7 columns"));
+            };
+            let int = |s: &str| s.parse::<usize>().map_err(|e| err(&e.to_string()));
+            rows.push(TraceRow {
+                layer: int(layer)?,
+                gate: int(gate)?,
+                ty: parse_gate_type(ty).ok_or_else(|| err("unknown gate type"))?,
+                u: int(u)?,
+                v: int(v)?,
+                inputs: inputs
+                    .split(';')
+                    .filter(|s| !s.is_empty())
+                    .map(parse_field)
+                    .collect::<Result<_, _>>()?,
+                value: parse_field(value)?,
+            });
+        }
+        Ok(Self { rows })
+    }
+
+    pub fn to_json(&self) -> String {
+        let rows: Vec<_> = self
+            .rows
+            .iter()
+            .map(|r| JsonRow {
+                layer: r.layer,
+                gate: r.gate,
+                ty: r.ty,
+                u: r.u,
+                v: r.v,
+                inputs: r.inputs.iter().map(decimal).collect(),
+                value: decimal(&r.value),
+            })
+            .collect();
+        serde_json::to_string_pretty(&rows).expect("trace serializes")
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, TraceError> {
+        let rows: Vec<JsonRow> = serde_json::from_str(text)?;
+        let rows = rows
+            .into_iter()
+            .map(|r| {
+                Ok(TraceRow {
+                    layer: r.layer,
+                    gate: r.gate,
+                    ty: r.ty,
+                    u: r.u,
+                    v: r.v,
+                    inputs: r
+                        .inputs
+                        .iter()
+                        .map(|s| parse_field(s))
+                        .collect::<Result<_, _>>()?,
+                    value: parse_field(&r.value)?,
+                })
+            })
+            .collect::<Result<_, TraceError>>()?;
+        Ok(Self { rows })
+    }
+
+    /// Either format, told apart by the first non‑blank character.
+    pub fn parse(text: &str) -> Result<Self, TraceError> {
+        if text.trim_start().starts_with('[') {
+            Self::from_json(text)
+        } else {
+            Self::from_csv(text)
+        }
+    }
+
+    /// The first gate, in layer then gate order, that is missing from one
+    /// trace or differs in wiring or value. As values only flow upwards, this
+    /// is where the two evaluations start to disagree.
+    pub fn diff(&self, other: &Trace) -> Option<Divergence> {
+        let key = |r: &TraceRow| (r.layer, r.gate);
+        let (mut a, mut b) = (self.rows.iter().peekable(), other.rows.iter().peekable());
+        loop {
+            let (left, right) = match (a.peek(), b.peek()) {
+                (None, None) => return None,
+                (Some(x), Some(y)) if key(x) == key(y) => (a.next(), b.next()),
+                (Some(x), Some(y)) if key(x) < key(y) => (a.next(), None),
+                (Some(_), None) => (a.next(), None),
+                _ => (None, b.next()),
+            };
+            if left != right {
+                return Some(Divergence {
+                    left: left.cloned(),
+                    right: right.cloned(),
+                });
+            }
+        }
+    }
+}
+
+impl fmt::Display for TraceRow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inputs: Vec<_> = self.inputs.iter().map(decimal).collect();
+        write!(
+            f,
+            "{:?}(u={}, v={}) inputs [{}] = {}",
+            self.ty,
+            self.u,
+            self.v,
+            inputs.join(", "),
+            decimal(&self.value)
+        )
+    }
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (layer, gate) = self.position();
+        writeln!(f, "first divergence at layer {layer}, gate {gate}")?;
+        let side = |r: &Option<TraceRow>| match r {
+            Some(r) => r.to_string(),
+            None => "(missing)".to_string(),
+        };
+        writeln!(f, "  left:  {}", side(&self.left))?;
+        write!(f, "  right: {}", side(&self.right))
+    }
+}
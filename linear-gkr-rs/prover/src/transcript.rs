@@ -0,0 +1,150 @@
+//! Fiat–Shamir transcripts: the prover and the verifier absorb the same
+//! messages and derive the same challenges from them.
+
+use std::sync::OnceLock;
+
+use ark_ff::{BigInteger, PrimeField};
+use field::FieldElement;
+use sha3::{Digest, Keccak256};
+
+pub trait Transcript {
+    fn absorb(&mut self, values: &[FieldElement]);
+
+    fn challenge(&mut self) -> FieldElement;
+
+    fn challenges(&mut self, n: usize) -> Vec<FieldElement> {
+        (0..n).map(|_| self.challenge()).collect()
+    }
+
+    /// Absorb what a proof is about before its first challenge: the digest of
+    /// the circuit (see `Layered::digest`) followed by the padded layer‑0
+    /// assignment, as one message.
+    fn bind(&mut self, digest: FieldElement, inputs: &[FieldElement]) {
+        let mut statement = Vec::with_capacity(inputs.len() + 1);
+        statement.push(digest);
+        statement.extend_from_slice(inputs);
+        self.absorb(&statement);
+    }
+}
+
+/// Keccak‑256 hash chain over 32‑byte big‑endian field elements, chosen so
+/// that it can be replayed with the EVM's `keccak256`:
+///
+/// ```text
+/// absorb(x₁…xₙ):  s ← keccak256(s ‖ x₁ ‖ … ‖ xₙ)
+/// challenge():    s ← keccak256(s);  return s mod p
+/// ```
+///
+/// The initial state is `keccak256(label)`.
+#[derive(Clone)]
+pub struct Keccak256Transcript {
+    state: [u8; 32],
+}
+
+impl Keccak256Transcript {
+    pub fn new(label: &[u8]) -> Self {
+        Self {
+            state: Keccak256::digest(label).into(),
+        }
+    }
+}
+
+impl Default for Keccak256Transcript {
+    fn default() -> Self {
+        Self::new(b"linear-gkr")
+    }
+}
+
+impl Transcript for Keccak256Transcript {
+    fn absorb(&mut self, values: &[FieldElement]) {
+        let mut h = Keccak256::new();
+        h.update(self.state);
+        for v in values {
+            h.update(v.into_bigint().to_bytes_be());
+        }
+        self.state = h.finalize().into();
+    }
+
+    fn challenge(&mut self) -> FieldElement {
+        self.state = Keccak256::digest(self.state).into();
+        FieldElement::from_be_bytes_mod_order(&self.state)
+    }
+}
+
+/// Rounds of the MiMC‑5 block cipher over BN254 Fr: `⌈log₅ p⌉`.
+pub const MIMC_ROUNDS: usize = 110;
+
+/// Round constants `keccak256("linear-gkr-mimc" ‖ be32(i)) mod p`.
+pub fn mimc_constants() -> &'static [FieldElement] {
+    static CONSTANTS: OnceLock<Vec<FieldElement>> = OnceLock::new();
+    CONSTANTS.get_or_init(|| {
+        (0..MIMC_ROUNDS as u32)
+            .map(|i| {
+                let mut h = Keccak256::new();
+                h.update(b"linear-gkr-mimc");
+                h.update(i.to_be_bytes());
+                FieldElement::from_be_bytes_mod_order(&h.finalize())
+            })
+            .collect()
+    })
+}
+
+/// Miyaguchi–Preneel compression `E_s(x) + x + s`, where `E_k` is MiMC‑5
+/// keyed by `k`: rounds `x ← (x + k + cᵢ)⁵`, then `+ k`.
+pub fn mimc_compress(s: FieldElement, x: FieldElement) -> FieldElement {
+    let mut y = x;
+    for &c in mimc_constants() {
+        let t = y + s + c;
+        let t2 = t * t;
+        y = t2 * t2 * t;
+    }
+    y + s + s + x
+}
+
+/// Transcript over Fr built from [`mimc_compress`], cheap to replay inside an
+/// arithmetic circuit:
+///
+/// ```text
+/// absorb(x₁…xₙ):  s ← h(s, n + 1);  s ← h(s, xᵢ) for each i
+/// challenge():    s ← h(s, 0);      return s
+/// ```
+///
+/// The length prefix keeps absorbs and challenges apart. The initial state is
+/// `keccak256(label) mod p`.
+#[derive(Clone)]
+pub struct MimcTranscript {
+    state: FieldElement,
+}
+
+impl MimcTranscript {
+    pub fn new(label: &[u8]) -> Self {
+        Self {
+            state: FieldElement::from_be_bytes_mod_order(&Keccak256::digest(label)),
+        }
+    }
+
+    pub fn state(&self) -> FieldElement {
+        self.state
+    }
+}
+
+impl Default for MimcTranscript {
+    fn default() -> Self {
+        Self::new(b"linear-gkr")
+    }
+}
+
+impl Transcript for MimcTranscript {
+    fn absorb(&mut self, values: &[FieldElement]) {
+        let n = FieldElement::from(values.len() as u64 + 1);
+        self.state = mimc_compress(self.state, n);
+        for &v in values {
+            self.state = mimc_compress(self.state, v);
+        }
+    }
+
+    fn challenge(&mut self) -> FieldElement {
+        self.state = mimc_compress(self.state, FieldElement::from(0u64));
+        self.state
+    }
+}
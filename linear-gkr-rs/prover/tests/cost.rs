@@ -0,0 +1,116 @@
+//! Cost estimates made from a circuit's shape match the proofs it gets.
+
+use circuit::{load_from_path, Builder, Circuit, Gate, GateType, Layer, LayerReader};
+use prover::{estimate, estimate_layers, ClaimReduction, Keccak256Transcript, Prover, FIELD_BYTES};
+
+/// Layers of different widths, including a one‑variable output layer.
+fn circuit() -> Circuit {
+    let mut b = Builder::new();
+    let x = b.inputs(5);
+    let p = b.mul(x[0], x[1]);
+    let s = b.add(x[2], x[3]);
+    let q = b.mul(p, s);
+    let t = b.sub(q, x[4]);
+    b.output(t);
+    b.output(s);
+    b.build()
+}
+
+#[test]
+fn proof_size_matches_the_estimate() {
+    let circuits = [
+        circuit(),
+        load_from_path("../examples/test_circuit.txt").unwrap(),
+    ];
+    for c in &circuits {
+        let prover = Prover::evaluate(c);
+        for reduction in [ClaimReduction::Combine, ClaimReduction::Line] {
+            let mut transcript = Keccak256Transcript::default();
+            let proof = prover.prove_with_reduction(c, reduction, &mut transcript);
+            let report = estimate(c, reduction);
+            assert_eq!(
+                report.proof_elements,
+                proof.num_field_elements(),
+                "{reduction:?}"
+            );
+            let layers: usize = report.layers.iter().map(|l| l.proof_elements).sum();
+            assert_eq!(report.proof_elements, proof.outputs.len() + layers);
+        }
+    }
+}
+
+#[test]
+fn gate_counts_cover_every_slot() {
+    let c = circuit();
+    let report = estimate(&c, ClaimReduction::Combine);
+    for (layer, cost) in c.layers.iter().zip(&report.layers) {
+        let gates: usize = cost.gates.iter().map(|&(_, n)| n).sum();
+        assert_eq!(gates, layer.len());
+        assert_eq!(cost.bit_length, layer.bit_length);
+    }
+}
+
+/// Eight inputs multiplied down a tree of structured layers.
+fn tree() -> Circuit {
+    let mut inputs = Layer::dummy(3);
+    for g in 0..8 {
+        inputs.set(
+            g,
+            &Gate {
+                ty: GateType::Input,
+                u: g + 1,
+                v: 0,
+            },
+        );
+    }
+    let mut layers = vec![inputs];
+    layers.extend((0..3).rev().map(|b| Layer::binary_tree(GateType::Mul, b)));
+    Circuit {
+        layers,
+        assertions: Vec::new(),
+    }
+}
+
+#[test]
+fn closed_form_layers_are_hashed_once_and_skipped_per_proof() {
+    let c = tree();
+    let report = estimate(&c, ClaimReduction::Combine);
+    assert!(report.layers[1..]
+        .iter()
+        .all(|l| l.closed_form && l.terms > 0));
+
+    // the same gates without their closed forms
+    let mut bare = tree();
+    for layer in &mut bare.layers {
+        layer.predicate = None;
+    }
+    let bare = estimate(&bare, ClaimReduction::Combine);
+    assert!(report.verifier.total() < bare.verifier.total());
+    assert_eq!(report.key_hash_bytes, bare.key_hash_bytes);
+
+    // every term above the inputs is hashed, coefficients and all
+    let terms: usize = report.layers[1..].iter().map(|l| l.terms).sum();
+    assert!(report.key_hash_bytes > terms * 4 * FIELD_BYTES);
+}
+
+#[test]
+fn estimates_stream_from_a_reader() {
+    let path = "../examples/test_circuit.txt";
+    let c = load_from_path(path).unwrap();
+    for reduction in [ClaimReduction::Combine, ClaimReduction::Line] {
+        let layers = LayerReader::open(path).unwrap();
+        let streamed = estimate_layers(layers, &[], reduction).unwrap();
+        assert_eq!(streamed.to_string(), estimate(&c, reduction).to_string());
+    }
+
+    // and with the assertions of a circuit built in memory
+    let c = tree();
+    let layers = c.layers.iter().map(Ok::<_, ()>);
+    let streamed = estimate_layers(layers, &[0], ClaimReduction::Combine).unwrap();
+    let mut asserted = tree();
+    asserted.assertions = vec![0];
+    assert_eq!(
+        streamed.to_string(),
+        estimate(&asserted, ClaimReduction::Combine).to_string()
+    );
+}
@@ -0,0 +1,40 @@
+use ark_ff::Zero;
+use circuit::{Circuit, LayerReader};
+use field::FieldElement;
+use prover::Prover;
+
+fn circuit(text: &str) -> Circuit {
+    let layers = LayerReader::new(text.as_bytes())
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    Circuit {
+        layers,
+        assertions: Vec::new(),
+    }
+}
+
+#[test]
+fn values_padded_to_hypercube() {
+    // 5 inputs (max_id = 4) → 8 slots; 3 adds (max_id = 2) → 4 slots; 1 mul → 2 slots
+    let c = circuit(
+        "3\n\
+         5 0 0 1 0 0 1 2 0 0 2 3 0 0 3 4 0 0 4 5 0\n\
+         3 1 0 0 1 1 1 2 3 1 2 4 4\n\
+         1 2 0 0 2",
+    );
+    let p = Prover::evaluate(&c);
+    for (layer, vals) in c.layers.iter().zip(&p.values) {
+        assert_eq!(vals.len(), 1 << layer.bit_length);
+    }
+    assert_eq!(p.values[0].len(), 8);
+    assert_eq!(p.values[1].len(), 4);
+    assert_eq!(p.values[2].len(), 2);
+
+    assert_eq!(p.values[1][..3], [3u64.into(), 7u64.into(), 10u64.into()]);
+    assert_eq!(p.outputs()[0], FieldElement::from(30u64));
+    // padding slots evaluate to zero
+    assert!(p.values[0][5..].iter().all(Zero::is_zero));
+    assert!(p.values[1][3].is_zero());
+    assert!(p.outputs()[1].is_zero());
+}
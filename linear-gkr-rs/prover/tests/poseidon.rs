@@ -0,0 +1,114 @@
+use ark_ff::{PrimeField, Zero};
+use field::FieldElement;
+use light_poseidon::{Poseidon, PoseidonHasher};
+use prover::{
+    poseidon_hash, Permutation, PoseidonSponge, PoseidonTranscript, Transcript, SPONGE_WIDTH,
+};
+
+fn fe(hex: &str) -> FieldElement {
+    let bytes: Vec<u8> = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect();
+    FieldElement::from_be_bytes_mod_order(&bytes)
+}
+
+fn ints(xs: &[u64]) -> Vec<FieldElement> {
+    xs.iter().map(|&x| FieldElement::from(x)).collect()
+}
+
+#[test]
+fn matches_circomlib() {
+    // circomlibjs `poseidon([1])` and `poseidon([1, 2])`
+    assert_eq!(
+        poseidon_hash(&ints(&[1])),
+        fe("29176100eaa962bdc1fe6c654d6a3c130e96a4d1168b33848b897dc502820133")
+    );
+    assert_eq!(
+        poseidon_hash(&ints(&[1, 2])),
+        fe("115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a")
+    );
+}
+
+#[test]
+fn matches_light_poseidon() {
+    for n in 1..=12 {
+        let inputs = ints(&(1..=n as u64).map(|x| x * x + 7).collect::<Vec<_>>());
+        let expected = Poseidon::<FieldElement>::new_circom(n)
+            .unwrap()
+            .hash(&inputs)
+            .unwrap();
+        assert_eq!(poseidon_hash(&inputs), expected, "{n} inputs");
+    }
+}
+
+#[test]
+fn sponge_rate_and_squeeze() {
+    // the length prefix and one element fill the rate; squeezing permutes
+    // once more and reads state[1]
+    let mut sponge = PoseidonSponge::new(FieldElement::zero());
+    sponge.absorb(&ints(&[1, 2]));
+    let permutation = Permutation::new(SPONGE_WIDTH);
+    let mut state = ints(&[0, 3, 1]);
+    permutation.permute(&mut state);
+    state[1] += FieldElement::from(2u64);
+    permutation.permute(&mut state);
+    assert_eq!(sponge.squeeze(), state[1]);
+
+    // messages are absorbed whole: the same elements split differently
+    // are different messages
+    let mut a = PoseidonSponge::new(FieldElement::from(5u64));
+    a.absorb(&ints(&[1, 2, 3, 4, 5]));
+    let mut b = a.clone();
+    let mut c = PoseidonSponge::new(FieldElement::from(5u64));
+    c.absorb(&ints(&[1, 2, 3]));
+    c.absorb(&ints(&[4, 5]));
+    assert_eq!(a.squeeze(), b.squeeze());
+    assert_ne!(b.squeeze(), c.squeeze());
+}
+
+#[test]
+fn sponge_messages_are_length_delimited() {
+    let squeeze = |messages: &[&[u64]]| {
+        let mut sponge = PoseidonSponge::new(FieldElement::zero());
+        for m in messages {
+            sponge.absorb(&ints(m));
+        }
+        sponge.squeeze()
+    };
+    let x = 7;
+    let cases: [&[&[u64]]; 7] = [
+        &[],
+        &[&[]],
+        &[&[0]],
+        &[&[x]],
+        &[&[x, 0]],
+        &[&[x], &[]],
+        &[&[x], &[0]],
+    ];
+    let outputs: Vec<_> = cases.iter().map(|m| squeeze(m)).collect();
+    for (i, a) in outputs.iter().enumerate() {
+        for (j, b) in outputs.iter().enumerate().skip(i + 1) {
+            assert_ne!(a, b, "{:?} and {:?}", cases[i], cases[j]);
+        }
+    }
+    // known answer: `[7]` under the zero domain
+    assert_eq!(
+        outputs[3],
+        fe("0a87ca7cb762d3a29902cc8af0ae5cc4331ddb98b5ef17bea91b3756b67d3ed8")
+    );
+}
+
+#[test]
+fn transcript_known_answer() {
+    let mut t = PoseidonTranscript::default();
+    t.absorb(&ints(&[1, 2, 3]));
+    assert_eq!(
+        t.challenge(),
+        fe("03a4e9329c1bf45c3e28ff91c6a7bbaf9216ebf2f6e726ae8072daef19fb7214")
+    );
+    assert_eq!(
+        t.challenge(),
+        fe("16eab6b6897ccbb27b8aa00248ba13bd263211ea00a0711c6982fc1cb63922e8")
+    );
+}
@@ -0,0 +1,143 @@
+//! Witness traces: CSV and JSON round trips, diffs that find the first
+//! divergent gate, and evaluation stopping at a given layer.
+
+use ark_ff::One;
+use circuit::{Builder, Circuit};
+use field::FieldElement;
+use prover::{Prover, Trace, TraceError};
+
+fn field(values: &[u64]) -> Vec<FieldElement> {
+    values.iter().copied().map(FieldElement::from).collect()
+}
+
+/// `(x0 − x1)·x2 + 5` and `3·x3`, so that values wrap around the modulus.
+fn circuit() -> Circuit {
+    let mut b = Builder::new();
+    let x = b.inputs(4);
+    let d = b.sub(x[0], x[1]);
+    let p = b.mul(d, x[2]);
+    let e = b.add_const(p, FieldElement::from(5u64));
+    let s = b.scale(x[3], FieldElement::from(3u64));
+    b.output(e);
+    b.output(s);
+    b.build()
+}
+
+fn trace(c: &Circuit, inputs: &[u64]) -> Trace {
+    Trace::new(c, &Prover::evaluate_with_inputs(c, &field(inputs)))
+}
+
+#[test]
+fn csv_and_json_round_trip() {
+    let c = circuit();
+    let t = trace(&c, &[2, 9, 7, 11]);
+    assert!(t.rows.iter().any(|r| r.value == -FieldElement::from(44u64)));
+
+    let csv = t.to_csv();
+    let json = t.to_json();
+    assert_eq!(Trace::from_csv(&csv).unwrap(), t);
+    assert_eq!(Trace::from_json(&json).unwrap(), t);
+    assert_eq!(Trace::parse(&csv).unwrap(), t);
+    assert_eq!(Trace::parse(&json).unwrap(), t);
+    // CSV → JSON → CSV
+    let through_json = Trace::from_json(&Trace::from_csv(&csv).unwrap().to_json()).unwrap();
+    assert_eq!(through_json.to_csv(), csv);
+}
+
+#[test]
+fn malformed_traces_are_rejected() {
+    let header = "layer,gate,type,u,v,inputs,value\n";
+    let bad = |row: &str| Trace::from_csv(&format!("{header}0,0,Input,0,0,,3\n{row}\n"));
+    assert!(matches!(
+        bad("1,0,Add,0,1,3;4"),
+        Err(TraceError::Csv { line: 3, .. })
+    ));
+    assert!(matches!(
+        bad("1,0,Plus,0,1,3;4,7"),
+        Err(TraceError::Csv { line: 3, .. })
+    ));
+    assert!(matches!(
+        bad("1,0,Add,0,1,3;x,7"),
+        Err(TraceError::Field(_))
+    ));
+    assert!(matches!(Trace::parse("[{}]"), Err(TraceError::Json(_))));
+}
+
+#[test]
+fn diff_finds_the_first_divergent_gate() {
+    let c = circuit();
+    let t = trace(&c, &[2, 9, 7, 11]);
+    assert!(t.diff(&t.clone()).is_none());
+
+    // another x3 changes `3·x3` too, but layer 0 comes first
+    let other = trace(&c, &[2, 9, 7, 12]);
+    let d = t.diff(&other).expect("traces differ");
+    assert_eq!(d.position(), (0, 3));
+    assert_eq!(d.left.unwrap().value, FieldElement::from(11u64));
+    assert_eq!(d.right.unwrap().value, FieldElement::from(12u64));
+
+    // a wrong value above the inputs
+    let mut wrong = t.clone();
+    let k = wrong.rows.iter().position(|r| r.layer == 2).unwrap();
+    wrong.rows[k].value += FieldElement::one();
+    let d = t.diff(&wrong).unwrap();
+    assert_eq!(d.position(), (2, wrong.rows[k].gate));
+    assert_eq!(d.left.as_ref(), Some(&t.rows[k]));
+    assert_eq!(d.right.as_ref(), Some(&wrong.rows[k]));
+    // every row before it agrees
+    assert!(t.rows[..k] == wrong.rows[..k]);
+}
+
+#[test]
+fn diff_reports_missing_rows() {
+    let c = circuit();
+    let t = trace(&c, &[2, 9, 7, 11]);
+    let k = t.rows.iter().position(|r| r.layer == 1).unwrap();
+    let mut short = t.clone();
+    let removed = short.rows.remove(k);
+
+    let d = t.diff(&short).unwrap();
+    assert_eq!(d.position(), (removed.layer, removed.gate));
+    assert_eq!(d.left.as_ref(), Some(&removed));
+    assert!(d.right.is_none());
+    assert!(d.to_string().contains("right: (missing)"));
+
+    let d = short.diff(&t).unwrap();
+    assert!(d.left.is_none());
+    assert_eq!(d.right, Some(removed));
+
+    // a trace that stops early is missing its last layers
+    let mut prefix = t.clone();
+    prefix.rows.retain(|r| r.layer == 0);
+    let d = t.diff(&prefix).unwrap();
+    assert_eq!(d.position(), (1, t.rows[k].gate));
+}
+
+#[test]
+fn evaluate_up_to_stops_at_the_given_layer() {
+    let c = circuit();
+    let inputs = field(&[2, 9, 7, 11]);
+    let top = c.layers.len() - 1;
+    let full = Prover::evaluate_with_inputs(&c, &inputs);
+
+    let inputs_only = Prover::evaluate_up_to(&c, &inputs, 0);
+    assert_eq!(inputs_only.values, [c.pad_inputs(&inputs)]);
+    let t = Trace::new(&c, &inputs_only);
+    assert!(t.rows.iter().all(|r| r.layer == 0));
+    assert_eq!(t.rows.len(), c.layers[0].len());
+
+    for last in 1..=top {
+        let p = Prover::evaluate_up_to(&c, &inputs, last);
+        assert_eq!(p.values, full.values[..=last]);
+    }
+    let whole = Trace::new(&c, &Prover::evaluate_up_to(&c, &inputs, top));
+    assert_eq!(whole, Trace::new(&c, &full));
+}
+
+#[test]
+#[should_panic(expected = "layer 4 of a 4-layer circuit")]
+fn evaluate_up_to_rejects_layers_past_the_top() {
+    let c = circuit();
+    assert_eq!(c.layers.len(), 4);
+    Prover::evaluate_up_to(&c, &field(&[1, 2, 3, 4]), 4);
+}
@@ -0,0 +1,563 @@
+//! Preprocessing mode: Spark‑style commitments to the wiring predicates, so
+//! that verifying an arbitrary circuit never reads its gates.
+//!
+//! Preprocessing lists every wiring term `t` of a layer (see [`circuit::Term`])
+//! as entry `k` of sparse arrays — `row[k] = g_t`, `col_u[k] = u_t`,
+//! `col_v[k] = v_t` and the four coefficients — and commits to them. A
+//! layer's final sum‑check equation then reads
+//!
+//! ```text
+//! claim = Σ_k val_k(v_u, v_v) · T_g[row[k]] · T_u[col_u[k]] · T_v[col_v[k]]
+//! ```
+//!
+//! where `val_k(x, y) = cst_k + lin_u_k·x + lin_v_k·y + mul_k·x·y` and the
+//! tables are `T_g = α·eq(r₀, ·) + β·eq(r₁, ·)`, `T_u = eq(r_u, ·)`,
+//! `T_v = eq(r_v, ·)`. The prover commits to the three looked‑up columns
+//! `E_g`, `E_u`, `E_v` and proves the sum with a degree‑4 sum‑check; offline
+//! memory checking (see `memory`) shows the columns really are lookups into
+//! the tables, which the verifier can evaluate in closed form. Every remaining
+//! claim is an opening of a commitment, so the online verifier does
+//! `O(√terms)` group operations per layer instead of a pass over the gates.
+
+mod memory;
+mod sumcheck;
+
+use ark_ff::{One, Zero};
+use circuit::{bit_length, Circuit, Layer, Layered};
+use commit::{commit, open, Commitment, Generators, Opening};
+use field::FieldElement;
+use polynomial::mle::{eq, eq_table, evaluate};
+use prover::{Challenges, Proof, Prover, Transcript};
+use verifier::{Verifier, WiringClaim};
+
+use memory::Memory;
+
+/// Commitments to one memory's address column and timestamps.
+#[derive(Clone)]
+pub struct MemoryKey {
+    /// log₂ of the number of cells.
+    pub log_size: usize,
+    pub addr: Commitment,
+    pub read_ts: Commitment,
+    pub final_ts: Commitment,
+}
+
+#[derive(Clone)]
+pub struct LayerKey {
+    /// log₂ of the padded number of terms.
+    pub log_terms: usize,
+    /// `cst`, `lin_u`, `lin_v` and `mul` of every term.
+    pub vals: [Commitment; 4],
+    /// Lookups into the `g`, `u` and `v` tables.
+    pub memories: [MemoryKey; 3],
+}
+
+/// Everything the verifier needs to know about the circuit.
+#[derive(Clone)]
+pub struct VerifierKey {
+    /// The circuit's `Layered::digest`, which proofs are bound to.
+    pub digest: FieldElement,
+    pub bit_lengths: Vec<usize>,
+    pub assertions: Vec<usize>,
+    /// `layers[i - 1]` describes layer `i`.
+    pub layers: Vec<LayerKey>,
+    gens: Generators,
+}
+
+impl VerifierKey {
+    /// Bind the circuit into the transcript.
+    fn absorb(&self, transcript: &mut impl Transcript) {
+        let sizes: Vec<_> = self
+            .bit_lengths
+            .iter()
+            .map(|&b| FieldElement::from(b as u64))
+            .collect();
+        transcript.absorb(&sizes);
+        for layer in &self.layers {
+            transcript.absorb(&[FieldElement::from(layer.log_terms as u64)]);
+            let memories = layer
+                .memories
+                .iter()
+                .flat_map(|m| [&m.addr, &m.read_ts, &m.final_ts]);
+            for c in layer.vals.iter().chain(memories) {
+                transcript.absorb(&c.to_field_elements());
+            }
+        }
+    }
+}
+
+/// Sparse term arrays of one layer.
+struct SparseLayer {
+    log_terms: usize,
+    vals: [Vec<FieldElement>; 4],
+    memories: [Memory; 3],
+}
+
+impl SparseLayer {
+    fn new(layer: &Layer, prev_bit_length: usize) -> Self {
+        let mut terms = Vec::new();
+        for g in 0..layer.len() {
+            layer.terms(g, |t| terms.push((g, t)));
+        }
+        let log_terms = bit_length(terms.len());
+        let n = 1 << log_terms;
+
+        let mut vals: [Vec<FieldElement>; 4] = Default::default();
+        let mut addrs: [Vec<usize>; 3] = Default::default();
+        for (g, t) in terms {
+            for (col, x) in vals.iter_mut().zip([t.cst, t.lin_u, t.lin_v, t.mul]) {
+                col.push(x);
+            }
+            for (col, a) in addrs.iter_mut().zip([g, t.u, t.v]) {
+                col.push(a);
+            }
+        }
+        // padding terms are zero and read cell 0
+        for col in &mut vals {
+            col.resize(n, FieldElement::zero());
+        }
+        for col in &mut addrs {
+            col.resize(n, 0);
+        }
+        let [rows, us, vs] = addrs;
+        Self {
+            log_terms,
+            vals,
+            memories: [
+                Memory::new(rows, layer.bit_length),
+                Memory::new(us, prev_bit_length),
+                Memory::new(vs, prev_bit_length),
+            ],
+        }
+    }
+
+    fn commit(&self, gens: &Generators) -> LayerKey {
+        LayerKey {
+            log_terms: self.log_terms,
+            vals: self.vals.each_ref().map(|col| commit(gens, col)),
+            memories: self.memories.each_ref().map(|m| MemoryKey {
+                log_size: m.final_ts.len().trailing_zeros() as usize,
+                addr: commit(gens, &m.addr_values()),
+                read_ts: commit(gens, &m.read_ts),
+                final_ts: commit(gens, &m.final_ts),
+            }),
+        }
+    }
+}
+
+pub struct ProverKey {
+    vk: VerifierKey,
+    layers: Vec<SparseLayer>,
+}
+
+/// Commit to the wiring of every layer of `c`.
+pub fn preprocess(c: &Circuit) -> (ProverKey, VerifierKey) {
+    let layers: Vec<_> = (1..c.layers.len())
+        .map(|i| SparseLayer::new(&c.layers[i], c.layers[i - 1].bit_length))
+        .collect();
+    let bit_lengths: Vec<_> = c.layers.iter().map(|l| l.bit_length).collect();
+    let max_vars = layers
+        .iter()
+        .map(|l| l.log_terms)
+        .chain(bit_lengths.iter().copied())
+        .max()
+        .unwrap_or(0);
+    let gens = Generators::new(max_vars);
+    let vk = VerifierKey {
+        digest: c.digest(),
+        bit_lengths,
+        assertions: c.assertions.clone(),
+        layers: layers.iter().map(|l| l.commit(&gens)).collect(),
+        gens,
+    };
+    (
+        ProverKey {
+            vk: vk.clone(),
+            layers,
+        },
+        vk,
+    )
+}
+
+/// Evidence for one layer's final equation.
+pub struct LayerWiringProof {
+    /// Commitments to `E_g`, `E_u`, `E_v`.
+    pub lookups: [Commitment; 3],
+    /// Degree‑4 sum‑check over the terms.
+    pub rounds: Vec<Vec<FieldElement>>,
+    /// `val(ρ)` and `E_g(ρ)`, `E_u(ρ)`, `E_v(ρ)` at the sum‑check point `ρ`.
+    pub val: FieldElement,
+    pub lookups_at: [FieldElement; 3],
+    /// The twelve memory‑checking products.
+    pub products: Proof,
+    /// At both points left by `products`, for each memory: `addr`, `E` and
+    /// `read_ts` at the term variables and `final_ts` at the cell variables.
+    pub leaves: [[[FieldElement; 4]; 3]; 2],
+    /// Batched openings of everything above.
+    pub openings: Vec<Opening>,
+}
+
+pub struct SparkProof {
+    pub gkr: Proof,
+    /// One per layer, in the order of `gkr.layers`.
+    pub wiring: Vec<LayerWiringProof>,
+}
+
+/// Open the random combination of `tables` at `point`.
+fn open_batch(
+    point: &[FieldElement],
+    tables: &[&[FieldElement]],
+    transcript: &mut impl Transcript,
+) -> Opening {
+    let mu = transcript.challenge();
+    let mut combined = vec![FieldElement::zero(); tables[0].len()];
+    let mut pow = FieldElement::one();
+    for t in tables {
+        for (c, &x) in combined.iter_mut().zip(t.iter()) {
+            *c += pow * x;
+        }
+        pow *= mu;
+    }
+    open(&combined, point)
+}
+
+/// Check an [`open_batch`] opening of `claims` (commitment, value) at `point`.
+fn check_batch(
+    gens: &Generators,
+    point: &[FieldElement],
+    claims: &[(&Commitment, FieldElement)],
+    opening: &Opening,
+    transcript: &mut impl Transcript,
+) -> bool {
+    let mu = transcript.challenge();
+    let mut terms = Vec::with_capacity(claims.len());
+    let (mut value, mut pow) = (FieldElement::zero(), FieldElement::one());
+    for &(c, v) in claims {
+        terms.push((pow, c));
+        value += pow * v;
+        pow *= mu;
+    }
+    Commitment::combine(&terms).verify(gens, point, value, opening)
+}
+
+/// `T_g`, `T_u`, `T_v` for a layer's challenges.
+fn tables(ch: &Challenges) -> [Vec<FieldElement>; 3] {
+    let mut g = eq_table(&ch.r0, ch.alpha);
+    for (x, y) in g.iter_mut().zip(eq_table(&ch.r1, ch.beta)) {
+        *x += y;
+    }
+    let one = FieldElement::one();
+    [g, eq_table(&ch.ru, one), eq_table(&ch.rv, one)]
+}
+
+/// MLE of memory `j`'s table at `x`, in closed form.
+fn table_at(ch: &Challenges, j: usize, x: &[FieldElement]) -> FieldElement {
+    match j {
+        0 => ch.alpha * eq(&ch.r0, x) + ch.beta * eq(&ch.r1, x),
+        1 => eq(&ch.ru, x),
+        _ => eq(&ch.rv, x),
+    }
+}
+
+fn prove_layer(
+    key: &SparseLayer,
+    gens: &Generators,
+    ch: &Challenges,
+    v_u: FieldElement,
+    v_v: FieldElement,
+    transcript: &mut impl Transcript,
+) -> LayerWiringProof {
+    let tables = tables(ch);
+    let lookups: [Vec<FieldElement>; 3] =
+        std::array::from_fn(|j| key.memories[j].addr.iter().map(|&a| tables[j][a]).collect());
+    let lookup_coms = lookups.each_ref().map(|e| commit(gens, e));
+    for c in &lookup_coms {
+        transcript.absorb(&c.to_field_elements());
+    }
+
+    let [cst, lin_u, lin_v, mul] = &key.vals;
+    let val: Vec<_> = (0..cst.len())
+        .map(|k| cst[k] + lin_u[k] * v_u + lin_v[k] * v_v + mul[k] * v_u * v_v)
+        .collect();
+    let mut st = [
+        val.clone(),
+        lookups[0].clone(),
+        lookups[1].clone(),
+        lookups[2].clone(),
+    ];
+    let (rounds, rho) = sumcheck::prove(&mut st, transcript);
+    let (val_at, lookups_at) = (st[0][0], [st[1][0], st[2][0], st[3][0]]);
+    transcript.absorb(&[val_at, lookups_at[0], lookups_at[1], lookups_at[2]]);
+
+    let gamma = transcript.challenge();
+    let tau = transcript.challenge();
+    let log_leaves = log_leaves(
+        key.log_terms,
+        key.memories
+            .each_ref()
+            .map(|m| m.final_ts.len().trailing_zeros() as usize),
+    );
+    let instances: Vec<_> = (0..3)
+        .flat_map(|j| key.memories[j].leaves(&tables[j], &lookups[j], gamma, tau, log_leaves))
+        .collect();
+    // the leaves follow from what is already absorbed (the key, the lookup
+    // commitments, γ and τ), and the verifier never sees them: no binding
+    let dp = memory::product_circuit(log_leaves);
+    let (products, pch) =
+        Prover::evaluate_data_parallel(&dp, &instances).prove_unbound(&dp, transcript);
+    let last = pch.last().expect("product circuit has layers");
+
+    let addrs = key.memories.each_ref().map(|m| m.addr_values());
+    let mut leaves = [[[FieldElement::zero(); 4]; 3]; 2];
+    for (point, out) in [&last.ru, &last.rv].into_iter().zip(&mut leaves) {
+        let y = &point[..key.log_terms];
+        for (j, m) in key.memories.iter().enumerate() {
+            let n = m.final_ts.len().trailing_zeros() as usize;
+            out[j] = [
+                evaluate(&addrs[j], y),
+                evaluate(&lookups[j], y),
+                evaluate(&m.read_ts, y),
+                evaluate(&m.final_ts, &point[..n]),
+            ];
+        }
+    }
+    transcript.absorb(&leaves.concat().concat());
+
+    let mut openings = vec![open_batch(
+        &rho,
+        &[&val, &lookups[0], &lookups[1], &lookups[2]],
+        transcript,
+    )];
+    for point in [&last.ru, &last.rv] {
+        let m = &key.memories;
+        let term_tables: Vec<&[FieldElement]> = (0..3)
+            .flat_map(|j| [&addrs[j][..], &lookups[j][..], &m[j].read_ts[..]])
+            .collect();
+        openings.push(open_batch(
+            &point[..key.log_terms],
+            &term_tables,
+            transcript,
+        ));
+        let n_g = m[0].final_ts.len().trailing_zeros() as usize;
+        let n = m[1].final_ts.len().trailing_zeros() as usize;
+        openings.push(open_batch(&point[..n_g], &[&m[0].final_ts], transcript));
+        openings.push(open_batch(
+            &point[..n],
+            &[&m[1].final_ts, &m[2].final_ts],
+            transcript,
+        ));
+    }
+
+    LayerWiringProof {
+        lookups: lookup_coms,
+        rounds,
+        val: val_at,
+        lookups_at,
+        products,
+        leaves,
+        openings,
+    }
+}
+
+/// Leaf count (log₂) of the product trees: the largest memory or term array.
+fn log_leaves(log_terms: usize, log_sizes: [usize; 3]) -> usize {
+    log_sizes.into_iter().fold(log_terms, usize::max)
+}
+
+fn verify_layer(
+    key: &LayerKey,
+    gens: &Generators,
+    claim: &WiringClaim,
+    proof: &LayerWiringProof,
+    transcript: &mut impl Transcript,
+) -> bool {
+    let ch = &claim.challenges;
+    for c in &proof.lookups {
+        transcript.absorb(&c.to_field_elements());
+    }
+    if proof.rounds.len() != key.log_terms {
+        return false;
+    }
+    let Some((rho, last)) = sumcheck::verify(&proof.rounds, 4, claim.value, transcript) else {
+        return false;
+    };
+    let [e_g, e_u, e_v] = proof.lookups_at;
+    if last != proof.val * e_g * e_u * e_v {
+        return false;
+    }
+    transcript.absorb(&[proof.val, e_g, e_u, e_v]);
+
+    let gamma = transcript.challenge();
+    let tau = transcript.challenge();
+    let log_sizes = key.memories.each_ref().map(|m| m.log_size);
+    let l = log_leaves(key.log_terms, log_sizes);
+    let Some(input) = Verifier::reduce_with(
+        &memory::product_bit_lengths(l),
+        &[],
+        &proof.products,
+        transcript,
+        |i, ch| memory::product_wiring(l, i, ch),
+    ) else {
+        return false;
+    };
+    let out = &proof.products.outputs;
+    if (0..3).any(|j| out[4 * j] * out[4 * j + 1] != out[4 * j + 2] * out[4 * j + 3]) {
+        return false;
+    }
+
+    // the product circuit's input layer, from the claimed leaf evaluations
+    let one = FieldElement::one();
+    let gamma2 = gamma * gamma;
+    let leaf_mle = |point: &[FieldElement], vals: &[[FieldElement; 4]; 3]| {
+        let (lo, hi) = point.split_at(l);
+        let mut acc = FieldElement::zero();
+        for (j, &[addr, e, ts, fin]) in vals.iter().enumerate() {
+            let n = log_sizes[j];
+            let x = &lo[..n];
+            let init = gamma2 * memory::identity(x) + gamma * table_at(ch, j, x) - tau;
+            let rs = gamma2 * addr + gamma * e + ts - tau;
+            let leaves = [
+                memory::padded(lo, n, init),
+                memory::padded(lo, key.log_terms, rs + one),
+                memory::padded(lo, key.log_terms, rs),
+                memory::padded(lo, n, init + fin),
+            ];
+            for (kind, leaf) in leaves.into_iter().enumerate() {
+                let copy: Vec<_> = (0..memory::LOG_COPIES)
+                    .map(|b| FieldElement::from(((4 * j + kind) >> b & 1) as u64))
+                    .collect();
+                acc += eq(hi, &copy) * leaf;
+            }
+        }
+        acc
+    };
+    if input.claim
+        != input.alpha * leaf_mle(&input.r0, &proof.leaves[0])
+            + input.beta * leaf_mle(&input.r1, &proof.leaves[1])
+    {
+        return false;
+    }
+    transcript.absorb(&proof.leaves.concat().concat());
+
+    // openings
+    if proof.openings.len() != 7 {
+        return false;
+    }
+    let [cst, lin_u, lin_v, mul] = &key.vals;
+    let val = Commitment::combine(&[
+        (one, cst),
+        (claim.v_u, lin_u),
+        (claim.v_v, lin_v),
+        (claim.v_u * claim.v_v, mul),
+    ]);
+    let at_rho = [
+        (&val, proof.val),
+        (&proof.lookups[0], e_g),
+        (&proof.lookups[1], e_u),
+        (&proof.lookups[2], e_v),
+    ];
+    if !check_batch(gens, &rho, &at_rho, &proof.openings[0], transcript) {
+        return false;
+    }
+    let m = &key.memories;
+    for (p, (point, vals)) in [&input.r0, &input.r1]
+        .into_iter()
+        .zip(&proof.leaves)
+        .enumerate()
+    {
+        let openings = &proof.openings[1 + 3 * p..];
+        let at_terms: Vec<_> = (0..3)
+            .flat_map(|j| {
+                [
+                    (&m[j].addr, vals[j][0]),
+                    (&proof.lookups[j], vals[j][1]),
+                    (&m[j].read_ts, vals[j][2]),
+                ]
+            })
+            .collect();
+        let ok = check_batch(
+            gens,
+            &point[..key.log_terms],
+            &at_terms,
+            &openings[0],
+            transcript,
+        ) && check_batch(
+            gens,
+            &point[..m[0].log_size],
+            &[(&m[0].final_ts, vals[0][3])],
+            &openings[1],
+            transcript,
+        ) && check_batch(
+            gens,
+            &point[..m[1].log_size],
+            &[(&m[1].final_ts, vals[1][3]), (&m[2].final_ts, vals[2][3])],
+            &openings[2],
+            transcript,
+        );
+        if !ok {
+            return false;
+        }
+    }
+    true
+}
+
+/// Prove `prover`'s evaluation of `c` in preprocessing mode.
+pub fn prove(
+    pk: &ProverKey,
+    c: &Circuit,
+    prover: &Prover,
+    transcript: &mut impl Transcript,
+) -> SparkProof {
+    pk.vk.absorb(transcript);
+    let (gkr, challenges) = prover.prove_with_challenges(c, transcript);
+    let top = c.layers.len() - 1;
+    let wiring = (1..=top)
+        .rev()
+        .zip(&challenges)
+        .zip(&gkr.layers)
+        .map(|((i, ch), lp)| {
+            prove_layer(
+                &pk.layers[i - 1],
+                &pk.vk.gens,
+                ch,
+                lp.v_u,
+                lp.v_v,
+                transcript,
+            )
+        })
+        .collect();
+    SparkProof { gkr, wiring }
+}
+
+/// Check `proof` on the layer‑0 assignment `inputs` using only the key.
+pub fn verify(
+    vk: &VerifierKey,
+    inputs: &[FieldElement],
+    proof: &SparkProof,
+    transcript: &mut impl Transcript,
+) -> bool {
+    let n = 1 << vk.bit_lengths[0];
+    if inputs.len() > n {
+        return false;
+    }
+    let mut layer0 = inputs.to_vec();
+    layer0.resize(n, FieldElement::zero());
+
+    vk.absorb(transcript);
+    transcript.bind(vk.digest, &layer0);
+    let Some((input, claims)) =
+        Verifier::reduce_deferred(&vk.bit_lengths, &vk.assertions, &proof.gkr, transcript)
+    else {
+        return false;
+    };
+    if proof.wiring.len() != claims.len() {
+        return false;
+    }
+    for (claim, lp) in claims.iter().zip(&proof.wiring) {
+        if !verify_layer(&vk.layers[claim.layer - 1], &vk.gens, claim, lp, transcript) {
+            eprintln!("wiring opening failed at layer {}", claim.layer);
+            return false;
+        }
+    }
+    input.check(&layer0)
+}
@@ -0,0 +1,133 @@
+//! Offline memory checking for the lookups `E[k] = T[addr[k]]`.
+//!
+//! Addresses are fixed by the circuit, so the read timestamps (`read_ts[k]`:
+//! accesses to `addr[k]` before `k`) and the final counters (`final_ts[a]`)
+//! are committed once during preprocessing. With `h(a, v, t) = a·γ² + v·γ + t − τ`
+//! the lookups are consistent iff
+//!
+//! ```text
+//! Π_a h(a, T[a], 0) · Π_k h(addr[k], E[k], read_ts[k] + 1)
+//!     = Π_k h(addr[k], E[k], read_ts[k]) · Π_a h(a, T[a], final_ts[a])
+//! ```
+//!
+//! All twelve products of a layer's three memories are proven at once with a
+//! data‑parallel GKR proof over a binary tree of `Mul` gates, one copy per
+//! product (`Init`, `WS`, `RS`, `Audit` for each memory). Leaf vectors shorter
+//! than the tree are padded with ones.
+
+use ark_ff::{One, Zero};
+use circuit::{BinaryTree, Circuit, DataParallel, GateType, Layer, Wiring, WiringPredicate};
+use field::FieldElement;
+use prover::Challenges;
+
+pub(crate) const LOG_COPIES: usize = 4;
+
+pub(crate) struct Memory {
+    pub addr: Vec<usize>,
+    pub read_ts: Vec<FieldElement>,
+    pub final_ts: Vec<FieldElement>,
+}
+
+impl Memory {
+    /// Timestamps for reading `addr` in order from a memory of `2^log_size` cells.
+    pub fn new(addr: Vec<usize>, log_size: usize) -> Self {
+        let mut count = vec![0u64; 1 << log_size];
+        let read_ts = addr
+            .iter()
+            .map(|&a| {
+                count[a] += 1;
+                FieldElement::from(count[a] - 1)
+            })
+            .collect();
+        Self {
+            addr,
+            read_ts,
+            final_ts: count.into_iter().map(FieldElement::from).collect(),
+        }
+    }
+
+    pub fn addr_values(&self) -> Vec<FieldElement> {
+        self.addr
+            .iter()
+            .map(|&a| FieldElement::from(a as u64))
+            .collect()
+    }
+
+    /// Leaf vectors `[Init, WS, RS, Audit]` of length `2^log_leaves` for the
+    /// table `t` and the looked‑up values `e`.
+    pub fn leaves(
+        &self,
+        t: &[FieldElement],
+        e: &[FieldElement],
+        gamma: FieldElement,
+        tau: FieldElement,
+        log_leaves: usize,
+    ) -> [Vec<FieldElement>; 4] {
+        let h = |a: usize, v: FieldElement, ts: FieldElement| {
+            FieldElement::from(a as u64) * gamma * gamma + v * gamma + ts - tau
+        };
+        let one = FieldElement::one();
+        let mut out = [
+            (0..t.len())
+                .map(|a| h(a, t[a], FieldElement::zero()))
+                .collect::<Vec<_>>(),
+            (0..e.len())
+                .map(|k| h(self.addr[k], e[k], self.read_ts[k] + one))
+                .collect(),
+            (0..e.len())
+                .map(|k| h(self.addr[k], e[k], self.read_ts[k]))
+                .collect(),
+            (0..t.len()).map(|a| h(a, t[a], self.final_ts[a])).collect(),
+        ];
+        for leaves in &mut out {
+            leaves.resize(1 << log_leaves, one);
+        }
+        out
+    }
+}
+
+/// `log_leaves` levels of `Mul` gates down to a single product, replicated
+/// `2^LOG_COPIES` times.
+pub(crate) fn product_circuit(log_leaves: usize) -> DataParallel {
+    let mut layers = vec![Layer::dummy(log_leaves)];
+    for b in (0..log_leaves).rev() {
+        layers.push(Layer::binary_tree(GateType::Mul, b));
+    }
+    DataParallel::new(
+        Circuit {
+            layers,
+            assertions: Vec::new(),
+        },
+        LOG_COPIES,
+    )
+}
+
+pub(crate) fn product_bit_lengths(log_leaves: usize) -> Vec<usize> {
+    (0..=log_leaves).rev().map(|b| b + LOG_COPIES).collect()
+}
+
+/// Predicates of layer `i` of [`product_circuit`], from the template's closed form.
+pub(crate) fn product_wiring(log_leaves: usize, i: usize, ch: &Challenges) -> Wiring {
+    let b = log_leaves - i;
+    let local = ch.local(b, b + 1);
+    let tree = BinaryTree(GateType::Mul);
+    tree.evaluate(&local.r0, &local.ru, &local.rv) * local.alpha
+        + tree.evaluate(&local.r1, &local.ru, &local.rv) * local.beta
+}
+
+/// MLE over `point.len()` variables of a table of `2^s` values extended with
+/// ones, given the MLE `f` of the table at `point[..s]`.
+pub(crate) fn padded(point: &[FieldElement], s: usize, f: FieldElement) -> FieldElement {
+    let one = FieldElement::one();
+    let chi: FieldElement = point[s..].iter().map(|&x| one - x).product();
+    chi * f + one - chi
+}
+
+/// MLE of `a ↦ a` over `point.len()` variables.
+pub(crate) fn identity(point: &[FieldElement]) -> FieldElement {
+    point
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| FieldElement::from(1u64 << i) * x)
+        .sum()
+}
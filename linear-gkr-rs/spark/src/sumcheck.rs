@@ -0,0 +1,60 @@
+//! Sum‑check for `Σ_k Π_i Tᵢ(k)` over multilinear tables, binding the lowest
+//! variable first. Each round sends the round polynomial's values at
+//! `0, 1, …, d` for `d` the number of tables.
+
+use ark_ff::Zero;
+use field::FieldElement;
+use polynomial::interpolate;
+use polynomial::mle::fold;
+use prover::Transcript;
+
+/// Returns the round messages and the point; each table is left folded to
+/// its value at that point.
+pub(crate) fn prove(
+    tables: &mut [Vec<FieldElement>],
+    transcript: &mut impl Transcript,
+) -> (Vec<Vec<FieldElement>>, Vec<FieldElement>) {
+    let d = tables.len();
+    let (mut rounds, mut point) = (Vec::new(), Vec::new());
+    while tables[0].len() > 1 {
+        let mut evals = vec![FieldElement::zero(); d + 1];
+        for j in 0..tables[0].len() / 2 {
+            for (x, e) in evals.iter_mut().enumerate() {
+                let x = FieldElement::from(x as u64);
+                *e += tables
+                    .iter()
+                    .map(|t| t[2 * j] + (t[2 * j + 1] - t[2 * j]) * x)
+                    .product::<FieldElement>();
+            }
+        }
+        transcript.absorb(&evals);
+        let r = transcript.challenge();
+        for t in tables.iter_mut() {
+            fold(t, r);
+        }
+        rounds.push(evals);
+        point.push(r);
+    }
+    (rounds, point)
+}
+
+/// Check `rounds` against `claim`, returning the point and the claim left on
+/// `Π_i Tᵢ(point)`.
+pub(crate) fn verify(
+    rounds: &[Vec<FieldElement>],
+    degree: usize,
+    mut claim: FieldElement,
+    transcript: &mut impl Transcript,
+) -> Option<(Vec<FieldElement>, FieldElement)> {
+    let mut point = Vec::with_capacity(rounds.len());
+    for evals in rounds {
+        if evals.len() != degree + 1 || evals[0] + evals[1] != claim {
+            return None;
+        }
+        transcript.absorb(evals);
+        let r = transcript.challenge();
+        claim = interpolate(evals, r);
+        point.push(r);
+    }
+    Some((point, claim))
+}
@@ -0,0 +1,136 @@
+//! Preprocessing‑mode proofs: honest proofs verify against the key alone, and
+//! tampering with any part of a proof, its inputs or its key is caught.
+
+use ark_ff::{Field, One};
+use circuit::{load_from_path, Builder, Circuit, Wire};
+use field::FieldElement;
+use prover::{Keccak256Transcript, Prover};
+use spark::{preprocess, prove, verify, SparkProof, VerifierKey};
+
+fn field(values: &[u64]) -> Vec<FieldElement> {
+    values.iter().copied().map(FieldElement::from).collect()
+}
+
+/// `(x0 + x1)·x2 − x3`, `x0·x1·x2` and, if `assert`, `x0 − x0 = 0` asserted.
+fn circuit(assert: bool) -> Circuit {
+    shaped(Builder::sub, assert)
+}
+
+/// [`circuit`] with `op` in place of the subtraction.
+fn shaped(op: fn(&mut Builder, Wire, Wire) -> Wire, assert: bool) -> Circuit {
+    let mut b = Builder::new();
+    let x = b.inputs(4);
+    let s = b.add(x[0], x[1]);
+    let p = b.mul(s, x[2]);
+    let d = op(&mut b, p, x[3]);
+    let q = b.mul(x[0], x[1]);
+    let r = b.mul(q, x[2]);
+    b.output(d);
+    b.output(r);
+    if assert {
+        let zero = b.sub(x[0], x[0]);
+        b.assert_zero(zero);
+    }
+    b.build()
+}
+
+fn spark_prove(c: &Circuit, inputs: &[FieldElement]) -> (SparkProof, VerifierKey) {
+    let (pk, vk) = preprocess(c);
+    let prover = Prover::evaluate_with_inputs(c, inputs);
+    let proof = prove(&pk, c, &prover, &mut Keccak256Transcript::default());
+    (proof, vk)
+}
+
+fn accepts(vk: &VerifierKey, inputs: &[FieldElement], proof: &SparkProof) -> bool {
+    verify(vk, inputs, proof, &mut Keccak256Transcript::default())
+}
+
+#[test]
+fn honest_proofs_verify() {
+    let inputs = field(&[2, 3, 5, 7]);
+    for c in [circuit(false), circuit(true)] {
+        let (proof, vk) = spark_prove(&c, &inputs);
+        assert_eq!(proof.wiring.len(), c.layers.len() - 1);
+        assert!(accepts(&vk, &inputs, &proof));
+    }
+    let c = load_from_path("../examples/test_circuit.txt").unwrap();
+    let inputs = c.input_values();
+    let (proof, vk) = spark_prove(&c, &inputs);
+    assert!(accepts(&vk, &inputs, &proof));
+}
+
+#[test]
+fn tampered_proofs_are_rejected() {
+    let c = circuit(true);
+    let inputs = field(&[2, 3, 5, 7]);
+    let one = FieldElement::one();
+    type Tamper = fn(&mut SparkProof, FieldElement);
+    let tampers: [(&str, Tamper); 10] = [
+        ("output", |p, one| p.gkr.outputs[0] += one),
+        ("v_u", |p, one| p.gkr.layers[0].v_u += one),
+        ("v_v", |p, one| p.gkr.layers[1].v_v += one),
+        ("wiring round", |p, one| p.wiring[0].rounds[0][1] += one),
+        ("val", |p, one| p.wiring[1].val += one),
+        ("lookup", |p, one| p.wiring[0].lookups_at[1] += one),
+        ("memory product", |p, one| {
+            p.wiring[0].products.outputs[2] += one
+        }),
+        // still init·writes = reads·final, so only the product proof objects
+        ("balanced memory products", |p, _| {
+            let out = &mut p.wiring[0].products.outputs;
+            out[0].double_in_place();
+            out[2].double_in_place();
+        }),
+        ("memory leaf", |p, one| p.wiring[1].leaves[0][2][2] += one),
+        ("lookup commitment", |p, _| p.wiring[0].lookups.swap(1, 2)),
+    ];
+    for (name, tamper) in tampers {
+        let (mut proof, vk) = spark_prove(&c, &inputs);
+        tamper(&mut proof, one);
+        assert!(!accepts(&vk, &inputs, &proof), "{name} accepted");
+    }
+
+    // a whole layer's wiring evidence taken from another layer
+    let (mut proof, vk) = spark_prove(&c, &inputs);
+    proof.wiring.swap(0, 1);
+    assert!(!accepts(&vk, &inputs, &proof));
+    proof.wiring.pop();
+    assert!(!accepts(&vk, &inputs, &proof));
+}
+
+#[test]
+fn proofs_are_bound_to_their_inputs() {
+    let c = circuit(false);
+    let inputs = field(&[2, 3, 5, 7]);
+    let (proof, vk) = spark_prove(&c, &inputs);
+    assert!(!accepts(&vk, &field(&[2, 3, 5, 8]), &proof));
+    assert!(!accepts(&vk, &field(&[2, 3, 5, 7, 1]), &proof));
+}
+
+#[test]
+fn mismatched_verifier_keys_are_rejected() {
+    let inputs = field(&[2, 3, 5, 7]);
+    let (proof, vk) = spark_prove(&circuit(false), &inputs);
+    assert!(accepts(&vk, &inputs, &proof));
+
+    // a key for another circuit of the same shape
+    let (_, other) = preprocess(&shaped(Builder::add, false));
+    assert_eq!(other.bit_lengths, vk.bit_lengths);
+    assert!(!accepts(&other, &inputs, &proof));
+
+    // the right commitments with another circuit's digest
+    let mut key = vk.clone();
+    key.digest = other.digest;
+    assert!(!accepts(&key, &inputs, &proof));
+
+    // the top layer's wiring commitments, where the circuits differ, swapped
+    // for the other circuit's
+    let mut key = vk.clone();
+    let top = key.layers.len() - 1;
+    key.layers[top] = other.layers[top].clone();
+    assert!(!accepts(&key, &inputs, &proof));
+
+    let mut key = vk;
+    key.assertions = vec![1];
+    assert!(!accepts(&key, &inputs, &proof));
+}
@@ -0,0 +1,159 @@
+//! Verifying many proofs for one circuit at once.
+//!
+//! Each proof's sum‑checks are replayed on its own transcript with the final
+//! equations deferred. The deferred equations of every layer, and the input
+//! claims, are then checked as one random linear combination over all proofs,
+//! folded in a single pass over the layer's terms. The proofs' points differ,
+//! so this saves no field work over checking them one by one: every proof
+//! still costs its own `eq` tables and a product per term. What the batch
+//! saves is decoding the terms once per layer rather than once per proof, and
+//! one comparison per layer. Only when a combination fails are the proofs
+//! checked one by one to name the culprit. Proofs that should share their
+//! challenges are better made as one data‑parallel proof
+//! ([`Prover::prove_batch`](prover::Prover::prove_batch)).
+
+use ark_ff::{One, Zero};
+use circuit::Layer;
+use field::FieldElement;
+use polynomial::mle;
+use prover::{Proof, Transcript};
+
+use crate::{Betas, InputClaim, Verifier, VerifierKey, WiringClaim};
+
+/// Why a batch was rejected.
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum BatchError {
+    #[error("{inputs} assignments for {proofs} proofs")]
+    Length { inputs: usize, proofs: usize },
+    #[error("assignment {index} has {len} values for {slots} input slots")]
+    InputLength {
+        index: usize,
+        len: usize,
+        slots: usize,
+    },
+    #[error("proof {0} is invalid")]
+    Invalid(usize),
+}
+
+impl Verifier {
+    /// Check `proofs[k]` for `vk`'s circuit on the layer‑0 assignment
+    /// `inputs[k]`, each replayed on a clone of `transcript` as the prover's
+    /// was before the proof; the combination weights are drawn from one more
+    /// clone. On failure, name the first invalid proof, or the first
+    /// assignment too long for the input layer.
+    pub fn verify_batch<T: Transcript + Clone>(
+        vk: &VerifierKey,
+        inputs: &[Vec<FieldElement>],
+        proofs: &[Proof],
+        transcript: &T,
+    ) -> Result<(), BatchError> {
+        if inputs.len() != proofs.len() {
+            return Err(BatchError::Length {
+                inputs: inputs.len(),
+                proofs: proofs.len(),
+            });
+        }
+        let c = vk.circuit;
+        let slots = c.layers[0].len();
+        if let Some(index) = inputs.iter().position(|x| x.len() > slots) {
+            return Err(BatchError::InputLength {
+                index,
+                len: inputs[index].len(),
+                slots,
+            });
+        }
+        let bit_lengths: Vec<_> = c.layers.iter().map(|l| l.bit_length).collect();
+        let mut claims = Vec::with_capacity(proofs.len());
+        let layer0: Vec<_> = inputs.iter().map(|x| c.pad_inputs(x)).collect();
+        for (k, (proof, x)) in proofs.iter().zip(&layer0).enumerate() {
+            let mut transcript = transcript.clone();
+            transcript.bind(vk.digest, x);
+            match Self::reduce_deferred(&bit_lengths, &c.assertions, proof, &mut transcript) {
+                Some(claim) => claims.push(claim),
+                None => return Err(BatchError::Invalid(k)),
+            }
+        }
+
+        // combination weights, bound to every deferred equation
+        let mut transcript = transcript.clone();
+        for (input, wiring) in &claims {
+            for w in wiring {
+                transcript.absorb(&[w.value, w.v_u, w.v_v]);
+            }
+            transcript.absorb(&[input.claim]);
+        }
+        let rho = transcript.challenge();
+        let weights: Vec<_> = std::iter::successors(Some(FieldElement::one()), |w| Some(*w * rho))
+            .take(proofs.len())
+            .collect();
+
+        // claims[k].1 is ordered top‑down, one entry per layer
+        let top = c.layers.len() - 1;
+        for (j, i) in (1..=top).rev().enumerate() {
+            let layer = &c.layers[i];
+            let deferred: Vec<_> = claims.iter().map(|(_, w)| &w[j]).collect();
+            let lhs: FieldElement = deferred
+                .iter()
+                .zip(&weights)
+                .map(|(w, &rho)| rho * w.value)
+                .sum();
+            let rhs = if layer.predicate.is_some() {
+                deferred
+                    .iter()
+                    .zip(&weights)
+                    .map(|(w, &rho)| rho * Self::wiring(layer, &w.challenges).eval(w.v_u, w.v_v))
+                    .sum()
+            } else {
+                folded_sum(layer, &deferred, &weights)
+            };
+            if lhs != rhs {
+                return Err(Self::first_failing(&deferred, |w| {
+                    w.value == Self::wiring(layer, &w.challenges).eval(w.v_u, w.v_v)
+                }));
+            }
+        }
+
+        let eval = |input: &InputClaim, x: &[FieldElement]| {
+            input.alpha * mle::evaluate(x, &input.r0) + input.beta * mle::evaluate(x, &input.r1)
+        };
+        let mut acc = FieldElement::zero();
+        for (((input, _), x), &rho) in claims.iter().zip(&layer0).zip(&weights) {
+            acc += rho * (input.claim - eval(input, x));
+        }
+        if !acc.is_zero() {
+            let inputs: Vec<_> = claims.iter().map(|(input, _)| input).zip(&layer0).collect();
+            return Err(Self::first_failing(&inputs, |(input, x)| input.check(x)));
+        }
+        Ok(())
+    }
+
+    /// The first item failing `ok`, once a combined check has failed.
+    fn first_failing<T>(items: &[T], mut ok: impl FnMut(&T) -> bool) -> BatchError {
+        BatchError::Invalid(
+            items
+                .iter()
+                .position(|item| !ok(item))
+                .expect("a failed combination has a failing member"),
+        )
+    }
+}
+
+/// `Σₖ ρₖ·` right‑hand side of proof `k`'s deferred equation, in one pass
+/// over the layer's terms.
+fn folded_sum(layer: &Layer, deferred: &[&WiringClaim], weights: &[FieldElement]) -> FieldElement {
+    let betas: Vec<_> = deferred.iter().map(|w| Betas::new(&w.challenges)).collect();
+    let mut sum = FieldElement::zero();
+    for g in 0..layer.len() {
+        layer.terms(g, |t| {
+            for ((b, w), &rho) in betas.iter().zip(deferred).zip(weights) {
+                let (vu, vv) = (w.v_u, w.v_v);
+                sum += rho
+                    * b.g[g]
+                    * b.u[t.u]
+                    * b.v[t.v]
+                    * (t.cst + t.lin_u * vu + t.lin_v * vv + t.mul * vu * vv);
+            }
+        });
+    }
+    sum
+}
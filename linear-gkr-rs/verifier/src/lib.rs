@@ -1,22 +1,27 @@
 //! Sum‑check verifier – Rust port of `verifier.cpp` (slow track).
 
+mod batch;
+mod recursive;
+mod solidity;
+mod statement;
+
 use ark_ff::{One, Zero};
-use circuit::{Circuit, GateType, Layer};
-use field::{FieldElement, FieldExt};
-use prover::Prover;
-use rand::thread_rng;
-
-/// Generate `n` random field elements
-fn rand_vec(n: usize) -> Vec<FieldElement> {
-    (0..n).map(|_| FieldElement::random()).collect()
-}
+use circuit::{Circuit, DataParallel, Layer, Layered, Wiring};
+use field::FieldElement;
+use polynomial::interpolate;
+use polynomial::mle;
+use prover::{
+    Challenges, ClaimReduction, Keccak256Transcript, LayerProof, Proof, Prover, Transcript,
+};
+
+pub use batch::BatchError;
+pub use recursive::VerifierCircuit;
+pub use solidity::{calldata, encode_proof, solidity_verifier};
 
 /// Helper: β‑coefficients for a layer (Algorithm 1 in GKR papers).
 struct Betas {
-    /// βᵍ( r₀ )  (alpha weight)
-    g_r0: Vec<FieldElement>,
-    /// βᵍ( r₁ )  (beta weight)
-    g_r1: Vec<FieldElement>,
+    /// α·βᵍ(r₀) + β·βᵍ(r₁)
+    g: Vec<FieldElement>,
     /// βᵤ( rᵤ )
     u: Vec<FieldElement>,
     /// βᵥ( rᵥ )
@@ -24,170 +29,335 @@ struct Betas {
 }
 
 impl Betas {
-    fn new(
-        layer: &Layer,
-        prev: &Layer,
-        alpha: FieldElement,
-        beta: FieldElement,
-        r0: &[FieldElement],
-        r1: &[FieldElement],
-        ru: &[FieldElement],
-        rv: &[FieldElement],
-    ) -> Self {
-        let mut g_r0 = vec![FieldElement::zero(); 1 << layer.bit_length];
-        let mut g_r1 = vec![FieldElement::zero(); 1 << layer.bit_length];
-        g_r0[0] = alpha;
-        g_r1[0] = beta;
-
-        // Horner on bits of g
-        for (i, (&r0_i, &r1_i)) in r0.iter().zip(r1).enumerate() {
-            for j in (0..(1 << i)).rev() {
-                let base0 = g_r0[j];
-                let base1 = g_r1[j];
-                g_r0[j] = base0 * (FieldElement::one() - r0_i);
-                g_r0[j | (1 << i)] = base0 * r0_i;
-                g_r1[j] = base1 * (FieldElement::one() - r1_i);
-                g_r1[j | (1 << i)] = base1 * r1_i;
-            }
+    fn new(ch: &Challenges) -> Self {
+        let mut g = mle::eq_table(&ch.r0, ch.alpha);
+        for (x, y) in g.iter_mut().zip(mle::eq_table(&ch.r1, ch.beta)) {
+            *x += y;
         }
-
-        let mut u = vec![FieldElement::zero(); 1 << prev.bit_length];
-        let mut v = vec![FieldElement::zero(); 1 << prev.bit_length];
-        u[0] = FieldElement::one();
-        v[0] = FieldElement::one();
-        for (i, (&ru_i, &rv_i)) in ru.iter().zip(rv).enumerate() {
-            for j in (0..(1 << i)).rev() {
-                let bu = u[j];
-                let bv = v[j];
-                u[j] = bu * (FieldElement::one() - ru_i);
-                u[j | (1 << i)] = bu * ru_i;
-                v[j] = bv * (FieldElement::one() - rv_i);
-                v[j | (1 << i)] = bv * rv_i;
-            }
+        Self {
+            g,
+            u: mle::eq_table(&ch.ru, FieldElement::one()),
+            v: mle::eq_table(&ch.rv, FieldElement::one()),
         }
+    }
+}
 
-        Self { g_r0, g_r1, u, v }
+/// What is left of a proof once every layer is checked:
+/// `α·Ṽ₀(r₀) + β·Ṽ₀(r₁) = claim` on the input layer.
+pub struct InputClaim {
+    pub alpha: FieldElement,
+    pub beta: FieldElement,
+    pub r0: Vec<FieldElement>,
+    pub r1: Vec<FieldElement>,
+    pub claim: FieldElement,
+}
+
+impl InputClaim {
+    /// Check the claim against the whole (padded) input layer.
+    pub fn check(&self, input: &[FieldElement]) -> bool {
+        self.check_with(|r| mle::evaluate(input, r))
     }
 
-    fn add_value(&self, g: usize, u: usize, v: usize) -> FieldElement {
-        (self.g_r0[g] + self.g_r1[g]) * self.u[u] * self.v[v]
+    /// Check the claim with `eval(r)` standing in for `Ṽ₀(r)`.
+    pub fn check_with(&self, mut eval: impl FnMut(&[FieldElement]) -> FieldElement) -> bool {
+        self.claim == self.alpha * eval(&self.r0) + self.beta * eval(&self.r1)
     }
+}
 
-    fn mul_value(&self, g: usize, u: usize, v: usize) -> FieldElement {
-        // only for MUL gates
-        (self.g_r0[g] + self.g_r1[g]) * self.u[u] * self.v[v]
+/// A layer's final sum‑check equation left to the caller: the wiring
+/// predicates at `challenges` must satisfy `Wiring::eval(v_u, v_v) = value`.
+pub struct WiringClaim {
+    pub layer: usize,
+    pub challenges: Challenges,
+    pub v_u: FieldElement,
+    pub v_v: FieldElement,
+    pub value: FieldElement,
+}
+
+/// A circuit prepared for verification. Proofs are bound to the circuit's
+/// [`Layered::digest`], which takes a pass over every wiring term; the key
+/// computes it once, so that checking a proof against it does not.
+pub struct VerifierKey<'a, C = Circuit> {
+    pub circuit: &'a C,
+    pub digest: FieldElement,
+}
+
+impl<'a, C: Layered> VerifierKey<'a, C> {
+    pub fn new(circuit: &'a C) -> Self {
+        Self {
+            circuit,
+            digest: circuit.digest(),
+        }
     }
 }
 
 pub struct Verifier;
 
 impl Verifier {
+    /// Prove with `prover` and check the proof in one go.
     pub fn verify(c: &Circuit, prover: &Prover) -> bool {
-        // Initial alpha=1, beta=0 as in C++ reference
-        let mut alpha = FieldElement::one();
-        let mut beta = FieldElement::zero();
-
-        // r0/r1 for output layer
-        let mut rng = thread_rng();
-        let mut r0 = rand_vec(c.layers.last().unwrap().bit_length);
-        let mut r1 = rand_vec(c.layers.last().unwrap().bit_length);
+        Self::verify_with_inputs(c, prover, &c.input_values())
+    }
 
-        // The verifier holds the prover's final output vector
-        let mut claim =
-            alpha * Self::v_res(&r0, prover.outputs()) + beta * Self::v_res(&r1, prover.outputs());
+    /// [`Verifier::verify`] against an explicit layer‑0 assignment, as used with
+    /// [`Prover::evaluate_with_inputs`].
+    pub fn verify_with_inputs(c: &Circuit, prover: &Prover, inputs: &[FieldElement]) -> bool {
+        let proof = prover.prove(c, &mut Keccak256Transcript::default());
+        let vk = VerifierKey::new(c);
+        Self::verify_proof(&vk, inputs, &proof, &mut Keccak256Transcript::default())
+    }
 
-        // work from top layer down to 1
-        for depth in (1..c.layers.len()).rev() {
-            let layer = &c.layers[depth];
-            let prev = &c.layers[depth - 1];
+    /// Check `proof` for `vk`'s circuit on the layer‑0 assignment `inputs`,
+    /// which is rejected if it has more values than the input layer has slots.
+    pub fn verify_proof(
+        vk: &VerifierKey,
+        inputs: &[FieldElement],
+        proof: &Proof,
+        transcript: &mut impl Transcript,
+    ) -> bool {
+        let c = vk.circuit;
+        if inputs.len() > c.layers[0].len() {
+            return false;
+        }
+        let layer0 = c.pad_inputs(inputs);
+        transcript.bind(vk.digest, &layer0);
+        Self::reduce(c, proof, transcript).is_some_and(|claim| claim.check(&layer0))
+    }
 
-            // fresh ru, rv for this round
-            let ru = rand_vec(prev.bit_length);
-            let rv = rand_vec(prev.bit_length);
+    /// Check `proof` for the data‑parallel circuit of `vk` on `instances`,
+    /// which are rejected if there are more of them than copies or one has
+    /// more values than the template's input layer has slots.
+    ///
+    /// Wiring predicates are evaluated on the template only, so apart from
+    /// reading the inputs the work does not grow with the number of copies.
+    pub fn verify_data_parallel(
+        vk: &VerifierKey<DataParallel>,
+        instances: &[Vec<FieldElement>],
+        proof: &Proof,
+        transcript: &mut impl Transcript,
+    ) -> bool {
+        let dp = vk.circuit;
+        let slots = dp.template.layers[0].len();
+        if instances.len() > dp.copies() || instances.iter().any(|x| x.len() > slots) {
+            return false;
+        }
+        let bit_lengths: Vec<_> = (0..dp.depth()).map(|i| dp.bit_length(i)).collect();
+        let layers = &dp.template.layers;
+        let assertions = dp.assertions();
+        let layer0 = dp.pad_inputs(instances);
+        transcript.bind(vk.digest, &layer0);
+        Self::reduce_with(&bit_lengths, &assertions, proof, transcript, |i, ch| {
+            let local = ch.local(layers[i].bit_length, layers[i - 1].bit_length);
+            Self::wiring(&layers[i], &local)
+        })
+        .is_some_and(|claim| claim.check(&layer0))
+    }
 
-            // compute betas
-            let betas = Betas::new(layer, prev, alpha, beta, &r0, &r1, &ru, &rv);
+    /// Check a [`Prover::prove_batch`] proof of the template of `vk`'s
+    /// circuit `dp` on each of `witnesses`; per‑witness outputs are
+    /// `dp.copy_outputs(&output_layer)` with the output layer from
+    /// [`Proof::output_layer`]. More witnesses than copies are rejected.
+    pub fn verify_batch_proof(
+        vk: &VerifierKey<DataParallel>,
+        witnesses: &[Vec<FieldElement>],
+        proof: &Proof,
+        transcript: &mut impl Transcript,
+    ) -> bool {
+        Self::verify_data_parallel(vk, witnesses, proof, transcript)
+    }
 
-            // direct (slow) evaluation to get mult/add values
-            let add_value = Self::add_sum(layer, &betas);
-            let mul_value = Self::mul_sum(layer, &betas);
+    /// Check every layer of `proof` for `c`, returning the claim left on the
+    /// input layer.
+    ///
+    /// Unlike [`Verifier::verify_proof`], this does not bind the transcript to
+    /// the circuit and its inputs: callers do that first (see
+    /// [`Transcript::bind`]), unless the transcript already determines both.
+    pub fn reduce(
+        c: &Circuit,
+        proof: &Proof,
+        transcript: &mut impl Transcript,
+    ) -> Option<InputClaim> {
+        let bit_lengths: Vec<_> = c.layers.iter().map(|l| l.bit_length).collect();
+        Self::reduce_with(&bit_lengths, &c.assertions, proof, transcript, |i, ch| {
+            Self::wiring(&c.layers[i], ch)
+        })
+    }
 
-            // v_u and v_v from prover's taped evaluation
-            let v_u = Self::v_res(&ru, &prover.values[depth - 1]);
-            let v_v = Self::v_res(&rv, &prover.values[depth - 1]);
+    /// [`Verifier::reduce`] for a circuit known only by its layer sizes and
+    /// asserted outputs, with `wiring(i, ·)` evaluating layer `i`'s predicates.
+    pub fn reduce_with(
+        bit_lengths: &[usize],
+        assertions: &[usize],
+        proof: &Proof,
+        transcript: &mut impl Transcript,
+        mut wiring: impl FnMut(usize, &Challenges) -> Wiring,
+    ) -> Option<InputClaim> {
+        let outputs = Self::output_layer(bit_lengths, assertions, proof)?;
+        Self::reduce_layers(
+            bit_lengths,
+            proof,
+            transcript,
+            |r0| Some(mle::evaluate(&outputs, r0)),
+            |i, ch, lp, claim| claim == wiring(i, ch).eval(lp.v_u, lp.v_v),
+        )
+    }
 
-            // verify the "final" equation
-            if claim != add_value * (v_u + v_v) + mul_value * v_u * v_v {
-                eprintln!("final check failed at layer {depth}");
-                return false;
-            }
+    /// [`Verifier::reduce_with`] without evaluating any predicate: every
+    /// layer's final equation is returned for the caller to check, top‑down.
+    pub fn reduce_deferred(
+        bit_lengths: &[usize],
+        assertions: &[usize],
+        proof: &Proof,
+        transcript: &mut impl Transcript,
+    ) -> Option<(InputClaim, Vec<WiringClaim>)> {
+        let outputs = Self::output_layer(bit_lengths, assertions, proof)?;
+        let mut deferred = Vec::with_capacity(proof.layers.len());
+        let claim = Self::reduce_layers(
+            bit_lengths,
+            proof,
+            transcript,
+            |r0| Some(mle::evaluate(&outputs, r0)),
+            |i, ch, lp, value| {
+                deferred.push(WiringClaim {
+                    layer: i,
+                    challenges: ch.clone(),
+                    v_u: lp.v_u,
+                    v_v: lp.v_v,
+                    value,
+                });
+                true
+            },
+        )?;
+        Some((claim, deferred))
+    }
 
-            // update claim for next iteration
-            alpha = FieldElement::random_with(&mut rng);
-            beta = FieldElement::random_with(&mut rng);
-            claim = alpha * v_u + beta * v_v;
+    /// The output layer of `proof` (see [`Proof::output_layer`]), with zeros
+    /// at `assertions`, so that for a circuit of assertions only the top claim
+    /// is zero.
+    fn output_layer(
+        bit_lengths: &[usize],
+        assertions: &[usize],
+        proof: &Proof,
+    ) -> Option<Vec<FieldElement>> {
+        let outputs = proof.output_layer(bit_lengths[bit_lengths.len() - 1], assertions);
+        if outputs.is_none() {
+            eprintln!("proof does not match the circuit shape");
+        }
+        outputs
+    }
 
-            // shift randomness down for next layer
-            r0 = ru;
-            r1 = rv;
+    /// Replay the transcript of `proof`, checking every sum‑check round;
+    /// `top_claim(r₀)` is the output layer's MLE at the first point and
+    /// `final_check(i, challenges, layer proof, claim)` decides layer `i`'s
+    /// final equation.
+    fn reduce_layers(
+        bit_lengths: &[usize],
+        proof: &Proof,
+        transcript: &mut impl Transcript,
+        top_claim: impl FnOnce(&[FieldElement]) -> Option<FieldElement>,
+        mut final_check: impl FnMut(usize, &Challenges, &LayerProof, FieldElement) -> bool,
+    ) -> Option<InputClaim> {
+        let top = bit_lengths.len() - 1;
+        if proof.layers.len() != top {
+            eprintln!("proof does not match the circuit shape");
+            return None;
         }
 
-        // base layer: verify claim equals V_input(alpha, beta)
-        let input_layer = &c.layers[0];
-        let mut input_vals = vec![FieldElement::zero(); 1 << input_layer.bit_length];
-        for (&id, gate) in &input_layer.gates {
-            if id >= input_vals.len() {
-                panic!(
-                    "Gate ID {} out of bounds for input_vals of length {}",
-                    id,
-                    input_vals.len()
-                );
+        transcript.absorb(&[&[proof.reduction.tag()][..], &proof.outputs].concat());
+        let r0 = transcript.challenges(bit_lengths[top]);
+        let mut ch = Challenges {
+            alpha: FieldElement::one(),
+            beta: FieldElement::zero(),
+            r1: r0.clone(),
+            r0,
+            ru: Vec::new(),
+            rv: Vec::new(),
+        };
+        let mut claim = top_claim(&ch.r0)?;
+
+        // work from top layer down to 1
+        for (depth, lp) in (1..=top).rev().zip(&proof.layers) {
+            let n = bit_lengths[depth - 1];
+            if lp.rounds.len() != 2 * n {
+                eprintln!("wrong number of sum‑check rounds at layer {depth}");
+                return None;
             }
-            match gate.ty {
-                GateType::Input => input_vals[id] = FieldElement::from(gate.u as u64),
-                GateType::Dummy => input_vals[id] = FieldElement::zero(),
-                _ => panic!("only INPUT / DUMMY allowed in layer‑0"),
+
+            let mut point = Vec::with_capacity(2 * n);
+            for (k, p) in lp.rounds.iter().enumerate() {
+                if p.eval(FieldElement::zero()) + p.eval(FieldElement::one()) != claim {
+                    eprintln!("sum‑check round {k} failed at layer {depth}");
+                    return None;
+                }
+                transcript.absorb(&[p.a, p.b, p.c]);
+                let r = transcript.challenge();
+                claim = p.eval(r);
+                point.push(r);
+                if k + 1 == n {
+                    transcript.absorb(&[lp.v_u]);
+                }
             }
-        }
-        let res0 = Self::v_res(&r0, &input_vals);
-        let res1 = Self::v_res(&r1, &input_vals);
+            transcript.absorb(&[lp.v_v]);
+            ch.rv = point.split_off(n);
+            ch.ru = point;
 
-        claim == alpha * res0 + beta * res1
-    }
+            // verify the "final" equation
+            if !final_check(depth, &ch, lp, claim) {
+                eprintln!("final check failed at layer {depth}");
+                return None;
+            }
 
-    /// Evaluate V_output(r) (multilinear extension) by Horner.
-    fn v_res(r: &[FieldElement], vector: &[FieldElement]) -> FieldElement {
-        let mut cur = vector.to_vec();
-        let mut len = cur.len();
-        let mut step = 0;
-        while len > 1 {
-            for j in 0..(len / 2) {
-                cur[j] = cur[2 * j] * (FieldElement::one() - r[step]) + cur[2 * j + 1] * r[step];
+            // update claim and randomness for next iteration
+            match proof.reduction {
+                ClaimReduction::Combine => {
+                    if !lp.line.is_empty() {
+                        eprintln!("unexpected line restriction at layer {depth}");
+                        return None;
+                    }
+                    ch.alpha = transcript.challenge();
+                    ch.beta = transcript.challenge();
+                    claim = ch.alpha * lp.v_u + ch.beta * lp.v_v;
+                    ch.r0 = std::mem::take(&mut ch.ru);
+                    ch.r1 = std::mem::take(&mut ch.rv);
+                }
+                ClaimReduction::Line => {
+                    if lp.line.len() != n.saturating_sub(1) {
+                        eprintln!("wrong line restriction length at layer {depth}");
+                        return None;
+                    }
+                    transcript.absorb(&lp.line);
+                    let r = transcript.challenge();
+                    let mut q = vec![lp.v_u, lp.v_v];
+                    q.extend_from_slice(&lp.line);
+                    claim = interpolate(&q, r);
+                    ch.r0 = mle::line(&ch.ru, &ch.rv, r);
+                    ch.r1 = ch.r0.clone();
+                    (ch.alpha, ch.beta) = (FieldElement::one(), FieldElement::zero());
+                }
             }
-            len /= 2;
-            step += 1;
         }
-        cur[0]
+
+        Some(InputClaim {
+            alpha: ch.alpha,
+            beta: ch.beta,
+            r0: ch.r0,
+            r1: ch.r1,
+            claim,
+        })
     }
 
-    /// ∑_{add gates} βg βu βv
-    fn add_sum(layer: &Layer, betas: &Betas) -> FieldElement {
-        let mut acc = FieldElement::zero();
-        for (&g, gate) in &layer.gates {
-            if gate.ty == GateType::Add {
-                acc += betas.add_value(g, gate.u, gate.v);
+    /// Layer predicates at `α·(r₀, r_u, r_v) + β·(r₁, r_u, r_v)`: two closed‑form
+    /// evaluations for structured layers, one pass over the gates otherwise.
+    fn wiring(layer: &Layer, ch: &Challenges) -> Wiring {
+        match &layer.predicate {
+            Some(p) => {
+                p.evaluate(&ch.r0, &ch.ru, &ch.rv) * ch.alpha
+                    + p.evaluate(&ch.r1, &ch.ru, &ch.rv) * ch.beta
             }
-        }
-        acc
-    }
-    /// ∑_{mul gates} βg βu βv
-    fn mul_sum(layer: &Layer, betas: &Betas) -> FieldElement {
-        let mut acc = FieldElement::zero();
-        for (&g, gate) in &layer.gates {
-            if gate.ty == GateType::Mul {
-                acc += betas.mul_value(g, gate.u, gate.v);
+            None => {
+                let betas = Betas::new(ch);
+                layer.wiring_sum(&betas.g, &betas.u, &betas.v)
             }
         }
-        acc
     }
 }
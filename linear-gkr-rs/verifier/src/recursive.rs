@@ -0,0 +1,301 @@
+//! The verifier as a circuit, so that a GKR proof can be checked inside
+//! another one.
+//!
+//! [`VerifierCircuit::new`] unrolls [`Verifier::verify_proof`] for one inner
+//! circuit into a [`Circuit`]: the inner inputs and the proof are its layer‑0
+//! values, the Fiat–Shamir transcript is a [`MimcTranscript`] replayed gate by
+//! gate, the wiring predicates and input MLEs are evaluated from `eq` tables,
+//! and every check of the verifier becomes an output, asserted to be zero,
+//! that is zero iff the check holds. Proving the verifier circuit thus proves
+//! that the inner proof is accepted.
+//!
+//! Inner proofs must use [`ClaimReduction::Combine`] and
+//! `MimcTranscript::default()`.
+//!
+//! The transcript dominates the size: every MiMC compression is 440 layers
+//! deep, and the inner inputs and proof wait in relays until they are
+//! absorbed. A 3‑layer inner circuit on 4 inputs gives 34,971 layers of at
+//! most 64 slots, about 2.2M slots in all.
+
+use ark_ff::Zero;
+use circuit::{Builder, Circuit, Layered, Wire};
+use field::FieldElement;
+use prover::{mimc_constants, ClaimReduction, MimcTranscript, Proof};
+
+/// An operand that is either a wire or known when the circuit is built.
+#[derive(Clone, Copy)]
+enum Value {
+    Wire(Wire),
+    Const(FieldElement),
+}
+
+/// In‑circuit [`MimcTranscript`].
+struct CircuitTranscript {
+    state: Wire,
+}
+
+impl CircuitTranscript {
+    fn new(b: &mut Builder) -> Self {
+        Self {
+            state: b.constant(MimcTranscript::default().state()),
+        }
+    }
+
+    /// `mimc_compress(s, x)`, with the constant operand folded into the first
+    /// and last additions.
+    fn compress(b: &mut Builder, s: Wire, x: Value) -> Wire {
+        let mut y = None;
+        for &c in mimc_constants() {
+            let t = match (y, x) {
+                (Some(y), _) | (None, Value::Wire(y)) => {
+                    let k = b.add_const(s, c);
+                    b.add(y, k)
+                }
+                (None, Value::Const(x)) => b.add_const(s, x + c),
+            };
+            let t2 = b.mul(t, t);
+            let t4 = b.mul(t2, t2);
+            y = Some(b.mul(t4, t));
+        }
+        let s2 = b.scale(s, FieldElement::from(2u64));
+        let e = b.add(y.expect("MiMC has rounds"), s2);
+        match x {
+            Value::Wire(x) => b.add(e, x),
+            Value::Const(x) => b.add_const(e, x),
+        }
+    }
+
+    fn absorb(&mut self, b: &mut Builder, values: &[Wire]) {
+        let values: Vec<_> = values.iter().map(|&w| Value::Wire(w)).collect();
+        self.absorb_values(b, &values);
+    }
+
+    fn absorb_values(&mut self, b: &mut Builder, values: &[Value]) {
+        let n = FieldElement::from(values.len() as u64 + 1);
+        self.state = Self::compress(b, self.state, Value::Const(n));
+        for &v in values {
+            self.state = Self::compress(b, self.state, v);
+        }
+    }
+
+    fn challenge(&mut self, b: &mut Builder) -> Wire {
+        self.state = Self::compress(b, self.state, Value::Const(FieldElement::zero()));
+        self.state
+    }
+}
+
+/// In‑circuit `mle::eq_table(r, scale)`, with `None` standing for a scale of 1.
+fn eq_table(b: &mut Builder, r: &[Wire], scale: Option<Wire>) -> Vec<Wire> {
+    let Some((&r0, rest)) = r.split_first() else {
+        let one = FieldElement::from(1u64);
+        return vec![scale.unwrap_or_else(|| b.constant(one))];
+    };
+    let (lo, hi) = match scale {
+        Some(s) => {
+            let hi = b.mul(s, r0);
+            (b.sub(s, hi), hi)
+        }
+        None => {
+            let neg = b.scale(r0, -FieldElement::from(1u64));
+            (b.add_const(neg, FieldElement::from(1u64)), r0)
+        }
+    };
+    let mut out = vec![lo, hi];
+    for &ri in rest {
+        let half = out.len();
+        for j in 0..half {
+            let hi = b.mul(out[j], ri);
+            out[j] = b.sub(out[j], hi);
+            out.push(hi);
+        }
+    }
+    out
+}
+
+/// `Σ table[i]·values[i]`.
+fn dot(b: &mut Builder, table: &[Wire], values: &[Wire]) -> Wire {
+    let products: Vec<_> = table
+        .iter()
+        .zip(values)
+        .map(|(&x, &y)| b.mul(x, y))
+        .collect();
+    b.sum(&products)
+}
+
+/// `Σ cᵢ·wᵢ` over `(wᵢ, cᵢ)`, skipping zero coefficients.
+fn combination(b: &mut Builder, terms: &[(Wire, FieldElement)]) -> Wire {
+    let one = FieldElement::from(1u64);
+    let scaled: Vec<_> = terms
+        .iter()
+        .filter(|(_, c)| !c.is_zero())
+        .map(|&(w, c)| if c == one { w } else { b.scale(w, c) })
+        .collect();
+    b.sum(&scaled)
+}
+
+pub struct VerifierCircuit {
+    pub circuit: Circuit,
+    bit_lengths: Vec<usize>,
+    assertions: Vec<usize>,
+}
+
+impl VerifierCircuit {
+    /// The verifier of proofs for `c`.
+    pub fn new(c: &Circuit) -> Self {
+        let bit_lengths: Vec<_> = c.layers.iter().map(|l| l.bit_length).collect();
+        let top = bit_lengths.len() - 1;
+        let mut b = Builder::new();
+
+        // layer 0: inner inputs, sent outputs, then each layer's messages
+        let inputs = b.inputs(1 << bit_lengths[0]);
+        let sent: Vec<_> = (0..1 << bit_lengths[top])
+            .filter(|g| c.assertions.binary_search(g).is_err())
+            .collect();
+        let outputs = b.inputs(sent.len());
+        let messages: Vec<_> = (1..=top)
+            .rev()
+            .map(|i| {
+                let rounds: Vec<_> = (0..2 * bit_lengths[i - 1])
+                    .map(|_| [b.input(), b.input(), b.input()])
+                    .collect();
+                (rounds, b.input(), b.input())
+            })
+            .collect();
+
+        let mut checks = Vec::new();
+        let mut t = CircuitTranscript::new(&mut b);
+        // `Transcript::bind`: the inner circuit's digest, then its inputs
+        let statement: Vec<_> = std::iter::once(Value::Const(c.digest()))
+            .chain(inputs.iter().map(|&w| Value::Wire(w)))
+            .collect();
+        t.absorb_values(&mut b, &statement);
+        let message: Vec<_> = std::iter::once(Value::Const(ClaimReduction::Combine.tag()))
+            .chain(outputs.iter().map(|&w| Value::Wire(w)))
+            .collect();
+        t.absorb_values(&mut b, &message);
+        let r0: Vec<_> = (0..bit_lengths[top]).map(|_| t.challenge(&mut b)).collect();
+        let table = eq_table(&mut b, &r0, None);
+        // asserted outputs are zero and drop out
+        let sent_table: Vec<_> = sent.iter().map(|&g| table[g]).collect();
+        let mut claim = dot(&mut b, &sent_table, &outputs);
+        let mut g_table = table;
+        let mut alpha_beta = None;
+        let mut points = (r0.clone(), r0);
+
+        for (i, (rounds, v_u, v_v)) in (1..=top).rev().zip(messages) {
+            let n = bit_lengths[i - 1];
+            let mut point = Vec::with_capacity(2 * n);
+            for (k, &[p_a, p_b, p_c]) in rounds.iter().enumerate() {
+                // p(0) + p(1) = a + b + 2c
+                let c2 = b.scale(p_c, FieldElement::from(2u64));
+                let sum = b.sum(&[p_a, p_b, c2]);
+                checks.push(b.sub(sum, claim));
+                t.absorb(&mut b, &[p_a, p_b, p_c]);
+                let r = t.challenge(&mut b);
+                // p(r) = (a·r + b)·r + c
+                let ar = b.mul(p_a, r);
+                let ar_b = b.add(ar, p_b);
+                let arb_r = b.mul(ar_b, r);
+                claim = b.add(arb_r, p_c);
+                point.push(r);
+                if k + 1 == n {
+                    t.absorb(&mut b, &[v_u]);
+                }
+            }
+            t.absorb(&mut b, &[v_v]);
+            let rv = point.split_off(n);
+            let ru = point;
+
+            // final equation: the layer's predicates at (β_g, r_u, r_v)
+            if let Some((alpha, beta)) = alpha_beta {
+                let t0 = eq_table(&mut b, &points.0, Some(alpha));
+                let t1 = eq_table(&mut b, &points.1, Some(beta));
+                g_table = t0.iter().zip(&t1).map(|(&x, &y)| b.add(x, y)).collect();
+            }
+            let u_table = eq_table(&mut b, &ru, None);
+            let v_table = eq_table(&mut b, &rv, None);
+            let mut kinds: [Vec<(Wire, FieldElement)>; 4] = Default::default();
+            let layer = &c.layers[i];
+            for (g, &w_g) in g_table.iter().enumerate().take(layer.len()) {
+                layer.terms(g, |term| {
+                    let gu = b.mul(w_g, u_table[term.u]);
+                    let w = b.mul(gu, v_table[term.v]);
+                    for (kind, coeff) in kinds
+                        .iter_mut()
+                        .zip([term.cst, term.lin_u, term.lin_v, term.mul])
+                    {
+                        kind.push((w, coeff));
+                    }
+                });
+            }
+            let [w_cst, w_u, w_v, w_mul] = kinds.map(|k| combination(&mut b, &k));
+            let lin_u = b.mul(w_u, v_u);
+            let lin_v = b.mul(w_v, v_v);
+            let uv = b.mul(v_u, v_v);
+            let mul = b.mul(w_mul, uv);
+            let value = b.sum(&[w_cst, lin_u, lin_v, mul]);
+            checks.push(b.sub(claim, value));
+
+            let alpha = t.challenge(&mut b);
+            let beta = t.challenge(&mut b);
+            let au = b.mul(alpha, v_u);
+            let bv = b.mul(beta, v_v);
+            claim = b.add(au, bv);
+            alpha_beta = Some((alpha, beta));
+            points = (ru, rv);
+        }
+
+        // input layer, against the inner inputs
+        let (alpha, beta) = alpha_beta.expect("circuit has a layer above the inputs");
+        let t0 = eq_table(&mut b, &points.0, Some(alpha));
+        let t1 = eq_table(&mut b, &points.1, Some(beta));
+        let e0 = dot(&mut b, &t0, &inputs);
+        let e1 = dot(&mut b, &t1, &inputs);
+        let expected = b.add(e0, e1);
+        checks.push(b.sub(claim, expected));
+
+        for w in checks {
+            b.assert_zero(w);
+        }
+        Self {
+            circuit: b.build(),
+            bit_lengths,
+            assertions: c.assertions.clone(),
+        }
+    }
+
+    /// Layer‑0 values for checking `proof` on the inner assignment `inputs`:
+    /// the padded inputs, the sent outputs, then every layer's rounds
+    /// `(a, b, c)` followed by `v_u`, `v_v`. `None` if the proof has the wrong
+    /// shape.
+    pub fn assignment(&self, inputs: &[FieldElement], proof: &Proof) -> Option<Vec<FieldElement>> {
+        let b = &self.bit_lengths;
+        let top = b.len() - 1;
+        let shape_ok = proof.reduction == ClaimReduction::Combine
+            && inputs.len() <= 1 << b[0]
+            && proof.output_layer(b[top], &self.assertions).is_some()
+            && proof.layers.len() == top
+            && (1..=top)
+                .rev()
+                .zip(&proof.layers)
+                .all(|(i, lp)| lp.rounds.len() == 2 * b[i - 1] && lp.line.is_empty());
+        if !shape_ok {
+            return None;
+        }
+        let mut values = inputs.to_vec();
+        values.resize(1 << b[0], FieldElement::zero());
+        values.extend_from_slice(&proof.outputs);
+        for lp in &proof.layers {
+            for p in &lp.rounds {
+                values.extend([p.a, p.b, p.c]);
+            }
+            values.extend([lp.v_u, lp.v_v]);
+        }
+        Some(values)
+    }
+
+    /// Whether the verifier circuit's outputs accept: every check is zero.
+    pub fn accepts(outputs: &[FieldElement]) -> bool {
+        outputs.iter().all(Zero::is_zero)
+    }
+}
@@ -0,0 +1,297 @@
+//! Solidity verifier contracts for one fixed circuit.
+//!
+//! [`solidity_verifier`] emits a contract with
+//! `verify(uint256[] inputs, uint256[] proof) returns (bool)` that replays
+//! [`Verifier::verify_proof`](crate::Verifier::verify_proof) for proofs made
+//! with `Keccak256Transcript::default()` and [`ClaimReduction::Combine`]:
+//! the transcript is the same `keccak256` chain over 32‑byte words, starting
+//! from the circuit's digest and the padded inputs, the sum‑check arithmetic
+//! uses `addmod`/`mulmod` over BN254 Fr, and every layer's wiring terms are
+//! written out as code. The proof is passed flat, as
+//! laid out by [`encode_proof`]; asserted outputs are not part of it.
+
+use std::fmt::Write;
+
+use ark_ff::{BigInteger, One, PrimeField, Zero};
+use circuit::{Circuit, Layered};
+use field::FieldElement;
+use prover::{ClaimReduction, Proof};
+use sha3::{Digest, Keccak256};
+
+/// The proof as the contract reads it: the sent outputs, then for every layer
+/// from the top the rounds' `(a, b, c)` followed by `v_u`, `v_v`.
+pub fn encode_proof(proof: &Proof) -> Vec<FieldElement> {
+    assert_eq!(
+        proof.reduction,
+        ClaimReduction::Combine,
+        "the contract replays random linear combinations only"
+    );
+    let mut out = proof.outputs.clone();
+    for lp in &proof.layers {
+        for p in &lp.rounds {
+            out.extend([p.a, p.b, p.c]);
+        }
+        out.extend([lp.v_u, lp.v_v]);
+    }
+    out
+}
+
+/// ABI‑encoded call of `verify(inputs, encode_proof(proof))`.
+pub fn calldata(inputs: &[FieldElement], proof: &Proof) -> Vec<u8> {
+    let word = |x: usize| FieldElement::from(x as u64).into_bigint().to_bytes_be();
+    let proof = encode_proof(proof);
+    let mut data = Keccak256::digest(b"verify(uint256[],uint256[])")[..4].to_vec();
+    data.extend(word(0x40));
+    data.extend(word(0x40 + 32 * (inputs.len() + 1)));
+    for array in [inputs, &proof[..]] {
+        data.extend(word(array.len()));
+        for x in array {
+            data.extend(x.into_bigint().to_bytes_be());
+        }
+    }
+    data
+}
+
+/// `acc += coeff · w` as Solidity, or nothing for a zero coefficient.
+fn accumulate(src: &mut String, acc: &str, coeff: FieldElement) {
+    if coeff.is_zero() {
+        return;
+    }
+    let term = if coeff.is_one() {
+        "w".to_string()
+    } else if (-coeff).is_one() {
+        "P - w".to_string()
+    } else {
+        format!("mulmod(w, {}, P)", coeff.into_bigint())
+    };
+    writeln!(src, "        {acc} = addmod({acc}, {term}, P);").unwrap();
+}
+
+/// Source of a verifier contract named `name` for proofs of `c`.
+pub fn solidity_verifier(c: &Circuit, name: &str) -> String {
+    let b: Vec<_> = c.layers.iter().map(|l| l.bit_length).collect();
+    let top = b.len() - 1;
+    let sent: Vec<_> = (0..1 << b[top])
+        .filter(|g| c.assertions.binary_search(g).is_err())
+        .collect();
+    let proof_len = sent.len() + (1..=top).map(|i| 6 * b[i - 1] + 2).sum::<usize>();
+
+    let mut src = String::new();
+    writeln!(
+        src,
+        r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// GKR verifier for one fixed circuit, generated by linear-gkr-rs.
+contract {name} {{
+    uint256 constant P = {p};
+    uint256 constant DIGEST = {digest};
+    uint256 constant N_INPUTS = {n_inputs};
+    uint256 constant N_OUTPUTS = {n_outputs};
+    uint256 constant PROOF_LEN = {proof_len};
+
+    struct State {{
+        bytes32 t;
+        uint256 claim;
+        uint256 alpha;
+        uint256 beta;
+        uint256[] r0;
+        uint256[] r1;
+        uint256 pos;
+    }}
+
+    function challenge(State memory s) internal pure returns (uint256) {{
+        s.t = keccak256(abi.encodePacked(s.t));
+        return uint256(s.t) % P;
+    }}
+
+    function eqTable(uint256[] memory r, uint256 scale) internal pure returns (uint256[] memory t) {{
+        t = new uint256[](1 << r.length);
+        t[0] = scale;
+        for (uint256 i = 0; i < r.length; i++) {{
+            uint256 half = 1 << i;
+            for (uint256 j = 0; j < half; j++) {{
+                uint256 hi = mulmod(t[j], r[i], P);
+                t[j | half] = hi;
+                t[j] = addmod(t[j], P - hi, P);
+            }}
+        }}
+    }}
+
+    function dot(uint256[] memory x, uint256[] memory y) internal pure returns (uint256 acc) {{
+        for (uint256 i = 0; i < y.length; i++) {{
+            acc = addmod(acc, mulmod(x[i], y[i], P), P);
+        }}
+    }}
+
+    function round(uint256[] calldata proof, State memory s) internal pure returns (bool, uint256) {{
+        uint256 a = proof[s.pos];
+        uint256 b = proof[s.pos + 1];
+        uint256 c = proof[s.pos + 2];
+        s.pos += 3;
+        if (addmod(addmod(a, b, P), addmod(c, c, P), P) != s.claim) return (false, 0);
+        s.t = keccak256(abi.encodePacked(s.t, a, b, c));
+        uint256 r = challenge(s);
+        s.claim = addmod(mulmod(addmod(mulmod(a, r, P), b, P), r, P), c, P);
+        return (true, r);
+    }}
+
+    function layer(uint256[] calldata proof, State memory s, uint256 i, uint256 n) internal pure returns (bool) {{
+        uint256 vu = proof[s.pos + 6 * n];
+        uint256 vv = proof[s.pos + 6 * n + 1];
+        uint256[] memory ru = new uint256[](n);
+        uint256[] memory rv = new uint256[](n);
+        bool ok;
+        for (uint256 k = 0; k < n; k++) {{
+            (ok, ru[k]) = round(proof, s);
+            if (!ok) return false;
+        }}
+        if (n > 0) s.t = keccak256(abi.encodePacked(s.t, vu));
+        for (uint256 k = 0; k < n; k++) {{
+            (ok, rv[k]) = round(proof, s);
+            if (!ok) return false;
+        }}
+        s.t = keccak256(abi.encodePacked(s.t, vv));
+        s.pos += 2;
+
+        uint256[] memory g = eqTable(s.r0, s.alpha);
+        uint256[] memory g1 = eqTable(s.r1, s.beta);
+        for (uint256 j = 0; j < g.length; j++) {{
+            g[j] = addmod(g[j], g1[j], P);
+        }}
+        if (s.claim != wiring(i, g, eqTable(ru, 1), eqTable(rv, 1), vu, vv)) return false;
+
+        s.alpha = challenge(s);
+        s.beta = challenge(s);
+        s.claim = addmod(mulmod(s.alpha, vu, P), mulmod(s.beta, vv, P), P);
+        s.r0 = ru;
+        s.r1 = rv;
+        return true;
+    }}
+
+    function combine(uint256 cc, uint256 cu, uint256 cv, uint256 cm, uint256 vu, uint256 vv) internal pure returns (uint256) {{
+        uint256 lin = addmod(mulmod(cu, vu, P), mulmod(cv, vv, P), P);
+        return addmod(addmod(cc, lin, P), mulmod(cm, mulmod(vu, vv, P), P), P);
+    }}
+"#,
+        p = FieldElement::MODULUS,
+        digest = c.digest().into_bigint(),
+        n_inputs = 1 << b[0],
+        n_outputs = sent.len(),
+    )
+    .unwrap();
+
+    // wiring predicates, one function per layer
+    writeln!(
+        src,
+        "    function wiring(uint256 i, uint256[] memory g, uint256[] memory u, uint256[] memory v, uint256 vu, uint256 vv) internal pure returns (uint256) {{"
+    )
+    .unwrap();
+    for i in 1..=top {
+        writeln!(
+            src,
+            "        if (i == {i}) return wiring{i}(g, u, v, vu, vv);"
+        )
+        .unwrap();
+    }
+    writeln!(src, "        revert();\n    }}").unwrap();
+    for (i, layer) in c.layers.iter().enumerate().skip(1) {
+        writeln!(
+            src,
+            "\n    function wiring{i}(uint256[] memory g, uint256[] memory u, uint256[] memory v, uint256 vu, uint256 vv) internal pure returns (uint256) {{"
+        )
+        .unwrap();
+        writeln!(src, "        uint256 w;\n        uint256 cc;\n        uint256 cu;\n        uint256 cv;\n        uint256 cm;").unwrap();
+        for g in 0..layer.len() {
+            layer.terms(g, |t| {
+                writeln!(
+                    src,
+                    "        w = mulmod(mulmod(g[{g}], u[{}], P), v[{}], P);",
+                    t.u, t.v
+                )
+                .unwrap();
+                for (acc, coeff) in [
+                    ("cc", t.cst),
+                    ("cu", t.lin_u),
+                    ("cv", t.lin_v),
+                    ("cm", t.mul),
+                ] {
+                    accumulate(&mut src, acc, coeff);
+                }
+            });
+        }
+        writeln!(
+            src,
+            "        return combine(cc, cu, cv, cm, vu, vv);\n    }}"
+        )
+        .unwrap();
+    }
+
+    // entry point, with the layers unrolled
+    writeln!(
+        src,
+        r#"
+    function verify(uint256[] calldata inputs, uint256[] calldata proof) external pure returns (bool) {{
+        if (inputs.length > N_INPUTS || proof.length != PROOF_LEN) return false;
+        for (uint256 k = 0; k < proof.length; k++) {{
+            if (proof[k] >= P) return false;
+        }}
+        uint256[] memory x = new uint256[](N_INPUTS);
+        for (uint256 k = 0; k < inputs.length; k++) {{
+            if (inputs[k] >= P) return false;
+            x[k] = inputs[k];
+        }}
+
+        State memory s;
+        s.t = keccak256("linear-gkr");
+        s.t = keccak256(abi.encodePacked(s.t, DIGEST, x));
+        uint256[] memory outs = new uint256[](N_OUTPUTS);
+        for (uint256 k = 0; k < N_OUTPUTS; k++) {{
+            outs[k] = proof[k];
+        }}
+        s.t = keccak256(abi.encodePacked(s.t, uint256({combine}), outs));
+        s.r0 = new uint256[]({b_top});
+        for (uint256 k = 0; k < {b_top}; k++) {{
+            s.r0[k] = challenge(s);
+        }}
+        s.r1 = s.r0;
+        s.alpha = 1;
+"#,
+        b_top = b[top],
+        combine = ClaimReduction::Combine.tag().into_bigint(),
+    )
+    .unwrap();
+    if c.assertions.is_empty() {
+        writeln!(src, "        s.claim = dot(eqTable(s.r0, 1), outs);").unwrap();
+    } else {
+        // asserted outputs are zero: only the sent slots' eq weights count
+        writeln!(src, "        uint256[] memory e = eqTable(s.r0, 1);").unwrap();
+        writeln!(
+            src,
+            "        uint256[] memory w = new uint256[](N_OUTPUTS);"
+        )
+        .unwrap();
+        for (k, g) in sent.iter().enumerate() {
+            writeln!(src, "        w[{k}] = e[{g}];").unwrap();
+        }
+        writeln!(src, "        s.claim = dot(w, outs);").unwrap();
+    }
+    writeln!(src, "        s.pos = N_OUTPUTS;").unwrap();
+    for i in (1..=top).rev() {
+        writeln!(
+            src,
+            "        if (!layer(proof, s, {i}, {})) return false;",
+            b[i - 1]
+        )
+        .unwrap();
+    }
+    writeln!(
+        src,
+        r#"
+        return s.claim == addmod(dot(eqTable(s.r0, s.alpha), x), dot(eqTable(s.r1, s.beta), x), P);
+    }}
+}}"#
+    )
+    .unwrap();
+    src
+}
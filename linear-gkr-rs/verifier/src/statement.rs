@@ -0,0 +1,150 @@
+//! Checking [`StatementProof`]s: public inputs and revealed outputs are
+//! evaluated directly, private inputs and hidden outputs through a sum‑check
+//! over their committed tables, placed on their slots by the statement (see
+//! `prover::Statement`).
+
+use ark_ff::{One, Zero};
+use commit::Generators;
+use field::FieldElement;
+use polynomial::mle::eq_at;
+use prover::{ClaimReduction, CommittedTable, Statement, StatementProof, Transcript};
+
+use crate::{Verifier, VerifierKey};
+
+/// `Σ eq(r, slots[k])·values[k]`: the MLE at `r` of a table holding `values`
+/// at `slots` and zeros elsewhere.
+fn sparse_evaluate(r: &[FieldElement], slots: &[usize], values: &[FieldElement]) -> FieldElement {
+    slots
+        .iter()
+        .zip(values)
+        .map(|(&g, &x)| eq_at(r, g) * x)
+        .sum()
+}
+
+/// Check the sum‑check of `table` (see `prover::CommittedTable`) for the
+/// layer holding the committed values at `slots`: its claimed evaluations at
+/// `points`, if they check out.
+fn opened(
+    gens: &Generators,
+    table: &CommittedTable,
+    slots: &[usize],
+    points: &[&[FieldElement]],
+    transcript: &mut impl Transcript,
+) -> Option<Vec<FieldElement>> {
+    let vars = slots.len().next_power_of_two().trailing_zeros() as usize;
+    if table.evals.len() != points.len() || table.rounds.len() != vars {
+        return None;
+    }
+    transcript.absorb(&table.evals);
+    let gammas = transcript.challenges(points.len());
+    let mut claim: FieldElement = gammas.iter().zip(&table.evals).map(|(&y, &e)| y * e).sum();
+    let mut rho = Vec::with_capacity(vars);
+    for p in &table.rounds {
+        if p.eval(FieldElement::zero()) + p.eval(FieldElement::one()) != claim {
+            return None;
+        }
+        transcript.absorb(&[p.a, p.b, p.c]);
+        let r = transcript.challenge();
+        claim = p.eval(r);
+        rho.push(r);
+    }
+    transcript.absorb(&[table.value]);
+
+    // S̃(ρ) = Σ_j eq(ρ, j)·Σ_k γ_k·eq(r_k, s_j), from the slot map
+    let weight: FieldElement = slots
+        .iter()
+        .enumerate()
+        .map(|(j, &g)| {
+            let w: FieldElement = points
+                .iter()
+                .zip(&gammas)
+                .map(|(r, &y)| y * eq_at(r, g))
+                .sum();
+            eq_at(&rho, j) * w
+        })
+        .sum();
+    let ok = claim == table.value * weight
+        && table
+            .commitment
+            .verify(gens, &rho, table.value, &table.opening);
+    ok.then(|| table.evals.clone())
+}
+
+impl Verifier {
+    /// Check `proof` of `vk`'s circuit for `statement`, given the values of
+    /// its public inputs in order.
+    pub fn verify_statement(
+        vk: &VerifierKey,
+        statement: &Statement,
+        public_inputs: &[FieldElement],
+        proof: &StatementProof,
+        transcript: &mut impl Transcript,
+    ) -> bool {
+        let c = vk.circuit;
+        let private = statement.private_inputs(c);
+        let hidden = statement.hidden_outputs(c);
+        let shape_ok = statement.is_valid(c)
+            && public_inputs.len() == statement.public_inputs.len()
+            && proof.gkr.outputs.len() == statement.revealed_outputs.len()
+            && proof.gkr.reduction == ClaimReduction::Combine
+            && private.is_empty() == proof.private_inputs.is_none()
+            && hidden.is_empty() == proof.hidden_outputs.is_none();
+        if !shape_ok {
+            eprintln!("proof does not match the statement");
+            return false;
+        }
+        let gens = Statement::generators(c);
+        statement.bind(c, vk.digest, public_inputs, transcript);
+        let tables = [&proof.private_inputs, &proof.hidden_outputs];
+        for table in tables.into_iter().flatten() {
+            transcript.absorb(&table.commitment.to_field_elements());
+        }
+
+        let bit_lengths: Vec<_> = c.layers.iter().map(|l| l.bit_length).collect();
+        let mut r0 = Vec::new();
+        let top_claim = |r: &[FieldElement]| {
+            r0 = r.to_vec();
+            let revealed = sparse_evaluate(r, &statement.revealed_outputs, &proof.gkr.outputs);
+            let hidden = match &proof.hidden_outputs {
+                Some(table) => *table.evals.first()?,
+                None => FieldElement::zero(),
+            };
+            Some(revealed + hidden)
+        };
+        let Some(input) = Self::reduce_layers(
+            &bit_lengths,
+            &proof.gkr,
+            transcript,
+            top_claim,
+            |i, ch, lp, claim| claim == Self::wiring(&c.layers[i], ch).eval(lp.v_u, lp.v_v),
+        ) else {
+            return false;
+        };
+
+        let private = match &proof.private_inputs {
+            Some(table) => {
+                // the input claim's points are the last layer's r_u and r_v
+                match opened(&gens, table, &private, &[&input.r0, &input.r1], transcript) {
+                    Some(evals) => evals,
+                    None => {
+                        eprintln!("private input table failed");
+                        return false;
+                    }
+                }
+            }
+            None => vec![FieldElement::zero(); 2],
+        };
+        if let Some(table) = &proof.hidden_outputs {
+            if opened(&gens, table, &hidden, &[&r0], transcript).is_none() {
+                eprintln!("hidden output table failed");
+                return false;
+            }
+        }
+        input.claim
+            == input.alpha
+                * (sparse_evaluate(&input.r0, &statement.public_inputs, public_inputs) + private[0])
+                + input.beta
+                    * (sparse_evaluate(&input.r1, &statement.public_inputs, public_inputs)
+                        + private[1])
+    }
+}
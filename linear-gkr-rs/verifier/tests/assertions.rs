@@ -0,0 +1,84 @@
+//! Outputs asserted to be zero are left out of proofs and checked against
+//! zero by the verifier.
+
+mod common;
+
+use circuit::{Builder, Circuit};
+use field::FieldElement;
+use prover::{estimate, ClaimReduction, Keccak256Transcript, Proof, Prover};
+use verifier::{Verifier, VerifierKey};
+
+use common::field;
+
+/// `x0·x1 − x2` and `x0 + x1 − x3`, both asserted, next to the public `x0·x3`.
+fn circuit() -> Circuit {
+    let mut b = Builder::new();
+    let x = b.inputs(4);
+    let m = b.mul(x[0], x[1]);
+    let d = b.sub(m, x[2]);
+    b.assert_zero(d);
+    let p = b.mul(x[0], x[3]);
+    b.output(p);
+    let s = b.add(x[0], x[1]);
+    let e = b.sub(s, x[3]);
+    b.assert_zero(e);
+    b.build()
+}
+
+fn prove(c: &Circuit, inputs: &[FieldElement]) -> Proof {
+    Prover::evaluate_with_inputs(c, inputs).prove(c, &mut Keccak256Transcript::default())
+}
+
+fn accepts(c: &Circuit, inputs: &[FieldElement], proof: &Proof) -> bool {
+    Verifier::verify_proof(
+        &VerifierKey::new(c),
+        inputs,
+        proof,
+        &mut Keccak256Transcript::default(),
+    )
+}
+
+#[test]
+fn asserted_outputs_are_not_sent() {
+    let c = circuit();
+    assert_eq!(c.assertions, [0, 2]);
+    let inputs = field(&[3, 4, 12, 7]);
+    let proof = prove(&c, &inputs);
+    // the public output and the dummy slot
+    assert_eq!(proof.outputs, field(&[21, 0]));
+    assert_eq!(
+        proof.num_field_elements(),
+        estimate(&c, ClaimReduction::Combine).proof_elements
+    );
+    assert!(accepts(&c, &inputs, &proof));
+    assert_eq!(
+        proof.output_layer(2, &c.assertions).unwrap(),
+        field(&[0, 21, 0, 0])
+    );
+}
+
+#[test]
+fn failed_assertions_are_rejected() {
+    let c = circuit();
+    // x0·x1 ≠ x2
+    let inputs = field(&[3, 4, 11, 7]);
+    assert!(!accepts(&c, &inputs, &prove(&c, &inputs)));
+    // sending the asserted outputs as well does not fit the circuit
+    let inputs = field(&[3, 4, 12, 7]);
+    let mut proof = prove(&c, &inputs);
+    proof.outputs.insert(0, FieldElement::from(0u64));
+    assert!(!accepts(&c, &inputs, &proof));
+}
+
+#[test]
+fn malformed_assertion_lists_are_rejected() {
+    let mut c = circuit();
+    let inputs = field(&[3, 4, 12, 7]);
+    let proof = prove(&c, &inputs);
+    // two outputs sent, so each list below has the right length
+    for assertions in [vec![0, 0], vec![2, 0], vec![3, 3], vec![0, 4]] {
+        assert_eq!(proof.output_layer(2, &assertions), None, "{assertions:?}");
+        c.assertions = assertions;
+        assert!(!accepts(&c, &inputs, &proof), "{:?}", c.assertions);
+    }
+}
@@ -0,0 +1,194 @@
+//! Batches of proofs for one circuit: honest batches verify on any
+//! transcript, and the first invalid proof is named.
+
+mod common;
+
+use std::collections::BTreeMap;
+
+use ark_ff::One;
+use circuit::{Builder, Circuit, Gate, GateType, Layer};
+use field::FieldElement;
+use prover::{Keccak256Transcript, MimcTranscript, Proof, Prover, Transcript};
+use verifier::{BatchError, Verifier, VerifierKey};
+
+use common::field;
+
+/// `(x0 + x1)·x2 − x3` and `x1·x3`.
+fn circuit() -> Circuit {
+    let mut b = Builder::new();
+    let x = b.inputs(4);
+    let s = b.add(x[0], x[1]);
+    let p = b.mul(s, x[2]);
+    let d = b.sub(p, x[3]);
+    let q = b.mul(x[1], x[3]);
+    b.output(d);
+    b.output(q);
+    b.build()
+}
+
+/// Four inputs multiplied pairwise by a structured layer.
+fn structured() -> Circuit {
+    let inputs: BTreeMap<_, _> = (0..4)
+        .map(|g| {
+            (
+                g,
+                Gate {
+                    ty: GateType::Input,
+                    u: 0,
+                    v: 0,
+                },
+            )
+        })
+        .collect();
+    Circuit {
+        layers: vec![
+            Layer::from_sparse(&inputs, 2),
+            Layer::binary_tree(GateType::Mul, 1),
+        ],
+        assertions: Vec::new(),
+    }
+}
+
+fn batch() -> Vec<Vec<FieldElement>> {
+    vec![
+        field(&[2, 3, 5, 7]),
+        field(&[1, 1, 2, 3]),
+        field(&[0, 4, 9, 6]),
+    ]
+}
+
+fn prove_all<T: Transcript + Clone>(
+    c: &Circuit,
+    inputs: &[Vec<FieldElement>],
+    transcript: &T,
+) -> Vec<Proof> {
+    inputs
+        .iter()
+        .map(|x| Prover::evaluate_with_inputs(c, x).prove(c, &mut transcript.clone()))
+        .collect()
+}
+
+fn verify(c: &Circuit, inputs: &[Vec<FieldElement>], proofs: &[Proof]) -> Result<(), BatchError> {
+    Verifier::verify_batch(
+        &VerifierKey::new(c),
+        inputs,
+        proofs,
+        &Keccak256Transcript::default(),
+    )
+}
+
+#[test]
+fn honest_batches_verify() {
+    let inputs = batch();
+    for c in [circuit(), structured()] {
+        let keccak = Keccak256Transcript::default();
+        let proofs = prove_all(&c, &inputs, &keccak);
+        assert_eq!(
+            Verifier::verify_batch(&VerifierKey::new(&c), &inputs, &proofs, &keccak),
+            Ok(())
+        );
+
+        let mimc = MimcTranscript::default();
+        let proofs = prove_all(&c, &inputs, &mimc);
+        assert_eq!(
+            Verifier::verify_batch(&VerifierKey::new(&c), &inputs, &proofs, &mimc),
+            Ok(())
+        );
+        // the proofs' transcript is the batch's
+        assert_eq!(
+            Verifier::verify_batch(&VerifierKey::new(&c), &inputs, &proofs, &keccak),
+            Err(BatchError::Invalid(0))
+        );
+    }
+    assert_eq!(verify(&circuit(), &[], &[]), Ok(()));
+}
+
+#[test]
+fn the_first_invalid_proof_is_named() {
+    let inputs = batch();
+    let one = FieldElement::one();
+    for c in [circuit(), structured()] {
+        let depth = c.layers.len() - 1;
+        for k in 0..inputs.len() {
+            // each layer's v_u, the bottom one's caught by the combined
+            // deferred equations alone
+            for i in 0..depth {
+                let mut proofs = prove_all(&c, &inputs, &Keccak256Transcript::default());
+                proofs[k].layers[i].v_u += one;
+                assert_eq!(
+                    verify(&c, &inputs, &proofs),
+                    Err(BatchError::Invalid(k)),
+                    "proof {k}, layer {i}"
+                );
+            }
+
+            let mut proofs = prove_all(&c, &inputs, &Keccak256Transcript::default());
+            proofs[k].outputs[0] += one;
+            assert_eq!(verify(&c, &inputs, &proofs), Err(BatchError::Invalid(k)));
+
+            let proofs = prove_all(&c, &inputs, &Keccak256Transcript::default());
+            let mut wrong = inputs.clone();
+            wrong[k][1] += one;
+            assert_eq!(verify(&c, &wrong, &proofs), Err(BatchError::Invalid(k)));
+        }
+
+        // two invalid proofs: the first is named
+        let mut proofs = prove_all(&c, &inputs, &Keccak256Transcript::default());
+        proofs[2].layers[depth - 1].v_v += one;
+        proofs[1].layers[depth - 1].v_v += one;
+        assert_eq!(verify(&c, &inputs, &proofs), Err(BatchError::Invalid(1)));
+    }
+}
+
+#[test]
+fn mismatched_lengths_are_an_error() {
+    let c = circuit();
+    let inputs = batch();
+    let mut proofs = prove_all(&c, &inputs, &Keccak256Transcript::default());
+    proofs.pop();
+    let err = verify(&c, &inputs, &proofs).unwrap_err();
+    assert_eq!(
+        err,
+        BatchError::Length {
+            inputs: 3,
+            proofs: 2
+        }
+    );
+    assert_eq!(err.to_string(), "3 assignments for 2 proofs");
+    assert_eq!(
+        verify(&c, &inputs[..1], &proofs),
+        Err(BatchError::Length {
+            inputs: 1,
+            proofs: 2
+        })
+    );
+}
+
+#[test]
+fn over_long_assignments_are_an_error() {
+    let c = circuit();
+    let inputs = batch();
+    let proofs = prove_all(&c, &inputs, &Keccak256Transcript::default());
+    let mut long = inputs.clone();
+    long[1] = c.pad_inputs(&long[1]);
+    assert_eq!(verify(&c, &long, &proofs), Ok(()));
+
+    long[1].push(FieldElement::from(0u64));
+    let slots = long[1].len() - 1;
+    let err = verify(&c, &long, &proofs).unwrap_err();
+    assert_eq!(
+        err,
+        BatchError::InputLength {
+            index: 1,
+            len: slots + 1,
+            slots
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "assignment 1 has {} values for {slots} input slots",
+            slots + 1
+        )
+    );
+}
@@ -0,0 +1,121 @@
+//! One proof for many witnesses of a circuit, as the copies of
+//! `DataParallel::batch`.
+
+mod common;
+
+use ark_ff::{One, Zero};
+use circuit::{Builder, Circuit, DataParallel, Layered};
+use field::FieldElement;
+use prover::{Keccak256Transcript, Proof, Prover};
+use verifier::{Verifier, VerifierKey};
+
+use common::field;
+
+/// `(x0 + x1)·x2 − x3` and `x1·x3`, with `x1·x3 − x1·x3` asserted zero.
+fn circuit() -> Circuit {
+    let mut b = Builder::new();
+    let x = b.inputs(4);
+    let s = b.add(x[0], x[1]);
+    let p = b.mul(s, x[2]);
+    let d = b.sub(p, x[3]);
+    let q = b.mul(x[1], x[3]);
+    let z = b.sub(q, q);
+    b.output(d);
+    b.output(q);
+    b.assert_zero(z);
+    b.build()
+}
+
+fn witnesses(n: u64) -> Vec<Vec<FieldElement>> {
+    (0..n)
+        .map(|k| field(&[k + 2, 3 * k + 1, 7 - k % 7, k * k]))
+        .collect()
+}
+
+fn prove(dp: &DataParallel, witnesses: &[Vec<FieldElement>]) -> Proof {
+    Prover::prove_batch(dp, witnesses, &mut Keccak256Transcript::default())
+}
+
+fn verify(dp: &DataParallel, witnesses: &[Vec<FieldElement>], proof: &Proof) -> bool {
+    Verifier::verify_batch_proof(
+        &VerifierKey::new(dp),
+        witnesses,
+        proof,
+        &mut Keccak256Transcript::default(),
+    )
+}
+
+/// Each copy's slice of the proof's output layer.
+fn copy_outputs(dp: &DataParallel, proof: &Proof) -> Vec<Vec<FieldElement>> {
+    let top = dp.depth() - 1;
+    let outputs = proof
+        .output_layer(dp.bit_length(top), &dp.assertions())
+        .expect("well‑formed proof");
+    dp.copy_outputs(&outputs).map(<[_]>::to_vec).collect()
+}
+
+#[test]
+fn witness_outputs_come_back_through_copy_outputs() {
+    let c = circuit();
+    let xs = witnesses(4);
+    let alone: Vec<_> = xs
+        .iter()
+        .map(|x| Prover::evaluate_with_inputs(&c, x).outputs().to_vec())
+        .collect();
+
+    let dp = DataParallel::batch(c, xs.len());
+    let proof = prove(&dp, &xs);
+    assert!(verify(&dp, &xs, &proof));
+    assert_eq!(copy_outputs(&dp, &proof), alone);
+    // the asserted slot is zero in every copy, without being sent
+    assert_eq!(proof.outputs.len(), 4 * 3);
+    assert!(alone.iter().all(|out| out[2].is_zero()));
+}
+
+#[test]
+fn batches_pad_to_a_power_of_two_copies() {
+    let c = circuit();
+    let padding = Prover::evaluate_with_inputs(&c, &[]).outputs().to_vec();
+    for n in 1..=5 {
+        let dp = DataParallel::batch(circuit(), n);
+        assert_eq!(dp.copies(), n.next_power_of_two(), "{n} witnesses");
+
+        let xs = witnesses(n as u64);
+        let proof = prove(&dp, &xs);
+        assert!(verify(&dp, &xs, &proof), "{n} witnesses");
+        let outputs = copy_outputs(&dp, &proof);
+        for (k, out) in outputs.iter().enumerate() {
+            let expected = match xs.get(k) {
+                Some(x) => Prover::evaluate_with_inputs(&c, x).outputs().to_vec(),
+                None => padding.clone(),
+            };
+            assert_eq!(*out, expected, "copy {k} of {n} witnesses");
+        }
+
+        // the padding copies are zero inputs, not free ones
+        let mut extra = xs.clone();
+        extra.resize(dp.copies(), vec![FieldElement::one()]);
+        if extra.len() > n {
+            assert!(!verify(&dp, &extra, &proof), "{n} witnesses");
+        }
+        // and there is no room past them
+        extra.resize(dp.copies() + 1, Vec::new());
+        assert!(!verify(&dp, &extra, &proof), "{n} witnesses");
+    }
+}
+
+#[test]
+fn one_tampered_witness_is_rejected() {
+    let xs = witnesses(3);
+    let dp = DataParallel::batch(circuit(), xs.len());
+    let proof = prove(&dp, &xs);
+    for k in 0..xs.len() {
+        for slot in 0..4 {
+            let mut bad = xs.clone();
+            bad[k][slot] += FieldElement::one();
+            assert!(!verify(&dp, &bad, &proof), "witness {k}, slot {slot}");
+        }
+    }
+    // a dropped witness is a zero one
+    assert!(!verify(&dp, &xs[..2], &proof));
+}
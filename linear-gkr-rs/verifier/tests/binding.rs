@@ -0,0 +1,143 @@
+//! Proofs are bound to their circuit and inputs: a prover that picks the
+//! inputs after seeing the challenges gets nowhere.
+
+mod common;
+
+use ark_ff::{Field, One};
+use circuit::{Builder, Circuit, DataParallel, Layered};
+use field::FieldElement;
+use polynomial::mle::eq_at;
+use prover::{Keccak256Transcript, Proof, Prover, Transcript};
+use verifier::{BatchError, InputClaim, Verifier, VerifierKey};
+
+use common::field;
+
+/// `(x0 + x1)·x2` and `x1·x3`.
+fn circuit() -> Circuit {
+    let mut b = Builder::new();
+    let x = b.inputs(4);
+    let s = b.add(x[0], x[1]);
+    let p = b.mul(s, x[2]);
+    let q = b.mul(x[1], x[3]);
+    b.output(p);
+    b.output(q);
+    b.build()
+}
+
+/// The input claim `proof` leaves when its transcript is bound to `inputs`.
+fn input_claim(c: &Circuit, inputs: &[FieldElement], proof: &Proof) -> InputClaim {
+    let mut transcript = Keccak256Transcript::default();
+    transcript.bind(c.digest(), &c.pad_inputs(inputs));
+    Verifier::reduce(c, proof, &mut transcript).expect("honest proof")
+}
+
+/// Other inputs meeting `claim`: `inputs` moved along slots 0 and 1 so that
+/// `α·Ṽ₀(r₀) + β·Ṽ₀(r₁)` does not change.
+fn shifted(c: &Circuit, inputs: &[FieldElement], claim: &InputClaim) -> Vec<FieldElement> {
+    let weight = |g| claim.alpha * eq_at(&claim.r0, g) + claim.beta * eq_at(&claim.r1, g);
+    let mut forged = c.pad_inputs(inputs);
+    forged[0] += FieldElement::one();
+    forged[1] -= weight(0) * weight(1).inverse().unwrap();
+    forged
+}
+
+#[test]
+fn proofs_do_not_carry_over_to_shifted_inputs() {
+    let c = circuit();
+    let inputs = field(&[2, 3, 5, 7]);
+    let proof =
+        Prover::evaluate_with_inputs(&c, &inputs).prove(&c, &mut Keccak256Transcript::default());
+    assert!(Verifier::verify_proof(
+        &VerifierKey::new(&c),
+        &inputs,
+        &proof,
+        &mut Keccak256Transcript::default()
+    ));
+
+    // the shifted inputs meet the input claim left by the honest challenges,
+    // so only the binding of the inputs tells them apart
+    let claim = input_claim(&c, &inputs, &proof);
+    let forged = shifted(&c, &inputs, &claim);
+    assert_ne!(forged, c.pad_inputs(&inputs));
+    assert!(claim.check(&forged));
+    assert!(!Verifier::verify_proof(
+        &VerifierKey::new(&c),
+        &forged,
+        &proof,
+        &mut Keccak256Transcript::default()
+    ));
+
+    let prove =
+        || Prover::evaluate_with_inputs(&c, &inputs).prove(&c, &mut Keccak256Transcript::default());
+    let batch = [inputs.clone(), forged];
+    assert_eq!(
+        Verifier::verify_batch(
+            &VerifierKey::new(&c),
+            &batch,
+            &[prove(), prove()],
+            &Keccak256Transcript::default()
+        ),
+        Err(BatchError::Invalid(1))
+    );
+}
+
+#[test]
+fn data_parallel_proofs_are_bound_to_every_instance() {
+    let dp = DataParallel::batch(circuit(), 2);
+    let instances = vec![field(&[2, 3, 5, 7]), field(&[1, 1, 2, 3])];
+    let proof = Prover::prove_batch(&dp, &instances, &mut Keccak256Transcript::default());
+    let verify = |instances: &[Vec<FieldElement>]| {
+        Verifier::verify_batch_proof(
+            &VerifierKey::new(&dp),
+            instances,
+            &proof,
+            &mut Keccak256Transcript::default(),
+        )
+    };
+    assert!(verify(&instances));
+
+    let mut transcript = Keccak256Transcript::default();
+    transcript.bind(dp.digest(), &dp.pad_inputs(&instances));
+    let bit_lengths: Vec<_> = (0..dp.depth()).map(|i| dp.bit_length(i)).collect();
+    let (claim, _) =
+        Verifier::reduce_deferred(&bit_lengths, &dp.assertions(), &proof, &mut transcript).unwrap();
+    // move the second instance only, keeping the input claim
+    let layer0 = dp.pad_inputs(&instances);
+    let half = layer0.len() / 2;
+    let weight = |g| claim.alpha * eq_at(&claim.r0, g) + claim.beta * eq_at(&claim.r1, g);
+    let mut forged = layer0.clone();
+    forged[half] += FieldElement::one();
+    forged[half + 1] -= weight(half) * weight(half + 1).inverse().unwrap();
+    assert!(claim.check(&forged));
+    let forged = vec![instances[0].clone(), forged[half..].to_vec()];
+    assert!(!verify(&forged));
+}
+
+#[test]
+fn digests_tell_circuits_apart() {
+    let c = circuit();
+    let mut asserted = circuit();
+    asserted.assertions = vec![1];
+    let mut b = Builder::new();
+    let x = b.inputs(4);
+    let s = b.sub(x[0], x[1]);
+    let p = b.mul(s, x[2]);
+    let q = b.mul(x[1], x[3]);
+    b.output(p);
+    b.output(q);
+    let minus = b.build();
+    let digests = [
+        c.digest(),
+        asserted.digest(),
+        minus.digest(),
+        DataParallel::new(circuit(), 1).digest(),
+        DataParallel::new(circuit(), 2).digest(),
+    ];
+    for (i, a) in digests.iter().enumerate() {
+        assert!(
+            digests[i + 1..].iter().all(|b| a != b),
+            "digest {i} repeats"
+        );
+    }
+    assert_eq!(c.digest(), circuit().digest());
+}
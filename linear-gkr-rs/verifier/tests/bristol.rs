@@ -0,0 +1,92 @@
+//! Bristol Fashion circuits are imported once and proven on any input bits.
+
+mod common;
+
+use circuit::{load_bristol, Bristol};
+use prover::{Keccak256Transcript, Prover};
+use verifier::{Verifier, VerifierKey};
+
+use common::field;
+
+fn bits(x: u64, n: usize) -> Vec<bool> {
+    (0..n).map(|i| x >> i & 1 == 1).collect()
+}
+
+#[test]
+fn full_adder_on_every_input() {
+    let bristol = Bristol::load("../examples/full_adder.bristol").unwrap();
+    assert_eq!(bristol.input_sizes, [1, 1, 1]);
+    assert_eq!(bristol.output_sizes, [1, 1]);
+    let c = bristol.to_circuit();
+    for x in 0..8 {
+        let inputs = bristol.assignment(&bits(x, 3)).unwrap();
+        let prover = Prover::evaluate_with_inputs(&c, &inputs);
+        let total = x.count_ones() as u64;
+        assert_eq!(
+            prover.outputs()[..2],
+            field(&[total & 1, total >> 1]),
+            "{x:03b}"
+        );
+        assert!(Verifier::verify_with_inputs(&c, &prover, &inputs));
+    }
+}
+
+#[test]
+fn proofs_are_bound_to_the_input_bits() {
+    let (c, inputs) = load_bristol("../examples/full_adder.bristol", &bits(0b011, 3)).unwrap();
+    let proof =
+        Prover::evaluate_with_inputs(&c, &inputs).prove(&c, &mut Keccak256Transcript::default());
+    let mut transcript = Keccak256Transcript::default();
+    assert!(Verifier::verify_proof(
+        &VerifierKey::new(&c),
+        &inputs,
+        &proof,
+        &mut transcript
+    ));
+    let other = Bristol::load("../examples/full_adder.bristol")
+        .unwrap()
+        .assignment(&bits(0b111, 3))
+        .unwrap();
+    let mut transcript = Keccak256Transcript::default();
+    assert!(!Verifier::verify_proof(
+        &VerifierKey::new(&c),
+        &other,
+        &proof,
+        &mut transcript
+    ));
+}
+
+#[test]
+fn inv_eq_and_mand_gates() {
+    // outputs ¬a, a ∧ 1 and b ∧ a, with 1 from EQ and b through EQW
+    let text = "\
+        4 8\n2 1 1\n1 3\n\n\
+        1 1 0 5 INV\n\
+        1 1 1 2 EQ\n\
+        1 1 1 3 EQW\n\
+        4 2 0 3 2 0 6 7 MAND\n";
+    let bristol = Bristol::from_reader(text.as_bytes()).unwrap();
+    let c = bristol.to_circuit();
+    for x in 0..4 {
+        let inputs = bristol.assignment(&bits(x, 2)).unwrap();
+        let prover = Prover::evaluate_with_inputs(&c, &inputs);
+        let (a, b) = (x & 1, x >> 1);
+        assert_eq!(prover.outputs()[..3], field(&[1 - a, a, a * b]), "{x:02b}");
+        assert!(Verifier::verify_with_inputs(&c, &prover, &inputs));
+    }
+}
+
+#[test]
+fn malformed_circuits_and_inputs_are_errors() {
+    let bristol = Bristol::load("../examples/full_adder.bristol").unwrap();
+    assert!(bristol.assignment(&bits(0, 2)).is_err());
+    let cases = [
+        "1 3\n1 1\n1 1\n\n2 1 0 5 2 XOR\n",
+        "1 3\n1 1\n1 1\n\n2 1 0 0 2 OR\n",
+        "2 3\n1 1\n1 1\n\n1 1 0 2 INV\n1 1 0 2 INV\n",
+        "1 3\n2 1\n1 1\n",
+    ];
+    for text in cases {
+        assert!(Bristol::from_reader(text.as_bytes()).is_err(), "{text:?}");
+    }
+}
@@ -0,0 +1,46 @@
+//! Helpers shared by the verifier's integration tests.
+
+#![allow(dead_code)]
+
+use circuit::{Builder, Circuit};
+use field::{seeded_rng, FieldElement, FieldExt};
+use rand::Rng;
+
+pub fn field(values: &[u64]) -> Vec<FieldElement> {
+    values.iter().copied().map(FieldElement::from).collect()
+}
+
+/// Random `Builder` gates over one to six inputs, the last one to three of
+/// them as outputs and, half of the time, an assertion that holds.
+pub fn random_circuit(seed: u64) -> Circuit {
+    let mut rng = seeded_rng(seed);
+    let mut b = Builder::new();
+    let inputs = rng.gen_range(1..=6);
+    let mut wires = b.inputs(inputs);
+    for _ in 0..rng.gen_range(1..=24) {
+        let x = wires[rng.gen_range(0..wires.len())];
+        let y = wires[rng.gen_range(0..wires.len())];
+        let w = match rng.gen_range(0..7) {
+            0 => b.add(x, y),
+            1 => b.sub(x, y),
+            2 => b.mul(x, y),
+            3 => b.scale(x, FieldElement::random_with(&mut rng)),
+            4 => b.add_const(x, FieldElement::random_with(&mut rng)),
+            5 => b.constant(FieldElement::random_with(&mut rng)),
+            _ => {
+                let k = rng.gen_range(1..=wires.len());
+                b.sum(&wires[wires.len() - k..])
+            }
+        };
+        wires.push(w);
+    }
+    for &w in wires.iter().rev().take(rng.gen_range(1..=3)) {
+        b.output(w);
+    }
+    if rng.gen_bool(0.5) {
+        let x = wires[rng.gen_range(0..wires.len())];
+        let zero = b.sub(x, x);
+        b.assert_zero(zero);
+    }
+    b.build()
+}
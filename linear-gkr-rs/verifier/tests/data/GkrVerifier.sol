@@ -0,0 +1,220 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// GKR verifier for one fixed circuit, generated by linear-gkr-rs.
+contract GkrVerifier {
+    uint256 constant P = 21888242871839275222246405745257275088548364400416034343698204186575808495617;
+    uint256 constant DIGEST = 10979070164983988686044149434339383289603557373574279101555991943994248921526;
+    uint256 constant N_INPUTS = 4;
+    uint256 constant N_OUTPUTS = 2;
+    uint256 constant PROOF_LEN = 72;
+
+    struct State {
+        bytes32 t;
+        uint256 claim;
+        uint256 alpha;
+        uint256 beta;
+        uint256[] r0;
+        uint256[] r1;
+        uint256 pos;
+    }
+
+    function challenge(State memory s) internal pure returns (uint256) {
+        s.t = keccak256(abi.encodePacked(s.t));
+        return uint256(s.t) % P;
+    }
+
+    function eqTable(uint256[] memory r, uint256 scale) internal pure returns (uint256[] memory t) {
+        t = new uint256[](1 << r.length);
+        t[0] = scale;
+        for (uint256 i = 0; i < r.length; i++) {
+            uint256 half = 1 << i;
+            for (uint256 j = 0; j < half; j++) {
+                uint256 hi = mulmod(t[j], r[i], P);
+                t[j | half] = hi;
+                t[j] = addmod(t[j], P - hi, P);
+            }
+        }
+    }
+
+    function dot(uint256[] memory x, uint256[] memory y) internal pure returns (uint256 acc) {
+        for (uint256 i = 0; i < y.length; i++) {
+            acc = addmod(acc, mulmod(x[i], y[i], P), P);
+        }
+    }
+
+    function round(uint256[] calldata proof, State memory s) internal pure returns (bool, uint256) {
+        uint256 a = proof[s.pos];
+        uint256 b = proof[s.pos + 1];
+        uint256 c = proof[s.pos + 2];
+        s.pos += 3;
+        if (addmod(addmod(a, b, P), addmod(c, c, P), P) != s.claim) return (false, 0);
+        s.t = keccak256(abi.encodePacked(s.t, a, b, c));
+        uint256 r = challenge(s);
+        s.claim = addmod(mulmod(addmod(mulmod(a, r, P), b, P), r, P), c, P);
+        return (true, r);
+    }
+
+    function layer(uint256[] calldata proof, State memory s, uint256 i, uint256 n) internal pure returns (bool) {
+        uint256 vu = proof[s.pos + 6 * n];
+        uint256 vv = proof[s.pos + 6 * n + 1];
+        uint256[] memory ru = new uint256[](n);
+        uint256[] memory rv = new uint256[](n);
+        bool ok;
+        for (uint256 k = 0; k < n; k++) {
+            (ok, ru[k]) = round(proof, s);
+            if (!ok) return false;
+        }
+        if (n > 0) s.t = keccak256(abi.encodePacked(s.t, vu));
+        for (uint256 k = 0; k < n; k++) {
+            (ok, rv[k]) = round(proof, s);
+            if (!ok) return false;
+        }
+        s.t = keccak256(abi.encodePacked(s.t, vv));
+        s.pos += 2;
+
+        uint256[] memory g = eqTable(s.r0, s.alpha);
+        uint256[] memory g1 = eqTable(s.r1, s.beta);
+        for (uint256 j = 0; j < g.length; j++) {
+            g[j] = addmod(g[j], g1[j], P);
+        }
+        if (s.claim != wiring(i, g, eqTable(ru, 1), eqTable(rv, 1), vu, vv)) return false;
+
+        s.alpha = challenge(s);
+        s.beta = challenge(s);
+        s.claim = addmod(mulmod(s.alpha, vu, P), mulmod(s.beta, vv, P), P);
+        s.r0 = ru;
+        s.r1 = rv;
+        return true;
+    }
+
+    function combine(uint256 cc, uint256 cu, uint256 cv, uint256 cm, uint256 vu, uint256 vv) internal pure returns (uint256) {
+        uint256 lin = addmod(mulmod(cu, vu, P), mulmod(cv, vv, P), P);
+        return addmod(addmod(cc, lin, P), mulmod(cm, mulmod(vu, vv, P), P), P);
+    }
+
+    function wiring(uint256 i, uint256[] memory g, uint256[] memory u, uint256[] memory v, uint256 vu, uint256 vv) internal pure returns (uint256) {
+        if (i == 1) return wiring1(g, u, v, vu, vv);
+        if (i == 2) return wiring2(g, u, v, vu, vv);
+        if (i == 3) return wiring3(g, u, v, vu, vv);
+        if (i == 4) return wiring4(g, u, v, vu, vv);
+        if (i == 5) return wiring5(g, u, v, vu, vv);
+        revert();
+    }
+
+    function wiring1(uint256[] memory g, uint256[] memory u, uint256[] memory v, uint256 vu, uint256 vv) internal pure returns (uint256) {
+        uint256 w;
+        uint256 cc;
+        uint256 cu;
+        uint256 cv;
+        uint256 cm;
+        w = mulmod(mulmod(g[0], u[0], P), v[0], P);
+        cu = addmod(cu, w, P);
+        w = mulmod(mulmod(g[1], u[0], P), v[1], P);
+        cm = addmod(cm, w, P);
+        w = mulmod(mulmod(g[2], u[2], P), v[0], P);
+        cu = addmod(cu, mulmod(w, 3, P), P);
+        w = mulmod(mulmod(g[3], u[1], P), v[3], P);
+        cu = addmod(cu, w, P);
+        cv = addmod(cv, w, P);
+        return combine(cc, cu, cv, cm, vu, vv);
+    }
+
+    function wiring2(uint256[] memory g, uint256[] memory u, uint256[] memory v, uint256 vu, uint256 vv) internal pure returns (uint256) {
+        uint256 w;
+        uint256 cc;
+        uint256 cu;
+        uint256 cv;
+        uint256 cm;
+        w = mulmod(mulmod(g[0], u[0], P), v[0], P);
+        cu = addmod(cu, w, P);
+        w = mulmod(mulmod(g[1], u[1], P), v[2], P);
+        cu = addmod(cu, w, P);
+        cv = addmod(cv, P - w, P);
+        w = mulmod(mulmod(g[2], u[3], P), v[0], P);
+        cu = addmod(cu, w, P);
+        return combine(cc, cu, cv, cm, vu, vv);
+    }
+
+    function wiring3(uint256[] memory g, uint256[] memory u, uint256[] memory v, uint256 vu, uint256 vv) internal pure returns (uint256) {
+        uint256 w;
+        uint256 cc;
+        uint256 cu;
+        uint256 cv;
+        uint256 cm;
+        w = mulmod(mulmod(g[0], u[0], P), v[0], P);
+        cu = addmod(cu, w, P);
+        w = mulmod(mulmod(g[1], u[1], P), v[0], P);
+        cc = addmod(cc, mulmod(w, 5, P), P);
+        cu = addmod(cu, w, P);
+        w = mulmod(mulmod(g[2], u[2], P), v[0], P);
+        cu = addmod(cu, w, P);
+        return combine(cc, cu, cv, cm, vu, vv);
+    }
+
+    function wiring4(uint256[] memory g, uint256[] memory u, uint256[] memory v, uint256 vu, uint256 vv) internal pure returns (uint256) {
+        uint256 w;
+        uint256 cc;
+        uint256 cu;
+        uint256 cv;
+        uint256 cm;
+        w = mulmod(mulmod(g[0], u[0], P), v[0], P);
+        cu = addmod(cu, w, P);
+        w = mulmod(mulmod(g[1], u[1], P), v[0], P);
+        cu = addmod(cu, w, P);
+        w = mulmod(mulmod(g[2], u[1], P), v[2], P);
+        cm = addmod(cm, w, P);
+        return combine(cc, cu, cv, cm, vu, vv);
+    }
+
+    function wiring5(uint256[] memory g, uint256[] memory u, uint256[] memory v, uint256 vu, uint256 vv) internal pure returns (uint256) {
+        uint256 w;
+        uint256 cc;
+        uint256 cu;
+        uint256 cv;
+        uint256 cm;
+        w = mulmod(mulmod(g[0], u[2], P), v[0], P);
+        cu = addmod(cu, w, P);
+        cv = addmod(cv, P - w, P);
+        w = mulmod(mulmod(g[1], u[1], P), v[0], P);
+        cu = addmod(cu, w, P);
+        return combine(cc, cu, cv, cm, vu, vv);
+    }
+
+    function verify(uint256[] calldata inputs, uint256[] calldata proof) external pure returns (bool) {
+        if (inputs.length > N_INPUTS || proof.length != PROOF_LEN) return false;
+        for (uint256 k = 0; k < proof.length; k++) {
+            if (proof[k] >= P) return false;
+        }
+        uint256[] memory x = new uint256[](N_INPUTS);
+        for (uint256 k = 0; k < inputs.length; k++) {
+            if (inputs[k] >= P) return false;
+            x[k] = inputs[k];
+        }
+
+        State memory s;
+        s.t = keccak256("linear-gkr");
+        s.t = keccak256(abi.encodePacked(s.t, DIGEST, x));
+        uint256[] memory outs = new uint256[](N_OUTPUTS);
+        for (uint256 k = 0; k < N_OUTPUTS; k++) {
+            outs[k] = proof[k];
+        }
+        s.t = keccak256(abi.encodePacked(s.t, uint256(0), outs));
+        s.r0 = new uint256[](1);
+        for (uint256 k = 0; k < 1; k++) {
+            s.r0[k] = challenge(s);
+        }
+        s.r1 = s.r0;
+        s.alpha = 1;
+
+        s.claim = dot(eqTable(s.r0, 1), outs);
+        s.pos = N_OUTPUTS;
+        if (!layer(proof, s, 5, 2)) return false;
+        if (!layer(proof, s, 4, 2)) return false;
+        if (!layer(proof, s, 3, 2)) return false;
+        if (!layer(proof, s, 2, 2)) return false;
+        if (!layer(proof, s, 1, 2)) return false;
+
+        return s.claim == addmod(dot(eqTable(s.r0, s.alpha), x), dot(eqTable(s.r1, s.beta), x), P);
+    }
+}
@@ -0,0 +1,13 @@
+#!/bin/sh
+# Regenerate the Solidity verifier snapshot (GkrVerifier.sol) from the
+# generator, and its runtime bytecode (GkrVerifier.bin) with solc 0.8.20 or
+# later, taken from $SOLC or PATH.
+set -eu
+cd "$(dirname "$0")"
+SOLC=${SOLC:-solc}
+
+UPDATE_SNAPSHOT=1 cargo test -q -p verifier --test solidity contract_source_matches_snapshot
+out=$("$SOLC" --optimize --bin-runtime GkrVerifier.sol)
+bin=$(printf '%s\n' "$out" | awk '/^Binary of the runtime part:/ { getline; print; exit }')
+test -n "$bin"
+printf '%s\n' "$bin" > GkrVerifier.bin
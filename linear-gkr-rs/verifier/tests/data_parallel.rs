@@ -0,0 +1,124 @@
+mod common;
+
+use circuit::{compile, DataParallel, Layered};
+use field::FieldElement;
+use prover::{Keccak256Transcript, Proof, Prover};
+use verifier::{Verifier, VerifierKey};
+
+use common::field;
+
+const TEMPLATE: &str = "input a, b, c; let s = a * b + c; output s * s - a, s + 2 * c;";
+
+fn data_parallel(log_copies: usize) -> DataParallel {
+    DataParallel::new(compile(TEMPLATE).unwrap().circuit, log_copies)
+}
+
+/// One layer‑0 assignment per copy, constants included.
+fn instances(n: usize) -> Vec<Vec<FieldElement>> {
+    let p = compile(TEMPLATE).unwrap();
+    (0..n as u64)
+        .map(|i| p.assignment(&field(&[i + 2, 3 * i + 1, 7 - i])).unwrap())
+        .collect()
+}
+
+fn prove(dp: &DataParallel, instances: &[Vec<FieldElement>]) -> Proof {
+    Prover::evaluate_data_parallel(dp, instances).prove(dp, &mut Keccak256Transcript::default())
+}
+
+fn verify(dp: &DataParallel, instances: &[Vec<FieldElement>], proof: &Proof) -> bool {
+    Verifier::verify_data_parallel(
+        &VerifierKey::new(dp),
+        instances,
+        proof,
+        &mut Keccak256Transcript::default(),
+    )
+}
+
+#[test]
+fn honest_proof_verifies() {
+    for log_copies in 0..4 {
+        let dp = data_parallel(log_copies);
+        let xs = instances(dp.copies());
+        assert!(
+            verify(&dp, &xs, &prove(&dp, &xs)),
+            "log_copies = {log_copies}"
+        );
+    }
+}
+
+#[test]
+fn copies_evaluate_like_the_flattened_circuit() {
+    let dp = data_parallel(2);
+    let xs = instances(3);
+    let flat = dp.flatten();
+    let layer0 = dp.pad_inputs(&xs);
+
+    let copies = Prover::evaluate_data_parallel(&dp, &xs);
+    let explicit = Prover::evaluate_with_inputs(&flat, &layer0);
+    assert_eq!(copies.values, explicit.values);
+
+    // Each copy's outputs are the template's outputs on that copy's inputs.
+    let template = &dp.template;
+    let out = template.layers.last().unwrap().len();
+    for (c, x) in xs.iter().enumerate() {
+        let alone = Prover::evaluate_with_inputs(template, x);
+        assert_eq!(copies.outputs()[c * out..(c + 1) * out], *alone.outputs());
+    }
+
+    // The flattened circuit has the same shape, and its own proof verifies.
+    for i in 0..dp.depth() {
+        assert_eq!(flat.layers[i].bit_length, dp.bit_length(i));
+    }
+    let proof = explicit.prove(&flat, &mut Keccak256Transcript::default());
+    assert!(Verifier::verify_proof(
+        &VerifierKey::new(&flat),
+        &layer0,
+        &proof,
+        &mut Keccak256Transcript::default()
+    ));
+}
+
+#[test]
+fn tampered_copy_is_rejected() {
+    let dp = data_parallel(2);
+    let xs = instances(4);
+    let proof = prove(&dp, &xs);
+    for c in 0..4 {
+        let mut bad = xs.clone();
+        bad[c][1] += FieldElement::from(1u64);
+        assert!(!verify(&dp, &bad, &proof), "copy {c}");
+    }
+}
+
+#[test]
+fn digest_covers_the_copy_count() {
+    let (one, two) = (data_parallel(1), data_parallel(2));
+    assert_ne!(one.digest(), two.digest());
+    assert_ne!(one.digest(), one.template.digest());
+
+    // Two instances fit either copy count, but the proof is for one of them.
+    let xs = instances(2);
+    let proof = prove(&one, &xs);
+    assert!(verify(&one, &xs, &proof));
+    assert!(!verify(&two, &xs, &proof));
+    assert!(!verify(&one, &xs, &prove(&two, &xs)));
+}
+
+#[test]
+fn too_many_inputs_are_rejected() {
+    let dp = data_parallel(1);
+    let xs = instances(2);
+    let proof = prove(&dp, &xs);
+
+    // one more value than the template's input layer has slots
+    let mut long = xs.clone();
+    long[1] = dp.template.pad_inputs(&long[1]);
+    assert!(verify(&dp, &long, &proof));
+    long[1].push(FieldElement::from(0u64));
+    assert!(!verify(&dp, &long, &proof));
+
+    // one more instance than there are copies
+    let mut more = xs.clone();
+    more.push(Vec::new());
+    assert!(!verify(&dp, &more, &proof));
+}
@@ -0,0 +1,156 @@
+//! DSL programs compile to circuits that compute what the source says.
+
+mod common;
+
+use circuit::{compile, GateType};
+use field::{seeded_rng, FieldElement, FieldExt};
+use prover::{Keccak256Transcript, Prover};
+use verifier::{Verifier, VerifierKey};
+
+use common::field;
+
+/// Outputs of `src` on `values`, checked to have a verifying proof.
+fn run(src: &str, values: &[FieldElement]) -> Vec<FieldElement> {
+    let program = compile(src).unwrap();
+    let inputs = program.assignment(values).unwrap();
+    let prover = Prover::evaluate_with_inputs(&program.circuit, &inputs);
+    let proof = prover.prove(&program.circuit, &mut Keccak256Transcript::default());
+    assert!(Verifier::verify_proof(
+        &VerifierKey::new(&program.circuit),
+        &inputs,
+        &proof,
+        &mut Keccak256Transcript::default()
+    ));
+    prover.outputs().to_vec()
+}
+
+fn parse_error(src: &str) -> String {
+    match compile(src) {
+        Err(circuit::Error::Parse(msg)) => msg,
+        Err(e) => panic!("{src:?}: unexpected error {e}"),
+        Ok(_) => panic!("{src:?} compiled"),
+    }
+}
+
+#[test]
+fn module_example() {
+    let src = "
+        input x, y, z;          # inputs, in layer-0 order
+        let a = x * y + 3;
+        let b = -(a - z) * a;
+        output a, b * b;
+    ";
+    let program = compile(src).unwrap();
+    assert_eq!(program.inputs, ["x", "y", "z"]);
+    let out = run(src, &field(&[2, 3, 5]));
+    // a = 9, b = -36
+    assert_eq!(out[..2], field(&[9, 1296]));
+}
+
+#[test]
+fn compiled_programs_match_direct_evaluation() {
+    type Direct = fn(&[FieldElement]) -> Vec<FieldElement>;
+    let programs: [(&str, Direct); 4] = [
+        ("input x, y; output x + y, x - y, x * y;", |v| {
+            vec![v[0] + v[1], v[0] - v[1], v[0] * v[1]]
+        }),
+        ("input x; output -x, - - x, 7 - x;", |v| {
+            vec![-v[0], v[0], FieldElement::from(7u64) - v[0]]
+        }),
+        (
+            "input a, b, c, d; let s = a + b - (c - d); output s * s * a, (a + b) * (c + d);",
+            |v| {
+                let s = v[0] + v[1] - (v[2] - v[3]);
+                vec![s * s * v[0], (v[0] + v[1]) * (v[2] + v[3])]
+            },
+        ),
+        // operands of very different depths
+        (
+            "input x, y; let p = x * x * x * x * x * x * x * x; output p + y + 1, y * p * y;",
+            |v| {
+                let p = (0..8).fold(FieldElement::from(1u64), |p, _| p * v[0]);
+                vec![p + v[1] + FieldElement::from(1u64), v[1] * p * v[1]]
+            },
+        ),
+    ];
+    let mut rng = seeded_rng(31);
+    for (src, direct) in programs {
+        let n = compile(src).unwrap().inputs.len();
+        for _ in 0..4 {
+            let values: Vec<_> = (0..n)
+                .map(|_| FieldElement::random_with(&mut rng))
+                .collect();
+            let expected = direct(&values);
+            assert_eq!(run(src, &values)[..expected.len()], expected, "{src}");
+        }
+    }
+}
+
+#[test]
+fn shared_subexpressions_are_computed_once() {
+    let arithmetic = |src| {
+        let program = compile(src).unwrap();
+        let layers = program.circuit.layers;
+        let count = layers
+            .iter()
+            .flat_map(|l| &l.ty)
+            .filter(|&&ty| matches!(ty, GateType::Add | GateType::Mul))
+            .count();
+        (layers.len(), count)
+    };
+    assert_eq!(
+        arithmetic("input x, y; output x * y + x, x + y * x;"),
+        arithmetic("input x, y; let a = x * y + x; output a, a;")
+    );
+}
+
+#[test]
+fn long_chains_compile() {
+    let src = format!("input x;\noutput x{};", " + x".repeat(99_999));
+    let program = compile(&src).unwrap();
+    // balanced: depth is logarithmic in the chain length
+    assert!(program.circuit.layers.len() <= 20);
+    assert_eq!(run(&src, &field(&[3]))[0], FieldElement::from(300_000u64));
+}
+
+#[test]
+fn deep_nesting_is_an_error() {
+    let shallow = format!("input x; output {}x{};", "(".repeat(200), ")".repeat(200));
+    assert_eq!(run(&shallow, &field(&[4]))[0], FieldElement::from(4u64));
+
+    let deep = format!(
+        "input x; output {}x{};",
+        "(".repeat(100_000),
+        ")".repeat(100_000)
+    );
+    assert!(parse_error(&deep).contains("nested deeper"));
+    let negations = format!("input x; output {}x;", "-".repeat(100_000));
+    assert!(parse_error(&negations).contains("nested deeper"));
+}
+
+#[test]
+fn malformed_programs_are_errors() {
+    for (src, msg) in [
+        ("input x; output y;", "undefined `y`"),
+        ("input x, x; output x;", "`x` already defined"),
+        ("input x; let x = 1; output x;", "`x` already defined"),
+        (
+            "input x; let a = x * x; input y; output a;",
+            "inputs must be declared",
+        ),
+        ("input x; print x;", "unknown statement `print`"),
+        ("input x; output x", "expected `;`"),
+        ("input x; output (x;", "expected `)`"),
+        ("input x; output x +;", "expected expression"),
+        ("input x; output x / 2;", "unexpected '/'"),
+        ("input x; output 99999999999999999999;", "bad number"),
+        ("input x;", "no outputs"),
+    ] {
+        let err = parse_error(src);
+        assert!(err.contains(msg), "{src:?}: {err:?}");
+    }
+    assert!(parse_error("input x;\n\noutput y;").starts_with("line 3:"));
+
+    let program = compile("input x, y; output x * y;").unwrap();
+    assert!(program.assignment(&field(&[1])).is_err());
+}
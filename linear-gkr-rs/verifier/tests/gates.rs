@@ -0,0 +1,220 @@
+//! Every gate type evaluates to the sum of its wiring terms, and constant
+//! gates read the right entries of their layer's constant pool.
+
+use std::collections::BTreeMap;
+
+use ark_ff::{One, Zero};
+use circuit::{Circuit, Gate, GateType, Layer, LayerReader};
+use field::{seeded_rng, FieldElement, FieldExt};
+use prover::{ClaimReduction, Keccak256Transcript, Prover};
+use verifier::{Verifier, VerifierKey};
+
+const TWO_TO_100: &str = "1267650600228229401496703205376";
+
+fn fe(x: u64) -> FieldElement {
+    FieldElement::from(x)
+}
+
+fn layer(bit_length: usize, gates: &[(GateType, usize, usize)], constants: &[u64]) -> Layer {
+    let gates: BTreeMap<_, _> = gates
+        .iter()
+        .enumerate()
+        .map(|(g, &(ty, u, v))| (g, Gate { ty, u, v }))
+        .collect();
+    let mut layer = Layer::from_sparse(&gates, bit_length);
+    layer.constants = constants.iter().copied().map(fe).collect();
+    layer
+}
+
+/// One gate of each type over four inputs, then a layer summing them up.
+/// `constants` is layer 1's pool: `Const` reads entry 0, `ScalarMul` entry 1
+/// and `AddConst` entry 2.
+fn all_gates(constants: [u64; 3]) -> Circuit {
+    use GateType::*;
+    let gates = [
+        (Add, 0, 1),
+        (Mul, 1, 2),
+        (Dummy, 0, 0),
+        (Input, 9, 0),
+        (DirectRelay, 3, 0),
+        (Sum, 0, 4),
+        (Not, 2, 0),
+        (Minus, 3, 0),
+        (Xor, 0, 1),
+        (Naab, 2, 3),
+        (Relay, 1, 0),
+        (Const, 0, 0),
+        (ScalarMul, 3, 1),
+        (AddConst, 2, 2),
+    ];
+    let inputs = layer(2, &[(Input, 0, 0); 4], &[]);
+    let top = layer(0, &[(Sum, 0, gates.len())], &[]);
+    Circuit {
+        layers: vec![inputs, layer(4, &gates, &constants), top],
+        assertions: Vec::new(),
+    }
+}
+
+/// `V(g)` recomputed from the terms of gate `g` over `prev`.
+fn from_terms(layer: &Layer, g: usize, prev: &[FieldElement]) -> FieldElement {
+    let mut value = FieldElement::zero();
+    layer.terms(g, |t| {
+        let (x, y) = (prev[t.u], prev[t.v]);
+        value += t.cst + t.lin_u * x + t.lin_v * y + t.mul * x * y;
+    });
+    value
+}
+
+fn assert_terms_match(c: &Circuit, prover: &Prover) {
+    for (l, layer) in c.layers.iter().enumerate().skip(1) {
+        for g in 0..layer.len() {
+            assert_eq!(
+                from_terms(layer, g, &prover.values[l - 1]),
+                prover.values[l][g],
+                "layer {l} gate {g} ({:?})",
+                layer.ty[g]
+            );
+        }
+    }
+}
+
+fn verifies(c: &Circuit, inputs: &[FieldElement], reduction: ClaimReduction) -> bool {
+    let proof = Prover::evaluate_with_inputs(c, inputs).prove_with_reduction(
+        c,
+        reduction,
+        &mut Keccak256Transcript::default(),
+    );
+    Verifier::verify_proof(
+        &VerifierKey::new(c),
+        inputs,
+        &proof,
+        &mut Keccak256Transcript::default(),
+    )
+}
+
+#[test]
+fn every_gate_type_evaluates_to_its_terms() {
+    let c = all_gates([5, 7, 11]);
+    let mut rng = seeded_rng(32);
+    for _ in 0..4 {
+        let x: Vec<_> = (0..4)
+            .map(|_| FieldElement::random_with(&mut rng))
+            .collect();
+        let prover = Prover::evaluate_with_inputs(&c, &x);
+        assert_terms_match(&c, &prover);
+
+        let one = FieldElement::one();
+        let expected = [
+            x[0] + x[1],
+            x[1] * x[2],
+            FieldElement::zero(),
+            fe(9),
+            x[3],
+            x[0] + x[1] + x[2] + x[3],
+            one - x[2],
+            x[3] - x[0],
+            x[0] + x[1] - fe(2) * x[0] * x[1],
+            x[3] - x[2] * x[3],
+            x[1],
+            fe(5),
+            fe(7) * x[3],
+            x[2] + fe(11),
+        ];
+        assert_eq!(prover.values[1][..expected.len()], expected);
+        for reduction in [ClaimReduction::Combine, ClaimReduction::Line] {
+            assert!(verifies(&c, &x, reduction), "{reduction:?}");
+        }
+    }
+}
+
+#[test]
+fn verifier_reads_the_constant_pool() {
+    let c = all_gates([5, 7, 11]);
+    let x = vec![fe(1), fe(2), fe(3), fe(4)];
+    let proof = Prover::evaluate_with_inputs(&c, &x).prove(&c, &mut Keccak256Transcript::default());
+    // the same gates over a different pool describe a different circuit
+    for constants in [[6, 7, 11], [5, 8, 11], [5, 7, 12], [7, 5, 11]] {
+        let other = all_gates(constants);
+        assert!(
+            !Verifier::verify_proof(
+                &VerifierKey::new(&other),
+                &x,
+                &proof,
+                &mut Keccak256Transcript::default()
+            ),
+            "{constants:?}"
+        );
+    }
+}
+
+#[test]
+fn parse_codes_4_5_6_fill_the_constant_pool() {
+    // layer 0: inputs 3 and 2^100 (too wide for `u`, so a pooled `Const`)
+    // layer 1: 4 → Const 2^100, 5 → 9·V(0), 6 → V(1) + 4, then the identity
+    // on V(0) via ScalarMul by 1
+    let text = format!(
+        "3
+         2  0 0 3 0  0 1 {TWO_TO_100} 0
+         4  4 0 {TWO_TO_100} 0  5 1 0 9  6 2 1 4  5 3 0 1
+         2  1 0 0 1  2 1 2 3"
+    );
+    let layers = LayerReader::new(text.as_bytes())
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let c = Circuit {
+        layers,
+        assertions: Vec::new(),
+    };
+
+    let big: FieldElement = (0..100).fold(fe(1), |x, _| x + x);
+    assert_eq!(c.layers[0].ty[..2], [GateType::Input, GateType::Const]);
+    assert_eq!(c.layers[0].constants, [big]);
+    assert_eq!(
+        c.layers[1].ty,
+        [
+            GateType::Const,
+            GateType::ScalarMul,
+            GateType::AddConst,
+            GateType::ScalarMul
+        ]
+    );
+    // one pool entry per literal, in file order
+    assert_eq!(c.layers[1].constants, [big, fe(9), fe(4), fe(1)]);
+    assert_eq!(c.layers[1].u[0], 0);
+    assert_eq!(c.layers[1].v[1..], [1, 2, 3]);
+
+    let inputs = c.input_values();
+    assert_eq!(inputs[..2], [fe(3), big]);
+    let prover = Prover::evaluate(&c);
+    assert_terms_match(&c, &prover);
+    assert_eq!(prover.values[1], [big, fe(27), big + fe(4), fe(3)]);
+    assert_eq!(prover.outputs(), [big + fe(27), (big + fe(4)) * fe(3)]);
+    for reduction in [ClaimReduction::Combine, ClaimReduction::Line] {
+        assert!(verifies(&c, &inputs, reduction), "{reduction:?}");
+    }
+}
+
+#[test]
+fn structured_layers_over_wider_layers_verify() {
+    // 2 structured gates over 8 inputs, of which they read the first 4
+    for top in [
+        Layer::binary_tree(GateType::Mul, 1),
+        Layer::element_wise(GateType::Xor, 1),
+    ] {
+        let c = Circuit {
+            layers: vec![layer(3, &[(GateType::Input, 0, 0); 8], &[]), top],
+            assertions: Vec::new(),
+        };
+        let x: Vec<_> = (2..10).map(fe).collect();
+        let prover = Prover::evaluate_with_inputs(&c, &x);
+        assert_terms_match(&c, &prover);
+        for reduction in [ClaimReduction::Combine, ClaimReduction::Line] {
+            assert!(
+                verifies(&c, &x, reduction),
+                "{:?}, {reduction:?}",
+                c.layers[1].ty[0]
+            );
+        }
+    }
+}
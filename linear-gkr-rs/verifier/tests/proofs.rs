@@ -0,0 +1,91 @@
+mod common;
+
+use circuit::{compile, Program};
+use field::FieldElement;
+use prover::{Keccak256Transcript, Proof, Prover};
+use verifier::{Verifier, VerifierKey};
+
+use common::field;
+
+const SRC: &str = "input a, b, c, d; let s = a * b + c; output s * d, s + d, a - c;";
+
+fn prove(p: &Program, inputs: &[FieldElement]) -> (Vec<FieldElement>, Proof) {
+    let layer0 = p.assignment(inputs).unwrap();
+    let proof = Prover::evaluate_with_inputs(&p.circuit, &layer0)
+        .prove(&p.circuit, &mut Keccak256Transcript::default());
+    (layer0, proof)
+}
+
+fn verify(p: &Program, layer0: &[FieldElement], proof: &Proof) -> bool {
+    Verifier::verify_proof(
+        &VerifierKey::new(&p.circuit),
+        layer0,
+        proof,
+        &mut Keccak256Transcript::default(),
+    )
+}
+
+#[test]
+fn honest_proof_verifies() {
+    let p = compile(SRC).unwrap();
+    let (layer0, proof) = prove(&p, &field(&[3, 5, 7, 11]));
+    assert_eq!(proof.layers.len(), p.circuit.layers.len() - 1);
+    assert!(verify(&p, &layer0, &proof));
+}
+
+#[test]
+fn tampered_messages_are_rejected() {
+    let p = compile(SRC).unwrap();
+    let (layer0, proof) = prove(&p, &field(&[3, 5, 7, 11]));
+
+    let mut bad = prove(&p, &field(&[3, 5, 7, 11])).1;
+    bad.outputs[0] += FieldElement::from(1u64);
+    assert!(!verify(&p, &layer0, &bad));
+
+    for i in 0..proof.layers.len() {
+        let mut bad = prove(&p, &field(&[3, 5, 7, 11])).1;
+        bad.layers[i].rounds[0].a += FieldElement::from(1u64);
+        assert!(!verify(&p, &layer0, &bad), "round 0 of layer proof {i}");
+
+        let mut bad = prove(&p, &field(&[3, 5, 7, 11])).1;
+        bad.layers[i].v_u += FieldElement::from(1u64);
+        assert!(!verify(&p, &layer0, &bad), "v_u of layer proof {i}");
+
+        let mut bad = prove(&p, &field(&[3, 5, 7, 11])).1;
+        bad.layers[i].v_v += FieldElement::from(1u64);
+        assert!(!verify(&p, &layer0, &bad), "v_v of layer proof {i}");
+    }
+
+    let mut bad = prove(&p, &field(&[3, 5, 7, 11])).1;
+    bad.layers.pop();
+    assert!(!verify(&p, &layer0, &bad));
+}
+
+#[test]
+fn proof_is_bound_to_its_inputs() {
+    let p = compile(SRC).unwrap();
+    let (_, proof) = prove(&p, &field(&[3, 5, 7, 11]));
+    let other = p.assignment(&field(&[3, 5, 7, 12])).unwrap();
+    assert!(!verify(&p, &other, &proof));
+}
+
+#[test]
+fn proof_is_bound_to_its_circuit() {
+    // Same shape and inputs, different wiring in the last layer.
+    let p = compile(SRC).unwrap();
+    let q = compile("input a, b, c, d; let s = a * b + c; output s * d, s - d, a - c;").unwrap();
+    let inputs = field(&[3, 5, 7, 11]);
+    let (layer0, proof) = prove(&p, &inputs);
+    assert_eq!(layer0, q.assignment(&inputs).unwrap());
+    assert!(!verify(&q, &layer0, &proof));
+}
+
+#[test]
+fn too_many_inputs_are_rejected() {
+    let p = compile(SRC).unwrap();
+    let (layer0, proof) = prove(&p, &field(&[3, 5, 7, 11]));
+    let mut long = p.circuit.pad_inputs(&layer0);
+    assert!(verify(&p, &long, &proof));
+    long.push(FieldElement::from(0u64));
+    assert!(!verify(&p, &long, &proof));
+}
@@ -0,0 +1,104 @@
+//! Circom imports: `examples/multiplier.r1cs` holds the constraints of
+//!
+//! ```circom
+//! signal input a;          // public
+//! signal input b;          // private
+//! signal output c <== a * b;
+//! signal output s <== a - b;
+//! ```
+//!
+//! over the wires `1, c, s, a, b`, and `examples/multiplier.wtns` the witness
+//! for `a = 3`, `b = 11`.
+
+use circuit::{load_circom, load_wtns, Circuit, R1cs};
+use field::FieldElement;
+use prover::{Keccak256Transcript, Prover};
+use verifier::{Verifier, VerifierKey};
+
+const R1CS: &str = "../examples/multiplier.r1cs";
+const WTNS: &str = "../examples/multiplier.wtns";
+
+fn accepts(witness: &[FieldElement]) -> bool {
+    let cs = R1cs::load(R1CS).unwrap();
+    proves(&cs.to_circuit(), &cs.assignment(witness).unwrap())
+}
+
+/// Prove and verify `c` on `inputs`, bypassing [`R1cs::assignment`].
+fn proves(c: &Circuit, inputs: &[FieldElement]) -> bool {
+    let proof =
+        Prover::evaluate_with_inputs(c, inputs).prove(c, &mut Keccak256Transcript::default());
+    Verifier::verify_proof(
+        &VerifierKey::new(c),
+        inputs,
+        &proof,
+        &mut Keccak256Transcript::default(),
+    )
+}
+
+#[test]
+fn fixture_is_read() {
+    let cs = R1cs::load(R1CS).unwrap();
+    assert_eq!(cs.num_wires, 5);
+    assert_eq!((cs.num_pub_out, cs.num_pub_in, cs.num_prv_in), (2, 1, 1));
+    assert_eq!(cs.constraints.len(), 2);
+    assert_eq!(cs.constraints[1].a[1], (4, -FieldElement::from(1u64)));
+
+    let witness = load_wtns(WTNS).unwrap();
+    let expected = [1, 33, 0, 3, 11].map(FieldElement::from);
+    assert_eq!(witness[..2], expected[..2]);
+    assert_eq!(witness[2], -FieldElement::from(8u64));
+    assert_eq!(witness[3..], expected[3..]);
+}
+
+#[test]
+fn satisfying_witness_verifies() {
+    let (c, inputs) = load_circom(R1CS, WTNS).unwrap();
+    // every output is asserted, so nothing is sent
+    assert_eq!(c.assertions.len(), c.layers[c.layers.len() - 1].len());
+    let proof =
+        Prover::evaluate_with_inputs(&c, &inputs).prove(&c, &mut Keccak256Transcript::default());
+    assert!(proof.outputs.is_empty());
+    assert!(accepts(&load_wtns(WTNS).unwrap()));
+}
+
+#[test]
+fn broken_witness_is_rejected() {
+    let witness = load_wtns(WTNS).unwrap();
+    for wire in 1..witness.len() {
+        let mut broken = witness.clone();
+        broken[wire] += FieldElement::from(1u64);
+        assert!(!accepts(&broken), "wire {wire}");
+    }
+    // a different but consistent witness is fine
+    let (a, b) = (FieldElement::from(5u64), FieldElement::from(2u64));
+    assert!(accepts(&[FieldElement::from(1u64), a * b, a - b, a, b]));
+}
+
+#[test]
+fn constant_wire_must_be_one() {
+    let cs = R1cs::load(R1CS).unwrap();
+    let c = cs.to_circuit();
+    let witness = load_wtns(WTNS).unwrap();
+
+    // the zero witness satisfies every constraint A·z ∘ B·z = C·z; only the
+    // z₀ − 1 output tells it apart
+    let zero = vec![FieldElement::from(0u64); witness.len()];
+    assert!(cs.assignment(&zero).is_err());
+    assert!(!proves(&c, &zero));
+
+    let mut broken = witness.clone();
+    broken[0] += FieldElement::from(1u64);
+    assert!(cs.assignment(&broken).is_err());
+    assert!(!proves(&c, &broken));
+}
+
+#[test]
+fn malformed_files_are_errors() {
+    let r1cs = std::fs::read(R1CS).unwrap();
+    assert!(R1cs::from_reader(&r1cs[..r1cs.len() - 1]).is_err());
+    let mut bad_magic = r1cs.clone();
+    bad_magic[0] = b'x';
+    assert!(R1cs::from_reader(&bad_magic[..]).is_err());
+    let cs = R1cs::load(R1CS).unwrap();
+    assert!(cs.assignment(&[FieldElement::from(1u64)]).is_err());
+}
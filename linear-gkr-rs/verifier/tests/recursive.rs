@@ -0,0 +1,91 @@
+//! The verifier as a circuit accepts exactly the proofs the verifier does.
+
+mod common;
+
+use ark_ff::One;
+use circuit::{Builder, Circuit};
+use field::FieldElement;
+use prover::{ClaimReduction, Keccak256Transcript, MimcTranscript, Proof, Prover};
+use verifier::{Verifier, VerifierCircuit, VerifierKey};
+
+use common::field;
+
+/// `x0·x1 + x1`.
+fn circuit() -> Circuit {
+    let mut b = Builder::new();
+    let x = b.inputs(2);
+    let p = b.mul(x[0], x[1]);
+    let s = b.add(p, x[1]);
+    b.output(s);
+    b.build()
+}
+
+fn prove(c: &Circuit, inputs: &[FieldElement]) -> Proof {
+    Prover::evaluate_with_inputs(c, inputs).prove(c, &mut MimcTranscript::default())
+}
+
+/// Whether the verifier circuit's checks all come out zero on `proof`.
+fn accepts(vc: &VerifierCircuit, inputs: &[FieldElement], proof: &Proof) -> bool {
+    let assignment = vc
+        .assignment(inputs, proof)
+        .expect("proof of the right shape");
+    let prover = Prover::evaluate_with_inputs(&vc.circuit, &assignment);
+    VerifierCircuit::accepts(prover.outputs())
+}
+
+#[test]
+fn honest_proofs_are_accepted() {
+    let c = circuit();
+    let vc = VerifierCircuit::new(&c);
+    // inputs wait in relays until absorbed, and so do the round keys `s + cᵢ`
+    // unless the builder computes them where they are read
+    let widest = vc.circuit.layers.iter().map(|l| l.bit_length).max();
+    assert!(widest <= Some(5), "{widest:?}");
+
+    let inputs = field(&[3, 4]);
+    let proof = prove(&c, &inputs);
+    assert!(Verifier::verify_proof(
+        &VerifierKey::new(&c),
+        &inputs,
+        &proof,
+        &mut MimcTranscript::default()
+    ));
+    assert!(accepts(&vc, &inputs, &proof));
+}
+
+#[test]
+fn tampered_proofs_are_rejected() {
+    let c = circuit();
+    let vc = VerifierCircuit::new(&c);
+    let inputs = field(&[3, 4]);
+    let one = FieldElement::one();
+
+    let mut proof = prove(&c, &inputs);
+    proof.layers[0].v_u += one;
+    assert!(!Verifier::verify_proof(
+        &VerifierKey::new(&c),
+        &inputs,
+        &proof,
+        &mut MimcTranscript::default()
+    ));
+    assert!(!accepts(&vc, &inputs, &proof));
+
+    let mut proof = prove(&c, &inputs);
+    proof.layers[1].rounds[0].b += one;
+    assert!(!accepts(&vc, &inputs, &proof));
+
+    // other inputs, or a proof on another transcript
+    let proof = prove(&c, &inputs);
+    assert!(!accepts(&vc, &field(&[3, 5]), &proof));
+    let proof =
+        Prover::evaluate_with_inputs(&c, &inputs).prove(&c, &mut Keccak256Transcript::default());
+    assert!(!accepts(&vc, &inputs, &proof));
+
+    // line reductions are not replayed
+    let proof = Prover::evaluate_with_inputs(&c, &inputs).prove_with_reduction(
+        &c,
+        ClaimReduction::Line,
+        &mut MimcTranscript::default(),
+    );
+    assert!(vc.assignment(&inputs, &proof).is_none());
+}
@@ -0,0 +1,116 @@
+//! Claim reduction by line restriction, and the binding of the reduction
+//! mode into the transcript.
+
+mod common;
+
+use ark_ff::One;
+use circuit::{load_from_path, Builder, Circuit};
+use field::FieldElement;
+use prover::{ClaimReduction, Keccak256Transcript, Proof, Prover};
+use verifier::{Verifier, VerifierKey};
+
+use common::field;
+
+/// Layers of 3, 2 and 1 bits and a 1‑bit layer directly above the inputs of
+/// `narrow`, where the line has no points beyond `v_u`, `v_v`.
+fn circuits() -> Vec<(Circuit, Vec<FieldElement>)> {
+    let mut b = Builder::new();
+    let x = b.inputs(5);
+    let p = b.mul(x[0], x[1]);
+    let s = b.add(x[2], x[3]);
+    let q = b.mul(p, s);
+    let t = b.sub(q, x[4]);
+    b.output(t);
+    b.output(s);
+    let wide = b.build();
+
+    let mut b = Builder::new();
+    let x = b.inputs(2);
+    let p = b.mul(x[0], x[1]);
+    b.output(p);
+    let narrow = b.build();
+
+    let example = load_from_path("../examples/test_circuit.txt").unwrap();
+    let example_inputs = example.input_values();
+    vec![
+        (wide, field(&[2, 3, 5, 7, 11])),
+        (narrow, field(&[6, 9])),
+        (example, example_inputs),
+    ]
+}
+
+fn prove(c: &Circuit, inputs: &[FieldElement], reduction: ClaimReduction) -> Proof {
+    Prover::evaluate_with_inputs(c, inputs).prove_with_reduction(
+        c,
+        reduction,
+        &mut Keccak256Transcript::default(),
+    )
+}
+
+fn accepts(c: &Circuit, inputs: &[FieldElement], proof: &Proof) -> bool {
+    Verifier::verify_proof(
+        &VerifierKey::new(c),
+        inputs,
+        proof,
+        &mut Keccak256Transcript::default(),
+    )
+}
+
+#[test]
+fn line_proofs_verify() {
+    for (c, inputs) in circuits() {
+        let proof = prove(&c, &inputs, ClaimReduction::Line);
+        assert_eq!(proof.reduction, ClaimReduction::Line);
+        // q(2..=b) on every layer below a sum‑check of 2b rounds
+        for lp in &proof.layers {
+            assert_eq!(lp.line.len(), (lp.rounds.len() / 2).saturating_sub(1));
+        }
+        assert!(accepts(&c, &inputs, &proof));
+
+        let mut wrong = inputs.clone();
+        wrong[0] += FieldElement::one();
+        assert!(!accepts(&c, &wrong, &proof));
+    }
+}
+
+#[test]
+fn tampered_lines_are_rejected() {
+    let (c, inputs) = circuits().remove(0);
+    let layers = prove(&c, &inputs, ClaimReduction::Line).layers;
+    for (i, lp) in layers.iter().enumerate() {
+        for k in 0..lp.line.len() {
+            let mut proof = prove(&c, &inputs, ClaimReduction::Line);
+            proof.layers[i].line[k] += FieldElement::one();
+            assert!(!accepts(&c, &inputs, &proof), "layer {i}, q({})", k + 2);
+        }
+        let mut proof = prove(&c, &inputs, ClaimReduction::Line);
+        proof.layers[i].line.push(FieldElement::one());
+        assert!(!accepts(&c, &inputs, &proof), "layer {i}, extra point");
+    }
+}
+
+#[test]
+fn the_reduction_mode_is_bound() {
+    let (c, inputs) = circuits().remove(0);
+    let combine = prove(&c, &inputs, ClaimReduction::Combine);
+    let line = prove(&c, &inputs, ClaimReduction::Line);
+    // the mode is absorbed with the outputs, so even the top layer's first
+    // round, which depends only on r₀, differs between the modes
+    let (a, b) = (combine.layers[0].rounds[0], line.layers[0].rounds[0]);
+    assert_ne!((a.a, a.b, a.c), (b.a, b.b, b.c));
+
+    // relabelled proofs, with the line restrictions dropped or filled in
+    let mut relabelled = prove(&c, &inputs, ClaimReduction::Line);
+    relabelled.reduction = ClaimReduction::Combine;
+    for lp in &mut relabelled.layers {
+        lp.line.clear();
+    }
+    assert!(!accepts(&c, &inputs, &relabelled));
+
+    let mut relabelled = prove(&c, &inputs, ClaimReduction::Combine);
+    relabelled.reduction = ClaimReduction::Line;
+    for (lp, honest) in relabelled.layers.iter_mut().zip(&line.layers) {
+        lp.line.clone_from(&honest.line);
+    }
+    assert!(!accepts(&c, &inputs, &relabelled));
+}
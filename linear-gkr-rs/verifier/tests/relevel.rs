@@ -0,0 +1,83 @@
+//! Re‑leveled circuits compute the same outputs and still prove.
+
+mod common;
+
+use circuit::{compile, relevel, Circuit};
+use field::{seeded_rng, FieldElement};
+use prover::{ClaimReduction, Keccak256Transcript, Prover};
+use verifier::{Verifier, VerifierKey};
+
+use common::random_circuit;
+
+fn proves(c: &Circuit, inputs: &[FieldElement], reduction: ClaimReduction) -> bool {
+    let prover = Prover::evaluate_with_inputs(c, inputs);
+    let proof = prover.prove_with_reduction(c, reduction, &mut Keccak256Transcript::default());
+    Verifier::verify_proof(
+        &VerifierKey::new(c),
+        inputs,
+        &proof,
+        &mut Keccak256Transcript::default(),
+    )
+}
+
+#[test]
+fn random_circuits_keep_their_outputs_and_prove() {
+    for seed in 0..300 {
+        let c = random_circuit(seed);
+        let inputs = c.random_inputs(&mut seeded_rng(seed ^ 0x5eed));
+        let outputs = Prover::evaluate_with_inputs(&c, &inputs).outputs().to_vec();
+        let assertions = c.assertions.clone();
+
+        let (r, report) = relevel(random_circuit(seed));
+        assert_eq!(r.assertions, assertions, "seed {seed}");
+        assert_eq!(report.depth_after, r.layers.len(), "seed {seed}");
+        assert!(
+            report.slots_after <= report.slots_before,
+            "seed {seed}: {report}"
+        );
+        assert_eq!(
+            r.layers[0].bit_length, c.layers[0].bit_length,
+            "seed {seed}"
+        );
+        let after = Prover::evaluate_with_inputs(&r, &inputs);
+        assert_eq!(after.outputs(), outputs, "seed {seed}");
+
+        let reduction = [ClaimReduction::Combine, ClaimReduction::Line][seed as usize % 2];
+        assert!(proves(&r, &inputs, reduction), "seed {seed}");
+    }
+}
+
+#[test]
+fn relays_and_slots_drop_on_a_relay_heavy_circuit() {
+    // The `k`s only need `y`, so ASAP computes them at level 1 and relays
+    // them up to the top next to the relays of `y` itself, which pads the
+    // layers to eight slots; re‑leveling computes them from `y` just below
+    // their readers.
+    let program = compile(
+        "input x, y;
+         let a1 = x * x + 1;
+         let a2 = a1 * a1 + 1;
+         let a3 = a2 * a2 + 1;
+         let a4 = a3 * a3 + 1;
+         let a5 = a4 * a4 + 1;
+         let a6 = a5 * a5 + 1;
+         let k1 = y * y;
+         let k2 = y + y;
+         let k3 = y * y * y;
+         output a6 * k1 + y, a6 * k2, a6 * k3;",
+    )
+    .unwrap();
+    let inputs = program
+        .assignment(&[FieldElement::from(2u64), FieldElement::from(5u64)])
+        .unwrap();
+    let outputs = Prover::evaluate_with_inputs(&program.circuit, &inputs)
+        .outputs()
+        .to_vec();
+
+    let (r, report) = relevel(program.circuit);
+    assert!(report.relays_after < report.relays_before, "{report}");
+    assert!(report.slots_after < report.slots_before, "{report}");
+    assert_eq!(report.depth_after, report.depth_before);
+    assert_eq!(Prover::evaluate_with_inputs(&r, &inputs).outputs(), outputs);
+    assert!(proves(&r, &inputs, ClaimReduction::Combine));
+}
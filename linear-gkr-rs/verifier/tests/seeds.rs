@@ -0,0 +1,52 @@
+//! A seed replays a run: the same seed draws the same circuit, inputs and
+//! proof, as the CLI's `--seed` relies on.
+
+mod common;
+
+use circuit::{Circuit, Layered};
+use field::{seeded_rng, FieldElement};
+use prover::{Keccak256Transcript, Proof, Prover};
+use verifier::{Verifier, VerifierKey};
+
+use common::random_circuit;
+
+/// `slow_track --seed`: inputs drawn from the seed, then a proof on them.
+fn run(c: &Circuit, seed: u64) -> (Vec<FieldElement>, Proof) {
+    let inputs = c.random_inputs(&mut seeded_rng(seed));
+    let proof =
+        Prover::evaluate_with_inputs(c, &inputs).prove(c, &mut Keccak256Transcript::default());
+    (inputs, proof)
+}
+
+/// Every field element of `proof`, in order.
+fn messages(proof: &Proof) -> Vec<FieldElement> {
+    let mut all = proof.outputs.clone();
+    for lp in &proof.layers {
+        all.extend(lp.rounds.iter().flat_map(|q| [q.a, q.b, q.c]));
+        all.extend([lp.v_u, lp.v_v]);
+        all.extend(&lp.line);
+    }
+    all
+}
+
+#[test]
+fn a_seed_replays_inputs_and_proof() {
+    for seed in 0..16 {
+        let c = random_circuit(seed);
+        assert_eq!(c.digest(), random_circuit(seed).digest(), "seed {seed}");
+
+        let (inputs, proof) = run(&c, seed);
+        let (again, replay) = run(&c, seed);
+        assert_eq!(inputs, again, "seed {seed}");
+        assert_eq!(messages(&proof), messages(&replay), "seed {seed}");
+        assert!(Verifier::verify_proof(
+            &VerifierKey::new(&c),
+            &inputs,
+            &proof,
+            &mut Keccak256Transcript::default()
+        ));
+
+        let (other, _) = run(&c, seed + 100);
+        assert_ne!(inputs, other, "seeds {seed} and {}", seed + 100);
+    }
+}
@@ -0,0 +1,152 @@
+//! Runs the generated Solidity verifier in revm against the Rust verifier.
+//!
+//! The generated source is compared against the snapshot in
+//! `data/GkrVerifier.sol`, and the contract test runs the runtime bytecode
+//! compiled from that snapshot, `data/GkrVerifier.bin`. Both are written by
+//! `data/regenerate.sh`, which needs `solc` 0.8.20 or later; set
+//! `UPDATE_SNAPSHOT=1` to rewrite the source snapshot alone.
+
+use ark_ff::One;
+use circuit::{Builder, Circuit};
+use field::FieldElement;
+use prover::{Keccak256Transcript, Proof, Prover};
+use revm::db::{CacheDB, EmptyDB};
+use revm::primitives::{
+    AccountInfo, Address, Bytecode, Bytes, ExecutionResult, Output, TransactTo, U256,
+};
+use revm::Evm;
+use verifier::{calldata, solidity_verifier, Verifier, VerifierKey};
+
+/// EIP‑170's limit on deployed code.
+const MAX_CODE_SIZE: usize = 0x6000;
+
+/// The snapshot of the contract generated for [`circuit`].
+const SOURCE: &str = include_str!("data/GkrVerifier.sol");
+
+/// Runtime bytecode `solc --optimize --bin-runtime` made of [`SOURCE`],
+/// written by `data/regenerate.sh`.
+fn bytecode() -> Vec<u8> {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/GkrVerifier.bin");
+    let hex = std::fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("{path}: {e}; run tests/data/regenerate.sh"));
+    let hex = hex.trim();
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+/// Result of calling `verify` on `code` with `data`.
+fn call(code: &[u8], data: Vec<u8>) -> bool {
+    let addr = Address::repeat_byte(0x42);
+    let mut db = CacheDB::new(EmptyDB::default());
+    let code = Bytecode::new_raw(Bytes::copy_from_slice(code));
+    let info = AccountInfo::new(U256::ZERO, 0, code.hash_slow(), code);
+    db.insert_account_info(addr, info);
+    let mut evm = Evm::builder()
+        .with_db(db)
+        .modify_tx_env(|tx| {
+            tx.transact_to = TransactTo::Call(addr);
+            tx.data = data.into();
+            tx.gas_limit = 1_000_000_000;
+        })
+        .modify_block_env(|block| block.gas_limit = U256::MAX)
+        .build();
+    match evm.transact().unwrap().result {
+        ExecutionResult::Success {
+            output: Output::Call(out),
+            ..
+        } => out.len() == 32 && out[31] == 1,
+        _ => false,
+    }
+}
+
+/// `(x0·x1 − 3·x2 + 5)·(x1 + x3) − x0`, exercising every coefficient shape.
+fn circuit() -> Circuit {
+    let mut b = Builder::new();
+    let x = b.inputs(4);
+    let m = b.mul(x[0], x[1]);
+    let s = b.scale(x[2], FieldElement::from(3u64));
+    let d = b.sub(m, s);
+    let e = b.add_const(d, FieldElement::from(5u64));
+    let f = b.add(x[1], x[3]);
+    let g = b.mul(e, f);
+    let h = b.sub(g, x[0]);
+    b.output(h);
+    b.output(e);
+    b.build()
+}
+
+fn prove(c: &Circuit, inputs: &[FieldElement]) -> Proof {
+    Prover::evaluate_with_inputs(c, inputs).prove(c, &mut Keccak256Transcript::default())
+}
+
+fn rust_accepts(c: &Circuit, inputs: &[FieldElement], proof: &Proof) -> bool {
+    Verifier::verify_proof(
+        &VerifierKey::new(c),
+        inputs,
+        proof,
+        &mut Keccak256Transcript::default(),
+    )
+}
+
+/// An honest proof, then proofs or inputs tampered with; only the first is
+/// valid.
+fn cases(c: &Circuit) -> Vec<(Vec<FieldElement>, Proof)> {
+    let inputs: Vec<_> = [2u64, 7, 3, 11]
+        .into_iter()
+        .map(FieldElement::from)
+        .collect();
+    let mut other = inputs.clone();
+    other[3] += FieldElement::one();
+    let tampered = |f: fn(&mut Proof)| {
+        let mut p = prove(c, &inputs);
+        f(&mut p);
+        (inputs.clone(), p)
+    };
+    vec![
+        tampered(|_| {}),
+        tampered(|p| p.outputs[0] += FieldElement::one()),
+        tampered(|p| p.layers[1].rounds[0].b += FieldElement::one()),
+        tampered(|p| p.layers.last_mut().unwrap().v_v += FieldElement::one()),
+        (other, prove(c, &inputs)),
+    ]
+}
+
+#[test]
+fn rust_verifier_accepts_only_the_honest_case() {
+    let c = circuit();
+    for (k, (inputs, proof)) in cases(&c).iter().enumerate() {
+        assert_eq!(rust_accepts(&c, inputs, proof), k == 0, "case {k}");
+    }
+}
+
+#[test]
+fn contract_source_matches_snapshot() {
+    let source = solidity_verifier(&circuit(), "GkrVerifier");
+    if std::env::var_os("UPDATE_SNAPSHOT").is_some() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/GkrVerifier.sol");
+        std::fs::write(path, &source).unwrap();
+        return;
+    }
+    assert!(
+        source == SOURCE,
+        "generated contract differs from tests/data/GkrVerifier.sol; \
+         run tests/data/regenerate.sh and review the diff"
+    );
+}
+
+#[test]
+#[ignore = "needs tests/data/GkrVerifier.bin, compiled by tests/data/regenerate.sh"]
+fn contract_matches_rust_verifier() {
+    let c = circuit();
+    let code = bytecode();
+    assert!(
+        code.len() <= MAX_CODE_SIZE,
+        "{} bytes of runtime code, over EIP-170's {MAX_CODE_SIZE}",
+        code.len()
+    );
+    for (k, (inputs, proof)) in cases(&c).iter().enumerate() {
+        assert_eq!(call(&code, calldata(inputs, proof)), k == 0, "case {k}");
+    }
+}
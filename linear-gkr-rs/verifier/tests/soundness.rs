@@ -0,0 +1,167 @@
+//! Soundness and completeness over randomly generated circuits.
+//!
+//! An [`Adversary`] proves honestly except for one [`Attack`]: a wrong gate
+//! value (with every layer above evaluated from it, so the lie is consistent
+//! up to the outputs) or a tampered proof message. Every attack, at every
+//! layer, must be rejected. Circuits and inputs come from seeded RNGs, so a
+//! failure names the seed that replays it.
+
+mod common;
+
+use ark_ff::One;
+use circuit::Circuit;
+use field::{seeded_rng, FieldElement};
+use prover::{ClaimReduction, Keccak256Transcript, Proof, Prover};
+use verifier::{Verifier, VerifierKey};
+
+use common::random_circuit;
+
+const REDUCTIONS: [ClaimReduction; 2] = [ClaimReduction::Combine, ClaimReduction::Line];
+
+/// A layer‑0 assignment for `random_circuit(seed)`.
+fn random_inputs(c: &Circuit, seed: u64) -> Vec<FieldElement> {
+    c.random_inputs(&mut seeded_rng(seed ^ 0x5eed))
+}
+
+/// The one thing an [`Adversary`] gets wrong. Layers are circuit layers for
+/// gate values and proof layers (from the output layer down) for messages.
+#[derive(Clone, Copy, Debug)]
+enum Attack {
+    /// Gate `gate` of `layer` off by one, the layers above evaluated from it.
+    GateValue { layer: usize, gate: usize },
+    /// Sum‑check message `round`, changed so that `p(0) + p(1)` still holds.
+    Round { layer: usize, round: usize },
+    /// The claimed `Ṽ(r_u)`.
+    VU { layer: usize },
+    /// The claimed `Ṽ(r_v)`.
+    VV { layer: usize },
+    /// Evaluation `k` of the line restriction ([`ClaimReduction::Line`]).
+    Line { layer: usize, k: usize },
+    /// Sent output `k`.
+    Output { k: usize },
+}
+
+/// Every attack on the proof of `c` (`honest` is its honest proof).
+fn attacks(c: &Circuit, honest: &Proof) -> Vec<Attack> {
+    let top = c.layers.len() - 1;
+    let mut attacks = Vec::new();
+    for (layer, l) in c.layers.iter().enumerate() {
+        // asserted outputs are not sent, so there is no lying about them
+        let asserted = |gate: &usize| layer == top && c.assertions.contains(gate);
+        attacks.extend(
+            (0..l.len())
+                .filter(|g| !asserted(g))
+                .map(|gate| Attack::GateValue { layer, gate }),
+        );
+    }
+    for (layer, lp) in honest.layers.iter().enumerate() {
+        attacks.extend((0..lp.rounds.len()).map(|round| Attack::Round { layer, round }));
+        attacks.extend([Attack::VU { layer }, Attack::VV { layer }]);
+        attacks.extend((0..lp.line.len()).map(|k| Attack::Line { layer, k }));
+    }
+    attacks.extend((0..honest.outputs.len()).map(|k| Attack::Output { k }));
+    attacks
+}
+
+/// A prover that carries out `attack` on `random_circuit(seed)`.
+struct Adversary {
+    seed: u64,
+    reduction: ClaimReduction,
+    attack: Attack,
+}
+
+impl Adversary {
+    fn prove(&self, inputs: &[FieldElement]) -> Proof {
+        let c = random_circuit(self.seed);
+        let one = FieldElement::one();
+        let mut prover = Prover::evaluate_with_inputs(&c, inputs);
+        if let Attack::GateValue { layer, gate } = self.attack {
+            prover.values[layer][gate] += one;
+            let mut upper = random_circuit(self.seed);
+            upper.layers.drain(..layer);
+            let above = Prover::evaluate_with_inputs(&upper, &prover.values[layer]);
+            prover.values.truncate(layer);
+            prover.values.extend(above.values);
+        }
+        let mut transcript = Keccak256Transcript::default();
+        let mut proof = prover.prove_with_reduction(&c, self.reduction, &mut transcript);
+        match self.attack {
+            Attack::GateValue { .. } => {}
+            Attack::Round { layer, round } => {
+                let p = &mut proof.layers[layer].rounds[round];
+                p.a += one;
+                p.b -= one;
+            }
+            Attack::VU { layer } => proof.layers[layer].v_u += one,
+            Attack::VV { layer } => proof.layers[layer].v_v += one,
+            Attack::Line { layer, k } => proof.layers[layer].line[k] += one,
+            Attack::Output { k } => proof.outputs[k] += one,
+        }
+        proof
+    }
+}
+
+fn accepts(c: &Circuit, inputs: &[FieldElement], proof: &Proof) -> bool {
+    Verifier::verify_proof(
+        &VerifierKey::new(c),
+        inputs,
+        proof,
+        &mut Keccak256Transcript::default(),
+    )
+}
+
+#[test]
+fn honest_proofs_of_random_circuits_verify() {
+    for seed in 0..64 {
+        let c = random_circuit(seed);
+        let inputs = random_inputs(&c, seed);
+        let prover = Prover::evaluate_with_inputs(&c, &inputs);
+        for reduction in REDUCTIONS {
+            let mut transcript = Keccak256Transcript::default();
+            let proof = prover.prove_with_reduction(&c, reduction, &mut transcript);
+            assert!(
+                accepts(&c, &inputs, &proof),
+                "seed {seed}, {reduction:?}: honest proof rejected"
+            );
+        }
+    }
+}
+
+#[test]
+fn every_attack_is_rejected() {
+    for seed in 0..8 {
+        let c = random_circuit(seed);
+        let inputs = random_inputs(&c, seed);
+        let prover = Prover::evaluate_with_inputs(&c, &inputs);
+        for reduction in REDUCTIONS {
+            let mut transcript = Keccak256Transcript::default();
+            let honest = prover.prove_with_reduction(&c, reduction, &mut transcript);
+            for attack in attacks(&c, &honest) {
+                let adversary = Adversary {
+                    seed,
+                    reduction,
+                    attack,
+                };
+                assert!(
+                    !accepts(&c, &inputs, &adversary.prove(&inputs)),
+                    "seed {seed}, {reduction:?}: {attack:?} accepted"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn wrong_inputs_are_rejected() {
+    for seed in 0..8 {
+        let c = random_circuit(seed);
+        let inputs = random_inputs(&c, seed);
+        let proof = Prover::evaluate_with_inputs(&c, &inputs)
+            .prove(&c, &mut Keccak256Transcript::default());
+        let other = random_inputs(&c, seed + 1);
+        assert!(
+            !accepts(&c, &other, &proof),
+            "seed {seed}: wrong inputs accepted"
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! Statements with private inputs and hidden outputs: the verifier sees only
+//! the public inputs and revealed outputs, and checks the rest through
+//! commitment openings.
+
+mod common;
+
+use circuit::{Builder, Circuit, Layered};
+use commit::commit;
+use field::FieldElement;
+use polynomial::mle::{eq_at, evaluate};
+use prover::{CommittedTable, Keccak256Transcript, Prover, Statement, StatementProof, Transcript};
+use verifier::{Verifier, VerifierKey};
+
+use common::field;
+
+/// Public `x0`, private `x1, x2`; outputs `x0·x1` (revealed), `x1 + x2`
+/// (hidden) and `x0·x2 − x1` (asserted).
+fn circuit() -> Circuit {
+    let mut b = Builder::new();
+    let x = b.inputs(3);
+    let p = b.mul(x[0], x[1]);
+    b.output(p);
+    let s = b.add(x[1], x[2]);
+    b.output(s);
+    let m = b.mul(x[0], x[2]);
+    let d = b.sub(m, x[1]);
+    b.assert_zero(d);
+    b.build()
+}
+
+fn statement() -> Statement {
+    Statement {
+        public_inputs: vec![0],
+        revealed_outputs: vec![0],
+    }
+}
+
+fn prove(c: &Circuit, statement: &Statement, inputs: &[FieldElement]) -> StatementProof {
+    Prover::evaluate_with_inputs(c, inputs).prove_statement(
+        c,
+        statement,
+        &mut Keccak256Transcript::default(),
+    )
+}
+
+fn accepts(
+    c: &Circuit,
+    statement: &Statement,
+    public_inputs: &[FieldElement],
+    proof: &StatementProof,
+) -> bool {
+    let mut transcript = Keccak256Transcript::default();
+    Verifier::verify_statement(
+        &VerifierKey::new(c),
+        statement,
+        public_inputs,
+        proof,
+        &mut transcript,
+    )
+}
+
+/// A proof for `statement` from a prover that evaluates `c` on `inputs` but
+/// binds the transcript to the claimed `public` values. It commits the true
+/// private inputs and hidden outputs and, if `forge_evals`, claims for them
+/// whatever the checks on the GKR proof need: `Ṽ₀` and the output layer's MLE
+/// minus the public and revealed parts, as with a committed table that has
+/// the true inputs on public slots and nonzero assertions.
+fn forge(
+    c: &Circuit,
+    statement: &Statement,
+    public: &[FieldElement],
+    inputs: &[FieldElement],
+    forge_evals: bool,
+) -> StatementProof {
+    let prover = Prover::evaluate_with_inputs(c, inputs);
+    let layers = [&prover.values[0][..], prover.outputs()];
+    let slots = [statement.private_inputs(c), statement.hidden_outputs(c)];
+    let sent = [
+        &statement.public_inputs[..],
+        &statement.revealed_outputs[..],
+    ];
+    let gens = Statement::generators(c);
+    let tables = [0, 1].map(|k| {
+        let mut table: Vec<_> = slots[k].iter().map(|&g| layers[k][g]).collect();
+        table.resize(table.len().next_power_of_two(), FieldElement::from(0u64));
+        table
+    });
+    let commitments = tables.each_ref().map(|t| commit(&gens, t));
+
+    let mut transcript = Keccak256Transcript::default();
+    statement.bind(c, c.digest(), public, &mut transcript);
+    for commitment in &commitments {
+        transcript.absorb(&commitment.to_field_elements());
+    }
+    let (gkr, challenges) = prover.prove_unbound_hiding(c, &slots[1], &mut transcript);
+    let last = challenges.last().unwrap();
+    let points = [
+        vec![&last.ru[..], &last.rv[..]],
+        vec![&challenges[0].r0[..]],
+    ];
+    let sent_values = [public.to_vec(), gkr.outputs.clone()];
+    let [private_inputs, hidden_outputs] = [0, 1].map(|k| {
+        let mut table = CommittedTable::prove(
+            commitments[k].clone(),
+            &tables[k],
+            &slots[k],
+            &points[k],
+            &mut transcript,
+        );
+        if forge_evals {
+            for (eval, r) in table.evals.iter_mut().zip(&points[k]) {
+                let sent: FieldElement = sent[k]
+                    .iter()
+                    .zip(&sent_values[k])
+                    .map(|(&g, &x)| eq_at(r, g) * x)
+                    .sum();
+                *eval = evaluate(layers[k], r) - sent;
+            }
+        }
+        Some(table)
+    });
+    StatementProof {
+        gkr,
+        private_inputs,
+        hidden_outputs,
+    }
+}
+
+#[test]
+fn private_inputs_and_hidden_outputs_verify() {
+    let c = circuit();
+    let statement = statement();
+    // padding slots are private or hidden as well
+    assert_eq!(statement.private_inputs(&c), [1, 2, 3]);
+    assert_eq!(statement.hidden_outputs(&c), [1, 3]);
+    // x0 = 2, x1 = 6, x2 = 3
+    let inputs = field(&[2, 6, 3]);
+    let proof = prove(&c, &statement, &inputs);
+    assert_eq!(proof.gkr.outputs, field(&[12]));
+    assert!(proof.private_inputs.is_some() && proof.hidden_outputs.is_some());
+    assert!(accepts(
+        &c,
+        &statement,
+        &statement.public_values(&inputs),
+        &proof
+    ));
+}
+
+#[test]
+fn tampered_statement_proofs_are_rejected() {
+    let c = circuit();
+    let statement = statement();
+    let inputs = field(&[2, 6, 3]);
+    let proof = prove(&c, &statement, &inputs);
+    assert!(!accepts(&c, &statement, &field(&[3]), &proof));
+
+    let mut bad = prove(&c, &statement, &inputs);
+    bad.private_inputs.as_mut().unwrap().evals[0] += FieldElement::from(1u64);
+    assert!(!accepts(&c, &statement, &field(&[2]), &bad));
+
+    let mut bad = prove(&c, &statement, &inputs);
+    bad.hidden_outputs.as_mut().unwrap().evals[0] += FieldElement::from(1u64);
+    assert!(!accepts(&c, &statement, &field(&[2]), &bad));
+
+    // x0·x2 ≠ x1
+    let inputs = field(&[2, 5, 3]);
+    let proof = prove(&c, &statement, &inputs);
+    assert!(!accepts(&c, &statement, &field(&[2]), &proof));
+}
+
+#[test]
+fn public_statement_commits_nothing() {
+    let c = circuit();
+    let statement = Statement::public(&c);
+    let inputs = field(&[2, 6, 3]);
+    let proof = prove(&c, &statement, &inputs);
+    assert!(proof.private_inputs.is_none() && proof.hidden_outputs.is_none());
+    let plain =
+        Prover::evaluate_with_inputs(&c, &inputs).prove(&c, &mut Keccak256Transcript::default());
+    assert_eq!(proof.gkr.outputs, plain.outputs);
+    assert_eq!(proof.gkr.num_field_elements(), plain.num_field_elements());
+    // bound to the slot lists as well, so it is not the plain proof
+    assert!(!Verifier::verify_proof(
+        &VerifierKey::new(&c),
+        &inputs,
+        &proof.gkr,
+        &mut Keccak256Transcript::default()
+    ));
+    let public_inputs = statement.public_values(&inputs);
+    assert!(accepts(&c, &statement, &public_inputs, &proof));
+}
+
+#[test]
+fn forged_public_inputs_are_rejected() {
+    let c = circuit();
+    let statement = statement();
+    // x0 = 7 for real, with x0·x1 = 49; claimed x0 = 2
+    let inputs = field(&[7, 7, 1]);
+    assert_eq!(
+        Prover::evaluate_with_inputs(&c, &inputs).outputs()[0],
+        field(&[49])[0]
+    );
+    for forge_evals in [false, true] {
+        let proof = forge(&c, &statement, &field(&[2]), &inputs, forge_evals);
+        assert_eq!(proof.gkr.outputs, field(&[49]));
+        assert!(
+            !accepts(&c, &statement, &field(&[2]), &proof),
+            "{forge_evals}"
+        );
+    }
+    // the forger itself is honest about honest values
+    let proof = forge(&c, &statement, &field(&[7]), &inputs, false);
+    assert!(accepts(&c, &statement, &field(&[7]), &proof));
+}
+
+#[test]
+fn forged_assertions_are_rejected() {
+    let c = circuit();
+    let statement = statement();
+    // x0·x2 − x1 = 1
+    let inputs = field(&[2, 5, 3]);
+    for forge_evals in [false, true] {
+        let proof = forge(&c, &statement, &field(&[2]), &inputs, forge_evals);
+        assert!(
+            !accepts(&c, &statement, &field(&[2]), &proof),
+            "{forge_evals}"
+        );
+    }
+}
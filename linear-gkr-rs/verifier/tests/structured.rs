@@ -0,0 +1,124 @@
+//! Structured layers are verified from their closed‑form predicates alone:
+//! with the digest in the key, the verifier never reads their gates.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use circuit::{BinaryTree, Circuit, ElementWise, Gate, GateType, Layer, Wiring, WiringPredicate};
+use field::FieldElement;
+use prover::{Keccak256Transcript, Prover};
+use verifier::{Verifier, VerifierKey};
+
+/// A predicate that counts its evaluations.
+struct Counting<P> {
+    inner: P,
+    calls: Arc<AtomicUsize>,
+}
+
+impl<P: WiringPredicate> WiringPredicate for Counting<P> {
+    fn evaluate(&self, g: &[FieldElement], u: &[FieldElement], v: &[FieldElement]) -> Wiring {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        self.inner.evaluate(g, u, v)
+    }
+}
+
+fn inputs() -> Layer {
+    let mut layer = Layer::dummy(3);
+    for g in 0..8 {
+        layer.set(
+            g,
+            &Gate {
+                ty: GateType::Input,
+                u: g + 2,
+                v: 0,
+            },
+        );
+    }
+    layer
+}
+
+/// 8 inputs, then a tree of 4 products, 2 element‑wise sums and 1 product.
+fn circuit() -> Circuit {
+    Circuit {
+        layers: vec![
+            inputs(),
+            Layer::binary_tree(GateType::Mul, 2),
+            Layer::element_wise(GateType::Add, 1),
+            Layer::binary_tree(GateType::Mul, 0),
+        ],
+        assertions: Vec::new(),
+    }
+}
+
+/// [`circuit`] with every structured layer's gates turned into dummies, so
+/// that a pass over its terms sees nothing, and its predicate counted.
+fn hollow(counters: &[Arc<AtomicUsize>; 3]) -> Circuit {
+    let mut c = circuit();
+    for (i, calls) in (1..4).zip(counters) {
+        let layer = &mut c.layers[i];
+        for g in 0..layer.len() {
+            layer.set(
+                g,
+                &Gate {
+                    ty: GateType::Dummy,
+                    u: 0,
+                    v: 0,
+                },
+            );
+        }
+        let calls = calls.clone();
+        layer.predicate = Some(match i {
+            2 => Box::new(Counting {
+                inner: ElementWise(GateType::Add),
+                calls,
+            }),
+            _ => Box::new(Counting {
+                inner: BinaryTree(GateType::Mul),
+                calls,
+            }),
+        });
+    }
+    c
+}
+
+#[test]
+fn structured_layers_are_verified_without_their_terms() {
+    let c = circuit();
+    let x = c.input_values();
+    let proof = Prover::evaluate_with_inputs(&c, &x).prove(&c, &mut Keccak256Transcript::default());
+    let vk = VerifierKey::new(&c);
+
+    let counters = [0; 3].map(|_| Arc::new(AtomicUsize::new(0)));
+    let hollow = hollow(&counters);
+    let key = VerifierKey {
+        circuit: &hollow,
+        digest: vk.digest,
+    };
+    assert!(Verifier::verify_proof(
+        &key,
+        &x,
+        &proof,
+        &mut Keccak256Transcript::default()
+    ));
+    // one closed‑form evaluation per claim point, and nothing else
+    for (i, calls) in (1..4).zip(&counters) {
+        let calls = calls.load(Ordering::Relaxed);
+        assert!((1..=2).contains(&calls), "layer {i}: {calls} evaluations");
+    }
+
+    // without the predicates, the hollow layers' terms say otherwise
+    let mut bare = hollow;
+    for layer in &mut bare.layers {
+        layer.predicate = None;
+    }
+    let key = VerifierKey {
+        circuit: &bare,
+        digest: vk.digest,
+    };
+    assert!(!Verifier::verify_proof(
+        &key,
+        &x,
+        &proof,
+        &mut Keccak256Transcript::default()
+    ));
+}